@@ -0,0 +1,639 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::archive_ops::{detect_format, entry_path, system_time};
+use crate::commands::constants::{
+    ARCHIVE_SEPARATOR, CONTENT_SNIFF_LEN, MAX_ARCHIVE_DEPTH, MAX_ARCHIVE_ENTRIES,
+    MAX_ARCHIVE_LISTINGS, MAX_SYMLINK_HOPS, MAX_TEXT_READ_LEN,
+};
+use crate::commands::content_type::content_type_of;
+use crate::commands::file_read;
+use crate::commands::file_write;
+use crate::commands::tree_filter::TreeFilter;
+use crate::models::archive::ArchiveFormat;
+use crate::models::error::{AppError, AppResult};
+use crate::models::file_content::FileContent;
+use crate::models::pathinfo::{NodeDetails, PathinfoKind, SymlinkDetails};
+use crate::platforms;
+use flate2::read::MultiGzDecoder;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zip::ZipArchive;
+
+// Listings of the archives being browsed, least recently used first
+static ARCHIVE_LISTINGS: Lazy<Mutex<Vec<Arc<ArchiveListing>>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Splits `archive.zip!/inner/path` into the archive and the path inside it. The
+/// archive is the first part before a `!/` that is an existing file, so folders
+/// with `!` in their names still work; paths without one aren't inside an archive.
+pub(crate) fn split_archive_path(path: &str) -> Option<(PathBuf, String)> {
+    let mut from = 0;
+    while let Some(offset) = path[from..].find('!') {
+        let at = from + offset;
+        let rest = &path[at + 1..];
+        if rest.is_empty() || rest.starts_with(['/', '\\']) {
+            let archive = Path::new(&path[..at]);
+            if archive.is_file() {
+                return Some((archive.to_path_buf(), rest.to_string()));
+            }
+        }
+        from = at + 1;
+    }
+    None
+}
+
+/// The path of an entry inside an archive, as `split_archive_path` takes it
+pub(crate) fn member_path(archive: &Path, inner: &str) -> String {
+    platforms::normalize_path(&format!(
+        "{}{}{}",
+        archive.to_string_lossy(),
+        ARCHIVE_SEPARATOR,
+        inner
+    ))
+}
+
+/// Lists a folder inside an archive the way `get_tree_data` lists one on disk
+///
+/// Entries get their size, modification time and, if asked for, content type;
+/// archives keep no creation time, so `created` repeats `modified`. Symlinks are never
+/// descended into, and `filter` is applied except for gitignore rules and Unix details.
+pub(crate) fn tree_data(
+    archive: &Path,
+    inner: &str,
+    depth_limit: Option<usize>,
+    nested: bool,
+    filter: &TreeFilter,
+) -> AppResult<Vec<NodeDetails>> {
+    let listing = open_listing(archive)?;
+    let (folder, entry) = listing.resolve(inner)?;
+    if entry.kind != PathinfoKind::Directory {
+        return Err(AppError::not_a_directory(member_path(archive, &folder)));
+    }
+
+    let mut nodes = listing.read_folder(&folder, 1, depth_limit, nested, filter);
+    if filter.options().content_types {
+        listing.add_content_types(&mut nodes)?;
+    }
+    Ok(nodes)
+}
+
+/// Reads a file inside an archive the way `read_file_content` reads one on disk.
+/// The etag is the archive's.
+pub(crate) fn read_member(archive: &Path, inner: &str) -> AppResult<FileContent> {
+    let listing = open_listing(archive)?;
+    let (inner, _) = listing.resolve(inner)?;
+    let path = member_path(archive, &inner);
+    let target = listing
+        .follow(&inner)
+        .ok_or_else(|| AppError::not_found(&path))?;
+    let entry = &listing.entries[&target];
+    if entry.kind != PathinfoKind::File {
+        return Err(AppError::not_a_file(&path));
+    }
+
+    let mut bytes = None;
+    listing.for_each_member(&[target.as_str()], MAX_TEXT_READ_LEN, |_, content| {
+        bytes = Some(content);
+        true
+    })?;
+    let bytes = bytes.ok_or_else(|| AppError::not_found(&path))?;
+    Ok(file_read::decode(
+        name_of(&inner),
+        &bytes,
+        entry.size,
+        listing.etag.clone(),
+    ))
+}
+
+/// Passes the path and content of the archive's files, in archive order, to `visit`
+/// until it returns false. Only the first `max_files` files no larger than
+/// `max_size` are read. Returns how many were.
+pub(crate) fn visit_files(
+    archive: &Path,
+    max_size: u64,
+    max_files: usize,
+    mut visit: impl FnMut(String, Vec<u8>) -> bool,
+) -> AppResult<usize> {
+    let listing = open_listing(archive)?;
+    let mut files: Vec<(&String, &ArchiveEntry)> = listing
+        .entries
+        .iter()
+        .filter(|(_, entry)| entry.kind == PathinfoKind::File && entry.size <= max_size)
+        .collect();
+    files.sort_by_key(|(_, entry)| entry.index);
+    files.truncate(max_files);
+
+    let wanted: Vec<&str> = files.iter().map(|(inner, _)| inner.as_str()).collect();
+    let mut visited = 0;
+    listing.for_each_member(&wanted, max_size, |inner, content| {
+        visited += 1;
+        visit(member_path(archive, inner), content)
+    })?;
+    Ok(visited)
+}
+
+/// The listing of the archive at `path`, read again when the archive has changed
+fn open_listing(path: &Path) -> AppResult<Arc<ArchiveListing>> {
+    let metadata = fs::metadata(path).map_err(|e| AppError::from_io(e, path))?;
+    let etag = file_write::etag(&metadata);
+    {
+        let mut listings = ARCHIVE_LISTINGS.lock().map_err(AppError::lock_poisoned)?;
+        if let Some(position) = listings.iter().position(|listing| listing.archive == path) {
+            let listing = listings.remove(position);
+            if listing.etag == etag {
+                listings.push(listing.clone());
+                return Ok(listing);
+            }
+        }
+    }
+
+    // Listing a compressed tar reads all of it, so it's done without the lock held
+    let listing = Arc::new(ArchiveListing::read(path, etag)?);
+    let mut listings = ARCHIVE_LISTINGS.lock().map_err(AppError::lock_poisoned)?;
+    listings.retain(|cached| cached.archive != path);
+    listings.push(listing.clone());
+    if listings.len() > MAX_ARCHIVE_LISTINGS {
+        listings.remove(0);
+    }
+    Ok(listing)
+}
+
+/// One entry of an archive being browsed
+struct ArchiveEntry {
+    kind: PathinfoKind,
+    size: u64,
+    /// Milliseconds since the Unix epoch
+    modified: u128,
+    /// Where a symlink points, as stored
+    link_target: Option<String>,
+    /// The entry's position in the archive, used to find it again
+    index: usize,
+}
+
+impl ArchiveEntry {
+    fn directory(modified: u128) -> Self {
+        ArchiveEntry {
+            kind: PathinfoKind::Directory,
+            size: 0,
+            modified,
+            link_target: None,
+            index: usize::MAX,
+        }
+    }
+}
+
+/// The entries of an archive, keyed by their `/`-separated path inside it. The
+/// archive's root is the folder with the empty path.
+struct ArchiveListing {
+    archive: PathBuf,
+    etag: String,
+    format: ArchiveFormat,
+    entries: HashMap<String, ArchiveEntry>,
+    /// The paths of each folder's entries
+    children: HashMap<String, Vec<String>>,
+    /// `member_path` of the root, which every entry's path starts with
+    root_path: String,
+}
+
+impl ArchiveListing {
+    fn read(archive: &Path, etag: String) -> AppResult<Self> {
+        let format = detect_format(archive)?.ok_or_else(|| {
+            AppError::invalid_argument(format!(
+                "'{}' isn't a zip or tar archive.",
+                platforms::normalize_path(&archive.to_string_lossy())
+            ))
+        })?;
+        let mut listing = ArchiveListing {
+            archive: archive.to_path_buf(),
+            etag,
+            format,
+            entries: HashMap::from([(String::new(), ArchiveEntry::directory(0))]),
+            children: HashMap::new(),
+            root_path: member_path(archive, ""),
+        };
+
+        if format == ArchiveFormat::Zip {
+            let mut zip = open_zip(archive)?;
+            for index in 0..zip.len() {
+                let mut file = zip.by_index(index).map_err(|e| zip_error(e, archive))?;
+                let Some(inner) = inner_name(Path::new(file.name())) else {
+                    continue;
+                };
+                let modified = file.last_modified().and_then(system_time);
+                let (kind, link_target) = if file.is_dir() {
+                    (PathinfoKind::Directory, None)
+                } else if file.is_symlink() {
+                    let mut target = String::new();
+                    (&mut file)
+                        .take(4096)
+                        .read_to_string(&mut target)
+                        .map_err(|e| AppError::from_io(e, archive))?;
+                    (PathinfoKind::Symlink, Some(target))
+                } else {
+                    (PathinfoKind::File, None)
+                };
+                listing.insert(
+                    inner,
+                    ArchiveEntry {
+                        kind,
+                        size: file.size(),
+                        modified: millis(modified),
+                        link_target,
+                        index,
+                    },
+                )?;
+            }
+        } else {
+            let mut entries = Vec::new();
+            visit_tar(archive, format, |index, entry| {
+                let header = entry.header();
+                let (kind, link_target) = match header.entry_type() {
+                    tar::EntryType::Directory => (PathinfoKind::Directory, None),
+                    tar::EntryType::Symlink => (
+                        PathinfoKind::Symlink,
+                        entry
+                            .link_name()
+                            .ok()
+                            .flatten()
+                            .map(|target| target.to_string_lossy().into_owned()),
+                    ),
+                    tar::EntryType::Regular | tar::EntryType::Continuous => {
+                        (PathinfoKind::File, None)
+                    }
+                    // Hard links, devices and the like
+                    _ => return Ok(true),
+                };
+                let Some(inner) = entry.path().ok().and_then(|path| inner_name(&path)) else {
+                    return Ok(true);
+                };
+                let modified = header
+                    .mtime()
+                    .ok()
+                    .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds));
+                entries.push((
+                    inner,
+                    ArchiveEntry {
+                        kind,
+                        size: header.size().unwrap_or(0),
+                        modified: millis(modified),
+                        link_target,
+                        index,
+                    },
+                ));
+                Ok(entries.len() as u64 <= MAX_ARCHIVE_ENTRIES)
+            })?;
+            for (inner, entry) in entries {
+                listing.insert(inner, entry)?;
+            }
+        }
+        Ok(listing)
+    }
+
+    /// Adds an entry, along with any of its folders the archive doesn't list. A
+    /// later entry with the same path replaces the earlier one, as when extracting.
+    fn insert(&mut self, inner: String, entry: ArchiveEntry) -> AppResult<()> {
+        if self.entries.len() as u64 > MAX_ARCHIVE_ENTRIES {
+            return Err(AppError::invalid_argument(format!(
+                "'{}' has more than {} entries.",
+                platforms::normalize_path(&self.archive.to_string_lossy()),
+                MAX_ARCHIVE_ENTRIES
+            )));
+        }
+        // The root is always listed, so this stops there at the latest
+        let mut missing = Vec::new();
+        let mut folder = parent_of(&inner);
+        while !self.entries.contains_key(folder) {
+            missing.push(folder.to_string());
+            folder = parent_of(folder);
+        }
+        for folder in missing.into_iter().rev() {
+            self.add(folder, ArchiveEntry::directory(entry.modified));
+        }
+        self.add(inner, entry);
+        Ok(())
+    }
+
+    fn add(&mut self, inner: String, entry: ArchiveEntry) {
+        let parent = parent_of(&inner).to_string();
+        if self.entries.insert(inner.clone(), entry).is_none() {
+            self.children.entry(parent).or_default().push(inner);
+        }
+    }
+
+    /// The normalized form of a path inside the archive and its entry
+    fn resolve(&self, inner: &str) -> AppResult<(String, &ArchiveEntry)> {
+        let mut parts = Vec::new();
+        for part in inner.split(['/', '\\']) {
+            match part {
+                "" | "." => {}
+                ".." => {
+                    return Err(AppError::invalid_argument(format!(
+                        "'{}' leads outside the archive.",
+                        member_path(&self.archive, inner)
+                    )))
+                }
+                part => parts.push(part),
+            }
+        }
+        let inner = parts.join("/");
+        match self.entries.get(&inner) {
+            Some(entry) => Ok((inner, entry)),
+            None => Err(AppError::not_found(member_path(&self.archive, &inner))),
+        }
+    }
+
+    /// The entry a path leads to once symlinks are followed, or none if a link is
+    /// broken, loops or leads outside the archive
+    fn follow(&self, inner: &str) -> Option<String> {
+        let mut current = inner.to_string();
+        for _ in 0..MAX_SYMLINK_HOPS {
+            let entry = self.entries.get(&current)?;
+            let Some(target) = &entry.link_target else {
+                return Some(current);
+            };
+            let mut parts: Vec<&str> = parent_of(&current)
+                .split('/')
+                .filter(|part| !part.is_empty())
+                .collect();
+            if target.starts_with('/') {
+                return None;
+            }
+            for part in target.split('/') {
+                match part {
+                    "" | "." => {}
+                    ".." => {
+                        parts.pop()?;
+                    }
+                    part => parts.push(part),
+                }
+            }
+            current = parts.join("/");
+        }
+        None
+    }
+
+    /// The entries of `folder` that pass `filter`, sorted, with those of its
+    /// subfolders either attached as children or following them
+    fn read_folder(
+        &self,
+        folder: &str,
+        depth: usize,
+        depth_limit: Option<usize>,
+        nested: bool,
+        filter: &TreeFilter,
+    ) -> Vec<NodeDetails> {
+        let mut nodes: Vec<NodeDetails> = self
+            .children
+            .get(folder)
+            .into_iter()
+            .flatten()
+            .map(|inner| self.node(inner))
+            .filter(|node| filter.allows(node))
+            .collect();
+        filter.sort(&mut nodes);
+        if depth_limit.is_some_and(|limit| depth >= limit) {
+            return nodes;
+        }
+
+        let mut descendants = Vec::new();
+        for node in nodes.iter_mut() {
+            if node.kind != PathinfoKind::Directory {
+                continue;
+            }
+            let inner = &node.path[self.root_path.len()..];
+            let children = self.read_folder(inner, depth + 1, depth_limit, nested, filter);
+            if nested {
+                node.children = Some(children);
+            } else {
+                descendants.extend(children);
+            }
+        }
+        nodes.extend(descendants);
+        nodes
+    }
+
+    /// The details of an entry, without its content type
+    fn node(&self, inner: &str) -> NodeDetails {
+        let entry = &self.entries[inner];
+        let name = name_of(inner).to_string();
+        let symlink = entry.link_target.as_ref().map(|target| {
+            let resolved = self.follow(inner).and_then(|path| self.entries.get(&path));
+            SymlinkDetails {
+                target: target.clone(),
+                broken: resolved.is_none(),
+                points_to_directory: resolved
+                    .is_some_and(|entry| entry.kind == PathinfoKind::Directory),
+            }
+        });
+        NodeDetails {
+            hidden: name.starts_with('.'),
+            name,
+            path: member_path(&self.archive, inner),
+            kind: entry.kind,
+            ignored: false,
+            size: entry.size,
+            created: entry.modified,
+            modified: entry.modified,
+            symlink,
+            unix: None,
+            content_type: None,
+            git_status: None,
+            children: None,
+        }
+    }
+
+    /// Detects the content type of the listed files, reading the archive once
+    fn add_content_types(&self, nodes: &mut [NodeDetails]) -> AppResult<()> {
+        let mut targets = HashMap::new();
+        self.collect_files(nodes, &mut targets);
+        let wanted: Vec<&str> = targets.values().map(String::as_str).collect();
+        let mut samples = HashMap::new();
+        self.for_each_member(&wanted, CONTENT_SNIFF_LEN as u64, |inner, sample| {
+            samples.insert(inner.to_string(), sample);
+            true
+        })?;
+
+        self.fill_content_types(nodes, &targets, &samples);
+        Ok(())
+    }
+
+    /// Maps the path of every file (or link to one) among `nodes` to the file it
+    /// leads to
+    fn collect_files(&self, nodes: &[NodeDetails], targets: &mut HashMap<String, String>) {
+        for node in nodes {
+            if let Some(children) = &node.children {
+                self.collect_files(children, targets);
+            }
+            if node.is_directory_like() || node.symlink.as_ref().is_some_and(|link| link.broken) {
+                continue;
+            }
+            let inner = &node.path[self.root_path.len()..];
+            if let Some(target) = self.follow(inner) {
+                targets.insert(inner.to_string(), target);
+            }
+        }
+    }
+
+    fn fill_content_types(
+        &self,
+        nodes: &mut [NodeDetails],
+        targets: &HashMap<String, String>,
+        samples: &HashMap<String, Vec<u8>>,
+    ) {
+        for node in nodes {
+            if let Some(children) = node.children.as_mut() {
+                self.fill_content_types(children, targets, samples);
+            }
+            let inner = &node.path[self.root_path.len()..];
+            if let Some(sample) = targets.get(inner).and_then(|target| samples.get(target)) {
+                node.content_type = Some(content_type_of(&node.name, sample));
+            }
+        }
+    }
+
+    /// Reads up to `limit` bytes of each of the `wanted` files and passes them to
+    /// `visit`, in archive order, until it returns false
+    fn for_each_member(
+        &self,
+        wanted: &[&str],
+        limit: u64,
+        mut visit: impl FnMut(&str, Vec<u8>) -> bool,
+    ) -> AppResult<()> {
+        let mut by_index: HashMap<usize, &str> = wanted
+            .iter()
+            .filter_map(|inner| Some((self.entries.get(*inner)?.index, *inner)))
+            .collect();
+        if by_index.is_empty() {
+            return Ok(());
+        }
+        let archive = &self.archive;
+
+        if self.format == ArchiveFormat::Zip {
+            let mut indexes: Vec<usize> = by_index.keys().copied().collect();
+            indexes.sort_unstable();
+            let mut zip = open_zip(archive)?;
+            for index in indexes {
+                let file = zip.by_index(index).map_err(|e| zip_error(e, archive))?;
+                let mut content = Vec::new();
+                file.take(limit)
+                    .read_to_end(&mut content)
+                    .map_err(|e| AppError::from_io(e, archive))?;
+                if !visit(by_index[&index], content) {
+                    break;
+                }
+            }
+            return Ok(());
+        }
+
+        visit_tar(archive, self.format, |index, entry| {
+            let Some(inner) = by_index.remove(&index) else {
+                return Ok(true);
+            };
+            let mut content = Vec::new();
+            entry
+                .take(limit)
+                .read_to_end(&mut content)
+                .map_err(|e| AppError::from_io(e, archive))?;
+            Ok(visit(inner, content) && !by_index.is_empty())
+        })
+    }
+}
+
+/// Calls `visit` with the position and entry of each entry of a tar archive until it
+/// returns false
+fn visit_tar(
+    archive: &Path,
+    format: ArchiveFormat,
+    mut visit: impl FnMut(usize, &mut tar::Entry<'_, Box<dyn Read>>) -> AppResult<bool>,
+) -> AppResult<()> {
+    let file = BufReader::new(File::open(archive).map_err(|e| AppError::from_io(e, archive))?);
+    let reader: Box<dyn Read> = match format {
+        ArchiveFormat::TarGz => Box::new(MultiGzDecoder::new(file)),
+        ArchiveFormat::TarZst => {
+            Box::new(zstd::Decoder::with_buffer(file).map_err(|e| AppError::from_io(e, archive))?)
+        }
+        _ => Box::new(file),
+    };
+    let mut tar = tar::Archive::new(reader);
+    let entries = tar.entries().map_err(|e| AppError::from_io(e, archive))?;
+    for (index, entry) in entries.enumerate() {
+        let mut entry = entry.map_err(|e| AppError::from_io(e, archive))?;
+        if !visit(index, &mut entry)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn open_zip(archive: &Path) -> AppResult<ZipArchive<BufReader<File>>> {
+    let file = File::open(archive).map_err(|e| AppError::from_io(e, archive))?;
+    ZipArchive::new(BufReader::new(file)).map_err(|e| zip_error(e, archive))
+}
+
+fn zip_error(e: zip::result::ZipError, archive: &Path) -> AppError {
+    match e {
+        zip::result::ZipError::Io(e) => AppError::from_io(e, archive),
+        e => AppError::invalid_argument(format!(
+            "'{}' isn't a valid zip archive: {}",
+            platforms::normalize_path(&archive.to_string_lossy()),
+            e
+        )),
+    }
+}
+
+/// An entry's name as a `/`-separated path, or none if it leads outside the archive
+/// or is nested too deep to list
+fn inner_name(name: &Path) -> Option<String> {
+    let path = entry_path(name)?;
+    if path.components().count() > MAX_ARCHIVE_DEPTH {
+        return None;
+    }
+    Some(
+        path.iter()
+            .map(|part| part.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+fn parent_of(inner: &str) -> &str {
+    inner.rfind('/').map_or("", |at| &inner[..at])
+}
+
+fn name_of(inner: &str) -> &str {
+    inner.rfind('/').map_or(inner, |at| &inner[at + 1..])
+}
+
+fn millis(time: Option<SystemTime>) -> u128 {
+    time.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .unwrap_or_default()
+        .as_millis()
+}
@@ -0,0 +1,974 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::constants::{
+    ARCHIVE_PROGRESS_INTERVAL, MAX_ARCHIVE_ENTRIES, MAX_COMPRESSION_RATIO, MIN_EXTRACT_LIMIT,
+};
+use crate::commands::filesystem_ops::ensure_directory;
+use crate::commands::move_ops::available_name;
+use crate::commands::sandbox;
+use crate::models::archive::{ArchiveFormat, ArchiveProgress, ArchiveSummary, ExtractOptions};
+use crate::models::error::{AppError, AppResult};
+use crate::models::transfer::ConflictPolicy;
+use crate::platforms;
+use chrono::{Datelike, Local, NaiveDate, TimeZone, Timelike};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, Metadata};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::Window;
+use tokio::sync::mpsc;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+// Cancellation channels of the archive jobs in flight, keyed by job id
+static ARCHIVE_JOBS: Lazy<Mutex<HashMap<String, mpsc::Sender<()>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[tauri::command]
+pub async fn cancel_archive(job_id: String) -> AppResult<()> {
+    let cancel_tx = ARCHIVE_JOBS
+        .lock()
+        .map_err(AppError::lock_poisoned)?
+        .remove(&job_id);
+    if let Some(cancel_tx) = cancel_tx {
+        let _ = cancel_tx.try_send(());
+    }
+    Ok(())
+}
+
+/// Packs files and folders into a new zip or tar archive
+///
+/// Folders are stored with their contents, and symlinks as links. Permissions and
+/// modification times are kept. Emits `archive-progress` tagged with `job_id`.
+///
+/// Parameters:
+/// - job_id: Caller-chosen id used to tag events and to cancel the job
+/// - sources: The files and folders to pack; each is stored under its own name
+/// - archive_path: The archive to create
+/// - format: Zip, Tar, TarGz or TarZst; defaults to what the archive's name says
+///
+/// Returns:
+/// - Ok(ArchiveSummary): Counts and the entries that couldn't be stored
+/// - Err(AppError): AlreadyExists if the archive exists, InvalidArgument if the format
+///   can't be told from the name or two sources share a name, Cancelled, or the
+///   error that stopped the job. The partial archive is removed.
+#[tauri::command]
+pub async fn create_archive(
+    window: Window,
+    job_id: String,
+    sources: Vec<String>,
+    archive_path: String,
+    format: Option<ArchiveFormat>,
+) -> AppResult<ArchiveSummary> {
+    let archive = PathBuf::from(platforms::normalize_path(&archive_path));
+    let sources: Vec<PathBuf> = sources
+        .iter()
+        .map(|source| PathBuf::from(platforms::normalize_path(source)))
+        .collect();
+    sandbox::ensure_allowed(&archive)?;
+    for source in &sources {
+        sandbox::ensure_entry_allowed(source)?;
+    }
+    let format = match format.or_else(|| format_from_name(&archive)) {
+        Some(format) => format,
+        None => {
+            return Err(AppError::invalid_argument(format!(
+                "Can't tell the archive format from the name '{}'.",
+                display(&archive)
+            )))
+        }
+    };
+
+    run_job(job_id, window, move |job| {
+        job.create(&sources, &archive, format)
+    })
+    .await
+}
+
+/// Unpacks a zip or tar archive into a folder
+///
+/// Entries whose paths would land outside the folder, including through symlinks,
+/// are skipped, and extraction stops if the contents grow past `options.max_bytes`,
+/// as a decompression bomb's would. Emits `archive-progress` tagged with `job_id`.
+///
+/// Parameters:
+/// - job_id: Caller-chosen id used to tag events and to cancel the job
+/// - archive_path: A .zip, .jar, .tar, .tar.gz/.tgz or .tar.zst archive; other names
+///   are recognized by their content
+/// - destination: The folder to unpack into
+/// - options: The conflict policy for existing files, and the size limit
+///
+/// Returns:
+/// - Ok(ArchiveSummary): Counts and the entries that were skipped
+/// - Err(AppError): NotADirectory if the destination isn't a folder, InvalidArgument
+///   if the file isn't an archive or is too large unpacked, Cancelled, or the error
+///   that stopped the job. Whatever was unpacked is removed again, and files replaced
+///   with `Overwrite` are put back.
+#[tauri::command]
+pub async fn extract_archive(
+    window: Window,
+    job_id: String,
+    archive_path: String,
+    destination: String,
+    options: Option<ExtractOptions>,
+) -> AppResult<ArchiveSummary> {
+    let archive = PathBuf::from(platforms::normalize_path(&archive_path));
+    let destination = PathBuf::from(platforms::normalize_path(&destination));
+    sandbox::ensure_allowed(&archive)?;
+    sandbox::ensure_allowed(&destination)?;
+    ensure_directory(&destination)?;
+    let options = options.unwrap_or_default();
+
+    run_job(job_id, window, move |job| {
+        job.extract(&archive, &destination, &options)
+    })
+    .await
+}
+
+type EmitProgress = Box<dyn FnMut(ArchiveProgress) + Send>;
+
+/// Runs `work` on a blocking thread, reachable by `cancel_archive` meanwhile
+async fn run_job<F>(job_id: String, window: Window, work: F) -> AppResult<ArchiveSummary>
+where
+    F: FnOnce(&mut ArchiveJob<'_, EmitProgress>) -> AppResult<ArchiveSummary> + Send + 'static,
+{
+    let (cancel_tx, mut cancel_rx) = mpsc::channel(1);
+    {
+        let mut jobs = ARCHIVE_JOBS.lock().map_err(AppError::lock_poisoned)?;
+        if jobs.contains_key(&job_id) {
+            return Err(AppError::invalid_argument(format!(
+                "An archive job with id '{}' is already running.",
+                job_id
+            )));
+        }
+        jobs.insert(job_id.clone(), cancel_tx);
+    }
+
+    let id = job_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let on_progress: EmitProgress = Box::new(move |progress| {
+            let _ = window.emit("archive-progress", progress);
+        });
+        let mut job = ArchiveJob::new(&id, &mut cancel_rx, on_progress);
+        work(&mut job)
+    })
+    .await
+    .map_err(|e| {
+        AppError::internal(format!(
+            "Archive job '{}' stopped unexpectedly: {}",
+            job_id, e
+        ))
+    });
+
+    if let Ok(mut jobs) = ARCHIVE_JOBS.lock() {
+        jobs.remove(&job_id);
+    }
+    result?
+}
+
+/// The format an archive's name calls for
+pub(crate) fn format_from_name(path: &Path) -> Option<ArchiveFormat> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveFormat::TarGz)
+    } else if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+        Some(ArchiveFormat::TarZst)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveFormat::Tar)
+    } else if [".zip", ".jar", ".war", ".ear", ".apk"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
+    {
+        Some(ArchiveFormat::Zip)
+    } else {
+        None
+    }
+}
+
+/// The format of the archive at `path`, from its name or else its first bytes.
+/// Compressed streams are taken to hold a tar archive.
+pub(crate) fn detect_format(path: &Path) -> AppResult<Option<ArchiveFormat>> {
+    if let Some(format) = format_from_name(path) {
+        return Ok(Some(format));
+    }
+    let file = File::open(path).map_err(|e| AppError::from_io(e, path))?;
+    let mut head = Vec::with_capacity(512);
+    file.take(512)
+        .read_to_end(&mut head)
+        .map_err(|e| AppError::from_io(e, path))?;
+    Ok(
+        if head.starts_with(b"PK\x03\x04") || head.starts_with(b"PK\x05\x06") {
+            Some(ArchiveFormat::Zip)
+        } else if head.starts_with(&[0x1F, 0x8B]) {
+            Some(ArchiveFormat::TarGz)
+        } else if head.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            Some(ArchiveFormat::TarZst)
+        } else if head.get(257..262) == Some(b"ustar") {
+            Some(ArchiveFormat::Tar)
+        } else {
+            None
+        },
+    )
+}
+
+/// An entry's name as a path relative to the destination, or none if it would
+/// lead outside it: absolute paths, drive prefixes and `..` are all refused
+pub(crate) fn entry_path(name: &Path) -> Option<PathBuf> {
+    let mut path = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+/// Whether a symlink at `link` (relative to `destination`) pointing to `target`
+/// ends up inside the destination. Every `..` has to step out of a real folder
+/// already on disk, since stepping out of a symlink lands beside what it points to.
+fn link_stays_inside(destination: &Path, link: &Path, target: &Path) -> bool {
+    let mut resolved = link.parent().map(Path::to_path_buf).unwrap_or_default();
+    for component in target.components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                let is_folder = fs::symlink_metadata(destination.join(&resolved))
+                    .map(|metadata| metadata.is_dir())
+                    .unwrap_or(false);
+                if !is_folder || !resolved.pop() {
+                    return false;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
+enum EntryKind {
+    Directory,
+    File,
+    Symlink(PathBuf),
+    /// Hard links, devices and the like, which aren't recreated
+    Other,
+}
+
+/// One entry of an archive being extracted
+struct Entry {
+    name: PathBuf,
+    kind: EntryKind,
+    mode: Option<u32>,
+    modified: Option<SystemTime>,
+}
+
+/// What an extraction changed on disk, so a failed one can be undone
+#[derive(Default)]
+struct Changes {
+    /// Files and folders created, in order
+    created: Vec<PathBuf>,
+    /// Files replaced with `Overwrite`, with where they were moved aside to
+    replaced: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Changes {
+    /// Moves the file at `target` out of the way, keeping it until extraction is done
+    fn move_aside(&mut self, target: &Path) -> AppResult<()> {
+        let name = target.file_name().unwrap_or_default().to_string_lossy();
+        let aside = available_name(&target.with_file_name(format!(".{}.replaced", name)), true);
+        fs::rename(target, &aside).map_err(|e| AppError::from_io(e, target))?;
+        self.replaced.push((target.to_path_buf(), aside));
+        Ok(())
+    }
+
+    /// Removes what was created and puts replaced files back
+    fn roll_back(&self) {
+        // Deepest first, so folders are empty by the time they're removed
+        for path in self.created.iter().rev() {
+            let _ = match fs::symlink_metadata(path) {
+                Ok(metadata) if metadata.is_dir() => fs::remove_dir(path),
+                _ => fs::remove_file(path),
+            };
+        }
+        for (original, aside) in self.replaced.iter().rev() {
+            let _ = fs::rename(aside, original);
+        }
+    }
+
+    /// Drops the replaced files once extraction has succeeded
+    fn finish(&self) {
+        for (_, aside) in &self.replaced {
+            let _ = fs::remove_file(aside);
+        }
+    }
+}
+
+/// Counts the bytes read through it into a shared total
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
+
+impl<R: io::Seek> io::Seek for CountingReader<R> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// Feeds a file's content through a job, which counts it, enforces the size limit
+/// and stops it on cancel. Errors reading `inner` are reported against `path`.
+struct JobReader<'j, 'a, P: FnMut(ArchiveProgress), R> {
+    job: &'j mut ArchiveJob<'a, P>,
+    inner: R,
+    path: &'j Path,
+}
+
+impl<P: FnMut(ArchiveProgress), R: Read> Read for JobReader<'_, '_, P, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self
+            .inner
+            .read(buf)
+            .map_err(|e| io::Error::other(AppError::from_io(e, self.path)))?;
+        self.job.count(read as u64).map_err(io::Error::other)?;
+        Ok(read)
+    }
+}
+
+/// The AppError a `JobReader` raised, or the write error against `path`
+fn copy_error(e: io::Error, path: &Path) -> AppError {
+    if e.get_ref().is_some_and(|inner| inner.is::<AppError>()) {
+        if let Some(inner) = e.into_inner() {
+            if let Ok(app_error) = inner.downcast::<AppError>() {
+                return *app_error;
+            }
+        }
+        return AppError::internal("Archive job failed");
+    }
+    AppError::from_io(e, path)
+}
+
+pub(crate) struct ArchiveJob<'a, P: FnMut(ArchiveProgress)> {
+    cancel_rx: &'a mut mpsc::Receiver<()>,
+    on_progress: P,
+    progress: ArchiveProgress,
+    /// Bytes of the archive read so far, while extracting
+    archive_read: Option<Arc<AtomicU64>>,
+    /// Uncompressed bytes archived or extracted
+    bytes: u64,
+    /// Extraction stops past this many uncompressed bytes
+    limit: u64,
+    skipped: Vec<String>,
+    start_time: Instant,
+    last_progress: Instant,
+}
+
+impl<'a, P: FnMut(ArchiveProgress)> ArchiveJob<'a, P> {
+    pub fn new(job_id: &str, cancel_rx: &'a mut mpsc::Receiver<()>, on_progress: P) -> Self {
+        ArchiveJob {
+            cancel_rx,
+            on_progress,
+            progress: ArchiveProgress {
+                job_id: job_id.to_string(),
+                ..Default::default()
+            },
+            archive_read: None,
+            bytes: 0,
+            limit: u64::MAX,
+            skipped: Vec::new(),
+            start_time: Instant::now(),
+            last_progress: Instant::now(),
+        }
+    }
+
+    /// Packs `sources` into a new archive at `archive`
+    pub fn create(
+        &mut self,
+        sources: &[PathBuf],
+        archive: &Path,
+        format: ArchiveFormat,
+    ) -> AppResult<ArchiveSummary> {
+        let items = self.collect(sources)?;
+        self.progress.bytes_total = items
+            .iter()
+            .filter(|(_, _, metadata)| metadata.is_file())
+            .map(|(_, _, metadata)| metadata.len())
+            .sum();
+
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(archive)
+            .map_err(|e| AppError::from_io(e, archive))?;
+        // The archive may be inside one of the folders being packed
+        let identity = platforms::file_identity(archive);
+        let items: Vec<_> = items
+            .into_iter()
+            .filter(|(source, _, metadata)| {
+                !metadata.is_file() || platforms::file_identity(source) != identity
+            })
+            .collect();
+
+        let writer = BufWriter::new(file);
+        let result = match format {
+            ArchiveFormat::Zip => self.write_zip(writer, &items, archive),
+            ArchiveFormat::Tar => self.write_tar(writer, &items, archive).and_then(|writer| {
+                finish(writer.into_inner().map_err(|e| e.into_error()), archive)
+            }),
+            ArchiveFormat::TarGz => {
+                let encoder = GzEncoder::new(writer, Compression::default());
+                self.write_tar(encoder, &items, archive)
+                    .and_then(|encoder| finish(encoder.finish(), archive))
+            }
+            ArchiveFormat::TarZst => zstd::Encoder::new(writer, 0)
+                .map_err(|e| AppError::from_io(e, archive))
+                .and_then(|encoder| self.write_tar(encoder, &items, archive))
+                .and_then(|encoder| finish(encoder.finish(), archive)),
+        };
+        if let Err(e) = result {
+            let _ = fs::remove_file(archive);
+            return Err(e);
+        }
+
+        Ok(self.summary(archive, format))
+    }
+
+    /// Every entry under `sources`, named relative to the folder holding its source
+    fn collect(&mut self, sources: &[PathBuf]) -> AppResult<Vec<(PathBuf, PathBuf, Metadata)>> {
+        let mut names = HashSet::new();
+        let mut items = Vec::new();
+        for source in sources {
+            let name = source.file_name().ok_or_else(|| {
+                AppError::invalid_argument(format!("Can't archive '{}'.", display(source)))
+            })?;
+            if !names.insert(name.to_os_string()) {
+                return Err(AppError::invalid_argument(format!(
+                    "More than one item is named '{}'.",
+                    name.to_string_lossy()
+                )));
+            }
+            let base = source.parent().unwrap_or(Path::new(""));
+            for entry in WalkDir::new(source).follow_links(false).sort_by_file_name() {
+                let entry = entry.map_err(|e| {
+                    let path = e.path().unwrap_or(source).to_path_buf();
+                    match e.into_io_error() {
+                        Some(io_error) => AppError::from_io(io_error, &path),
+                        None => AppError::internal(format!("Can't read '{}'", display(&path))),
+                    }
+                })?;
+                let metadata = entry
+                    .path()
+                    .symlink_metadata()
+                    .map_err(|e| AppError::from_io(e, entry.path()))?;
+                let name = entry
+                    .path()
+                    .strip_prefix(base)
+                    .unwrap_or(entry.path())
+                    .to_path_buf();
+                items.push((entry.into_path(), name, metadata));
+            }
+        }
+        Ok(items)
+    }
+
+    fn write_zip(
+        &mut self,
+        writer: BufWriter<File>,
+        items: &[(PathBuf, PathBuf, Metadata)],
+        archive: &Path,
+    ) -> AppResult<()> {
+        let zip_error = |e: zip::result::ZipError| AppError::from_io(e.into(), archive);
+        let mut zip = ZipWriter::new(writer);
+        for (source, name, metadata) in items {
+            self.checkpoint(false)?;
+            self.progress.current_path = Some(display(source));
+            let name = zip_name(name);
+            let mut options = SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .large_file(metadata.len() >= u32::MAX as u64);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                options = options.unix_permissions(metadata.permissions().mode() & 0o7777);
+            }
+            if let Some(time) = metadata.modified().ok().and_then(zip_time) {
+                options = options.last_modified_time(time);
+            }
+
+            let file_type = metadata.file_type();
+            if file_type.is_dir() {
+                zip.add_directory(name, options).map_err(zip_error)?;
+            } else if file_type.is_symlink() {
+                let target = fs::read_link(source).map_err(|e| AppError::from_io(e, source))?;
+                zip.add_symlink(name, target.to_string_lossy(), options)
+                    .map_err(zip_error)?;
+            } else if file_type.is_file() {
+                zip.start_file(name, options).map_err(zip_error)?;
+                let file = File::open(source).map_err(|e| AppError::from_io(e, source))?;
+                let mut reader = JobReader {
+                    job: self,
+                    inner: file.take(metadata.len()),
+                    path: source,
+                };
+                io::copy(&mut reader, &mut zip).map_err(|e| copy_error(e, archive))?;
+            } else {
+                self.skipped.push(display(source));
+                continue;
+            }
+            self.progress.entries_done += 1;
+        }
+        let mut writer = zip.finish().map_err(zip_error)?;
+        finish(writer.flush().map(|_| writer), archive)
+    }
+
+    fn write_tar<W: Write>(
+        &mut self,
+        writer: W,
+        items: &[(PathBuf, PathBuf, Metadata)],
+        archive: &Path,
+    ) -> AppResult<W> {
+        let tar_error = |e| AppError::from_io(e, archive);
+        let mut builder = tar::Builder::new(writer);
+        builder.follow_symlinks(false);
+        for (source, name, metadata) in items {
+            self.checkpoint(false)?;
+            self.progress.current_path = Some(display(source));
+            let mut header = tar::Header::new_gnu();
+            header.set_metadata(metadata);
+
+            let file_type = metadata.file_type();
+            if file_type.is_dir() {
+                header.set_size(0);
+                builder
+                    .append_data(&mut header, name, io::empty())
+                    .map_err(tar_error)?;
+            } else if file_type.is_symlink() {
+                let target = fs::read_link(source).map_err(|e| AppError::from_io(e, source))?;
+                header.set_size(0);
+                builder
+                    .append_link(&mut header, name, target)
+                    .map_err(tar_error)?;
+            } else if file_type.is_file() {
+                let file = File::open(source).map_err(|e| AppError::from_io(e, source))?;
+                let reader = JobReader {
+                    job: self,
+                    inner: file.take(metadata.len()),
+                    path: source,
+                };
+                builder
+                    .append_data(&mut header, name, reader)
+                    .map_err(|e| copy_error(e, archive))?;
+            } else {
+                self.skipped.push(display(source));
+                continue;
+            }
+            self.progress.entries_done += 1;
+        }
+        builder.into_inner().map_err(tar_error)
+    }
+
+    /// Unpacks the archive at `archive` into `destination`
+    pub fn extract(
+        &mut self,
+        archive: &Path,
+        destination: &Path,
+        options: &ExtractOptions,
+    ) -> AppResult<ArchiveSummary> {
+        let format = detect_format(archive)?.ok_or_else(|| {
+            AppError::invalid_argument(format!(
+                "'{}' isn't a zip or tar archive.",
+                display(archive)
+            ))
+        })?;
+        let size = fs::metadata(archive)
+            .map_err(|e| AppError::from_io(e, archive))?
+            .len();
+        self.progress.bytes_total = size;
+        self.limit = options.max_bytes.unwrap_or_else(|| {
+            size.saturating_mul(MAX_COMPRESSION_RATIO)
+                .max(MIN_EXTRACT_LIMIT)
+        });
+        let archive_read = Arc::new(AtomicU64::new(0));
+        self.archive_read = Some(archive_read.clone());
+
+        let mut changes = Changes::default();
+        let policy = options.conflict.unwrap_or_default();
+        let file = File::open(archive).map_err(|e| AppError::from_io(e, archive))?;
+        let reader = CountingReader {
+            inner: BufReader::new(file),
+            count: archive_read,
+        };
+        let result = match format {
+            ArchiveFormat::Zip => {
+                self.extract_zip(reader, archive, destination, policy, &mut changes)
+            }
+            ArchiveFormat::Tar => {
+                self.extract_tar(reader, archive, destination, policy, &mut changes)
+            }
+            ArchiveFormat::TarGz => self.extract_tar(
+                MultiGzDecoder::new(reader),
+                archive,
+                destination,
+                policy,
+                &mut changes,
+            ),
+            ArchiveFormat::TarZst => zstd::Decoder::new(reader)
+                .map_err(|e| AppError::from_io(e, archive))
+                .and_then(|decoder| {
+                    self.extract_tar(decoder, archive, destination, policy, &mut changes)
+                }),
+        };
+        if let Err(e) = result {
+            changes.roll_back();
+            return Err(e);
+        }
+        changes.finish();
+
+        Ok(self.summary(destination, format))
+    }
+
+    fn extract_zip<R: Read + io::Seek>(
+        &mut self,
+        reader: R,
+        archive: &Path,
+        destination: &Path,
+        policy: ConflictPolicy,
+        changes: &mut Changes,
+    ) -> AppResult<()> {
+        let zip_error = |e: zip::result::ZipError| match e {
+            zip::result::ZipError::Io(e) => AppError::from_io(e, archive),
+            e => AppError::invalid_argument(format!(
+                "'{}' isn't a valid zip archive: {}",
+                display(archive),
+                e
+            )),
+        };
+        let mut zip = ZipArchive::new(reader).map_err(zip_error)?;
+        for index in 0..zip.len() {
+            self.checkpoint(false)?;
+            let mut file = zip.by_index(index).map_err(zip_error)?;
+            let kind = if file.is_dir() {
+                EntryKind::Directory
+            } else if file.is_symlink() {
+                let mut target = String::new();
+                (&mut file)
+                    .take(4096)
+                    .read_to_string(&mut target)
+                    .map_err(|e| AppError::from_io(e, archive))?;
+                EntryKind::Symlink(PathBuf::from(target))
+            } else {
+                EntryKind::File
+            };
+            let entry = Entry {
+                name: PathBuf::from(file.name()),
+                kind,
+                mode: file.unix_mode(),
+                modified: file.last_modified().and_then(system_time),
+            };
+            self.place(&entry, &mut file, archive, destination, policy, changes)?;
+        }
+        Ok(())
+    }
+
+    fn extract_tar<R: Read>(
+        &mut self,
+        reader: R,
+        archive: &Path,
+        destination: &Path,
+        policy: ConflictPolicy,
+        changes: &mut Changes,
+    ) -> AppResult<()> {
+        let tar_error = |e| AppError::from_io(e, archive);
+        let mut tar = tar::Archive::new(reader);
+        for entry in tar.entries().map_err(tar_error)? {
+            self.checkpoint(false)?;
+            let mut entry = entry.map_err(tar_error)?;
+            let name = entry.path().map_err(tar_error)?.into_owned();
+            let header = entry.header();
+            let kind = match header.entry_type() {
+                tar::EntryType::Regular | tar::EntryType::Continuous => EntryKind::File,
+                tar::EntryType::Directory => EntryKind::Directory,
+                tar::EntryType::Symlink => match entry.link_name().map_err(tar_error)? {
+                    Some(target) => EntryKind::Symlink(target.into_owned()),
+                    None => EntryKind::Other,
+                },
+                _ => EntryKind::Other,
+            };
+            let entry_info = Entry {
+                name,
+                kind,
+                mode: header.mode().ok(),
+                modified: header
+                    .mtime()
+                    .ok()
+                    .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds)),
+            };
+            self.place(
+                &entry_info,
+                &mut entry,
+                archive,
+                destination,
+                policy,
+                changes,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes one entry under `destination`, refusing anything that would end up
+    /// outside it. What it creates or replaces is recorded in `changes`.
+    fn place(
+        &mut self,
+        entry: &Entry,
+        content: &mut impl Read,
+        archive: &Path,
+        destination: &Path,
+        policy: ConflictPolicy,
+        changes: &mut Changes,
+    ) -> AppResult<()> {
+        let shown = entry.name.to_string_lossy().into_owned();
+        self.progress.current_path = Some(shown.clone());
+        let Some(relative) = entry_path(&entry.name) else {
+            self.skipped.push(shown);
+            return Ok(());
+        };
+        if self.progress.entries_done >= MAX_ARCHIVE_ENTRIES {
+            return Err(AppError::invalid_argument(format!(
+                "'{}' has more than {} entries; extraction was stopped.",
+                display(archive),
+                MAX_ARCHIVE_ENTRIES
+            )));
+        }
+        self.progress.entries_done += 1;
+
+        // Create missing folders, but never go through a symlink or a file
+        let mut parent = destination.to_path_buf();
+        let components: Vec<_> = relative.components().collect();
+        for component in &components[..components.len() - 1] {
+            parent.push(component);
+            match fs::symlink_metadata(&parent) {
+                Ok(metadata) if metadata.is_dir() => {}
+                Ok(_) => {
+                    self.skipped.push(shown);
+                    return Ok(());
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    fs::create_dir(&parent).map_err(|e| AppError::from_io(e, &parent))?;
+                    changes.created.push(parent.clone());
+                }
+                Err(e) => return Err(AppError::from_io(e, &parent)),
+            }
+        }
+
+        let mut target = destination.join(&relative);
+        let existing = fs::symlink_metadata(&target).ok();
+        if let EntryKind::Directory = entry.kind {
+            match existing {
+                Some(metadata) if metadata.is_dir() => return Ok(()),
+                Some(_) if policy == ConflictPolicy::Overwrite => changes.move_aside(&target)?,
+                Some(_) if policy == ConflictPolicy::Fail => {
+                    return Err(AppError::already_exists(&target));
+                }
+                Some(_) => {
+                    self.skipped.push(shown);
+                    return Ok(());
+                }
+                None => {}
+            }
+            fs::create_dir(&target).map_err(|e| AppError::from_io(e, &target))?;
+            changes.created.push(target);
+            return Ok(());
+        }
+
+        if matches!(entry.kind, EntryKind::Other) {
+            self.skipped.push(shown);
+            return Ok(());
+        }
+        if let EntryKind::Symlink(link_target) = &entry.kind {
+            if cfg!(not(unix)) || !link_stays_inside(destination, &relative, link_target) {
+                self.skipped.push(shown);
+                return Ok(());
+            }
+        }
+        if let Some(metadata) = existing {
+            match policy {
+                ConflictPolicy::Fail => return Err(AppError::already_exists(&target)),
+                ConflictPolicy::Skip => {
+                    self.skipped.push(shown);
+                    return Ok(());
+                }
+                ConflictPolicy::KeepBoth => target = available_name(&target, false),
+                // Folders are never replaced by a file
+                ConflictPolicy::Overwrite if metadata.is_dir() => {
+                    self.skipped.push(shown);
+                    return Ok(());
+                }
+                ConflictPolicy::Overwrite => changes.move_aside(&target)?,
+            }
+        }
+
+        match &entry.kind {
+            #[cfg(unix)]
+            EntryKind::Symlink(link_target) => {
+                std::os::unix::fs::symlink(link_target, &target)
+                    .map_err(|e| AppError::from_io(e, &target))?;
+                changes.created.push(target);
+            }
+            _ => {
+                let mut file = fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&target)
+                    .map_err(|e| AppError::from_io(e, &target))?;
+                changes.created.push(target.clone());
+                let mut reader = JobReader {
+                    job: self,
+                    inner: content,
+                    path: archive,
+                };
+                io::copy(&mut reader, &mut file).map_err(|e| copy_error(e, &target))?;
+                if let Some(modified) = entry.modified {
+                    let _ = file.set_modified(modified);
+                }
+                #[cfg(unix)]
+                if let Some(mode) = entry.mode {
+                    use std::os::unix::fs::PermissionsExt;
+                    // No setuid, setgid or sticky bits from an archive
+                    let _ = file.set_permissions(fs::Permissions::from_mode(mode & 0o777));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds bytes that went through the job, failing past the size limit
+    fn count(&mut self, bytes: u64) -> AppResult<()> {
+        self.bytes += bytes;
+        if self.bytes > self.limit {
+            return Err(AppError::invalid_argument(format!(
+                "The archive unpacks to more than {} bytes; extraction was stopped in case \
+                 it's a decompression bomb.",
+                self.limit
+            )));
+        }
+        if self.archive_read.is_none() {
+            self.progress.bytes_done = self.bytes;
+        }
+        self.checkpoint(false)
+    }
+
+    /// Fails with Cancelled once a cancel arrives, and reports progress now and then
+    fn checkpoint(&mut self, force: bool) -> AppResult<()> {
+        if self.cancel_rx.try_recv().is_ok() {
+            return Err(AppError::Cancelled { path: None });
+        }
+        if force || self.last_progress.elapsed() >= ARCHIVE_PROGRESS_INTERVAL {
+            if let Some(read) = &self.archive_read {
+                self.progress.bytes_done = read.load(Ordering::Relaxed);
+            }
+            (self.on_progress)(self.progress.clone());
+            self.last_progress = Instant::now();
+        }
+        Ok(())
+    }
+
+    fn summary(&mut self, path: &Path, format: ArchiveFormat) -> ArchiveSummary {
+        self.progress.current_path = None;
+        let _ = self.checkpoint(true);
+        ArchiveSummary {
+            job_id: self.progress.job_id.clone(),
+            path: display(path),
+            format,
+            entries: self.progress.entries_done,
+            bytes: self.bytes,
+            skipped: std::mem::take(&mut self.skipped),
+            processing_time_ms: self.start_time.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+fn finish<W: Write>(writer: io::Result<W>, archive: &Path) -> AppResult<()> {
+    let mut writer = writer.map_err(|e| AppError::from_io(e, archive))?;
+    writer.flush().map_err(|e| AppError::from_io(e, archive))
+}
+
+/// A path inside a zip archive, which always uses `/`
+fn zip_name(name: &Path) -> String {
+    name.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Zip timestamps are local time with two-second precision, from 1980 on
+fn zip_time(time: SystemTime) -> Option<zip::DateTime> {
+    let local = chrono::DateTime::<Local>::from(time);
+    zip::DateTime::from_date_and_time(
+        u16::try_from(local.year()).ok()?,
+        local.month() as u8,
+        local.day() as u8,
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+    )
+    .ok()
+}
+
+/// The moment a zip timestamp, which is in local time, stands for
+pub(crate) fn system_time(time: zip::DateTime) -> Option<SystemTime> {
+    let local =
+        NaiveDate::from_ymd_opt(time.year() as i32, time.month() as u32, time.day() as u32)?
+            .and_hms_opt(
+                time.hour() as u32,
+                time.minute() as u32,
+                time.second() as u32,
+            )?;
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(SystemTime::from)
+}
+
+fn display(path: &Path) -> String {
+    platforms::normalize_path(&path.to_string_lossy())
+}
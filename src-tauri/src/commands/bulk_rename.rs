@@ -0,0 +1,530 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::exif;
+use crate::commands::journal::{self, Step};
+use crate::commands::move_ops::{available_name, is_plain_name};
+use crate::commands::sandbox;
+use crate::models::bulk_rename::{BulkRenameRules, CaseTransform, RenamePreview, RenameStatus};
+use crate::models::error::{AppError, AppResult};
+use crate::models::journal::OperationKind;
+use crate::platforms;
+use crate::search::regex::build_regex;
+use chrono::format::{Fixed, Item, StrftimeItems};
+use chrono::{DateTime, Local, NaiveDateTime};
+use regex::NoExpand;
+use std::collections::HashMap;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_TEMPLATE: &str = "{name}{ext}";
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const MAX_COUNTER_WIDTH: usize = 255;
+
+/// Shows what a bulk rename would do, without renaming anything
+///
+/// Parameters:
+/// - paths: The items to rename, in counter order
+/// - rules: Find/replace, case, template and counter settings
+///
+/// Returns:
+/// - Ok(Vec<RenamePreview>): The old and new name of every item, with collisions and
+///   invalid names flagged
+/// - Err(AppError): InvalidPattern for a find pattern or template that doesn't parse,
+///   or the error reading an item
+#[tauri::command]
+pub async fn preview_bulk_rename(
+    paths: Vec<String>,
+    rules: BulkRenameRules,
+) -> AppResult<Vec<RenamePreview>> {
+    plan(&normalize_paths(&paths)?, &rules)
+}
+
+/// Renames many items at once as a single undoable operation
+///
+/// Nothing is renamed if any new name collides or is invalid. Every item is first
+/// moved to a temporary name, so names can be swapped or shifted (`1→2`, `2→3`), and a
+/// failure part way puts every item back.
+///
+/// Parameters:
+/// - paths: The items to rename, in counter order
+/// - rules: Find/replace, case, template and counter settings
+///
+/// Returns:
+/// - Ok(Vec<RenamePreview>): What was done, as `preview_bulk_rename` described it
+/// - Err(AppError): InvalidArgument if a new name collides or is invalid,
+///   InvalidPattern for rules that don't parse, or the error that stopped a rename
+#[tauri::command]
+pub async fn bulk_rename(
+    paths: Vec<String>,
+    rules: BulkRenameRules,
+) -> AppResult<Vec<RenamePreview>> {
+    let preview = plan(&normalize_paths(&paths)?, &rules)?;
+    let blocked = preview
+        .iter()
+        .filter(|item| {
+            matches!(
+                item.status,
+                RenameStatus::Collision | RenameStatus::InvalidName
+            )
+        })
+        .count();
+    if blocked > 0 {
+        return Err(AppError::invalid_argument(format!(
+            "{} of the new names collide or aren't valid; nothing was renamed.",
+            blocked
+        )));
+    }
+
+    let pairs: Vec<(PathBuf, PathBuf)> = preview
+        .iter()
+        .filter(|item| item.status == RenameStatus::Rename)
+        .map(|item| {
+            (
+                PathBuf::from(&item.source),
+                PathBuf::from(&item.destination),
+            )
+        })
+        .collect();
+    if !pairs.is_empty() {
+        rename_all(&pairs)?;
+        journal::record(OperationKind::Rename, vec![Step::renamed(&pairs)]);
+    }
+    Ok(preview)
+}
+
+/// Renames every `from` to its `to` as one step. All items go to temporary names
+/// first, so swaps and chains work; if any rename fails, every item is put back.
+pub(crate) fn rename_all(pairs: &[(PathBuf, PathBuf)]) -> AppResult<()> {
+    let mut parked = Vec::with_capacity(pairs.len());
+    let mut placed = 0;
+    let result = park_and_place(pairs, &mut parked, &mut placed);
+    if result.is_err() {
+        for (index, temp) in parked.iter().enumerate().take(placed).rev() {
+            let _ = fs::rename(&pairs[index].1, temp);
+        }
+        for (index, temp) in parked.iter().enumerate().rev() {
+            let _ = fs::rename(temp, &pairs[index].0);
+        }
+    }
+    result
+}
+
+fn park_and_place(
+    pairs: &[(PathBuf, PathBuf)],
+    parked: &mut Vec<PathBuf>,
+    placed: &mut usize,
+) -> AppResult<()> {
+    for (from, _) in pairs {
+        let name = from.file_name().unwrap_or_default().to_string_lossy();
+        let temp = available_name(&from.with_file_name(format!(".{}.renaming", name)), true);
+        fs::rename(from, &temp).map_err(|e| AppError::from_io(e, from))?;
+        parked.push(temp);
+    }
+    for (temp, (_, to)) in parked.iter().zip(pairs) {
+        // fs::rename would silently replace a file that appeared since the preview
+        if fs::symlink_metadata(to).is_ok() {
+            return Err(AppError::already_exists(to));
+        }
+        fs::rename(temp, to).map_err(|e| AppError::from_io(e, to))?;
+        *placed += 1;
+    }
+    Ok(())
+}
+
+/// Works out the new name and status of every item
+pub(crate) fn plan(sources: &[PathBuf], rules: &BulkRenameRules) -> AppResult<Vec<RenamePreview>> {
+    let template = Template::parse(rules.template.as_deref().unwrap_or(DEFAULT_TEMPLATE))?;
+    let find = match rules.find.as_deref() {
+        Some(find) if !find.is_empty() => Some(build_regex(
+            find,
+            rules.is_regex,
+            rules.is_case_sensitive,
+            false,
+        )?),
+        _ => None,
+    };
+    let today = Local::now().naive_local();
+
+    let mut plans = Vec::with_capacity(sources.len());
+    // None once the counter has run past what an i64 holds
+    let mut next_counter = Some(rules.counter_start);
+    for source in sources {
+        let counter = next_counter.ok_or_else(|| {
+            AppError::invalid_argument("The counter grows too large for this many items.")
+        })?;
+        let metadata = fs::symlink_metadata(source).map_err(|e| AppError::from_io(e, source))?;
+        let name = source
+            .file_name()
+            .ok_or_else(|| {
+                AppError::invalid_argument(format!("'{}' can't be renamed.", display(source)))
+            })?
+            .to_string_lossy()
+            .into_owned();
+        let (stem, extension) = if rules.include_extension || metadata.is_dir() {
+            (name.as_str(), "")
+        } else {
+            split_extension(&name)
+        };
+
+        let mut stem = match &find {
+            Some(regex) if rules.is_regex => regex.replace_all(stem, rules.replace.as_str()),
+            Some(regex) => regex.replace_all(stem, NoExpand(&rules.replace)),
+            None => stem.into(),
+        }
+        .into_owned();
+        if let Some(case) = rules.case {
+            stem = change_case(&stem, case);
+        }
+
+        let new_name = template.render(&Subject {
+            source,
+            metadata: &metadata,
+            stem: &stem,
+            extension,
+            counter,
+            today,
+        });
+        next_counter = counter.checked_add(rules.counter_step);
+
+        let status = if !is_plain_name(&new_name) {
+            RenameStatus::InvalidName
+        } else if new_name == name {
+            RenameStatus::Unchanged
+        } else {
+            RenameStatus::Rename
+        };
+        plans.push(Plan {
+            target: source.with_file_name(&new_name),
+            source: source.clone(),
+            new_name,
+            status,
+            conflict: None,
+        });
+    }
+
+    find_collisions(&mut plans);
+    Ok(plans.into_iter().map(Plan::into_preview).collect())
+}
+
+struct Plan {
+    source: PathBuf,
+    target: PathBuf,
+    new_name: String,
+    status: RenameStatus,
+    conflict: Option<PathBuf>,
+}
+
+impl Plan {
+    fn into_preview(self) -> RenamePreview {
+        RenamePreview {
+            source: display(&self.source),
+            destination: display(&self.target),
+            new_name: self.new_name,
+            status: self.status,
+            conflict: self.conflict.as_deref().map(display),
+        }
+    }
+}
+
+/// Flags renames onto a name another item of the batch ends up with, or onto an
+/// existing entry that isn't itself renamed away
+fn find_collisions(plans: &mut [Plan]) {
+    let mut by_target: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, plan) in plans.iter().enumerate() {
+        if plan.status != RenameStatus::InvalidName {
+            by_target
+                .entry(path_key(&plan.target))
+                .or_default()
+                .push(index);
+        }
+    }
+    for indices in by_target.values().filter(|indices| indices.len() > 1) {
+        for &index in indices {
+            if plans[index].status == RenameStatus::Rename {
+                let other = indices.iter().find(|&&other| other != index).copied();
+                plans[index].status = RenameStatus::Collision;
+                plans[index].conflict = other.map(|other| plans[other].source.clone());
+            }
+        }
+    }
+
+    let by_source: HashMap<String, usize> = plans
+        .iter()
+        .enumerate()
+        .map(|(index, plan)| (path_key(&plan.source), index))
+        .collect();
+    // An item that stays put blocks the name it holds, which can block another in turn
+    loop {
+        let mut changed = false;
+        for index in 0..plans.len() {
+            let plan = &plans[index];
+            let is_own_name = path_key(&plan.target) == path_key(&plan.source);
+            if plan.status != RenameStatus::Rename
+                || is_own_name
+                || fs::symlink_metadata(&plan.target).is_err()
+            {
+                continue;
+            }
+            let vacated = by_source
+                .get(&path_key(&plan.target))
+                .is_some_and(|&holder| plans[holder].status == RenameStatus::Rename);
+            if !vacated {
+                plans[index].conflict = Some(plans[index].target.clone());
+                plans[index].status = RenameStatus::Collision;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Paths compare without case where the filesystem usually ignores it
+fn path_key(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if cfg!(any(target_os = "windows", target_os = "macos")) {
+        path.to_lowercase()
+    } else {
+        path.into_owned()
+    }
+}
+
+/// Splits `name.ext` into `name` and `.ext`. Hidden files like `.env` are all stem.
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    }
+}
+
+fn change_case(text: &str, case: CaseTransform) -> String {
+    match case {
+        CaseTransform::Lower => text.to_lowercase(),
+        CaseTransform::Upper => text.to_uppercase(),
+        CaseTransform::Title => {
+            let mut at_word_start = true;
+            let mut result = String::with_capacity(text.len());
+            for c in text.chars() {
+                if at_word_start {
+                    result.extend(c.to_uppercase());
+                } else {
+                    result.extend(c.to_lowercase());
+                }
+                at_word_start = !c.is_alphanumeric();
+            }
+            result
+        }
+    }
+}
+
+/// The item a template is rendered for
+struct Subject<'a> {
+    source: &'a Path,
+    metadata: &'a Metadata,
+    stem: &'a str,
+    extension: &'a str,
+    counter: i64,
+    today: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DateSource {
+    Modified,
+    Exif,
+    Today,
+}
+
+#[derive(Debug)]
+enum Piece {
+    Text(String),
+    Name,
+    Extension,
+    Parent,
+    Counter { width: usize },
+    Date { source: DateSource, format: String },
+}
+
+/// A parsed rename template; see `BulkRenameRules::template`
+struct Template {
+    pieces: Vec<Piece>,
+}
+
+impl Template {
+    fn parse(template: &str) -> AppResult<Self> {
+        let invalid = |reason: String| AppError::InvalidPattern {
+            pattern: template.to_string(),
+            reason,
+        };
+
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err(invalid("'}' without a matching '{'".to_string())),
+                '{' => {
+                    let mut token = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        token.push(c);
+                    }
+                    if !closed {
+                        return Err(invalid("'{' without a matching '}'".to_string()));
+                    }
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    pieces.push(Self::token(&token).map_err(invalid)?);
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(Template { pieces })
+    }
+
+    fn token(token: &str) -> Result<Piece, String> {
+        let (name, argument) = match token.split_once(':') {
+            Some((name, argument)) => (name, Some(argument)),
+            None => (token, None),
+        };
+        let date = |source| {
+            let format = argument.unwrap_or(DEFAULT_DATE_FORMAT);
+            // Dates are rendered without a time zone, so offsets and zone names can't be filled in
+            let unusable = |item: Item| match item {
+                Item::Error => true,
+                Item::Fixed(fixed) => matches!(
+                    fixed,
+                    Fixed::TimezoneName
+                        | Fixed::TimezoneOffset
+                        | Fixed::TimezoneOffsetZ
+                        | Fixed::TimezoneOffsetColon
+                        | Fixed::TimezoneOffsetColonZ
+                        | Fixed::TimezoneOffsetDoubleColon
+                        | Fixed::TimezoneOffsetTripleColon
+                        | Fixed::Internal(_)
+                ),
+                _ => false,
+            };
+            if StrftimeItems::new(format).any(unusable) {
+                return Err(format!("'{}' is not a valid date format", format));
+            }
+            Ok(Piece::Date {
+                source,
+                format: format.to_string(),
+            })
+        };
+
+        match (name, argument) {
+            ("name", None) => Ok(Piece::Name),
+            ("ext", None) => Ok(Piece::Extension),
+            ("parent", None) => Ok(Piece::Parent),
+            ("n", None) => Ok(Piece::Counter { width: 0 }),
+            ("n", Some(width)) => width
+                .parse()
+                .ok()
+                .filter(|width| *width <= MAX_COUNTER_WIDTH)
+                .map(|width| Piece::Counter { width })
+                .ok_or_else(|| format!("'{}' is not a counter width", width)),
+            ("mtime", _) => date(DateSource::Modified),
+            ("exif", _) => date(DateSource::Exif),
+            ("date", _) => date(DateSource::Today),
+            _ => Err(format!("unknown token '{{{}}}'", token)),
+        }
+    }
+
+    fn render(&self, subject: &Subject) -> String {
+        let modified = || {
+            subject
+                .metadata
+                .modified()
+                .map(|time| DateTime::<Local>::from(time).naive_local())
+                .unwrap_or(subject.today)
+        };
+
+        let mut name = String::new();
+        for piece in &self.pieces {
+            match piece {
+                Piece::Text(text) => name.push_str(text),
+                Piece::Name => name.push_str(subject.stem),
+                Piece::Extension => name.push_str(subject.extension),
+                Piece::Parent => {
+                    let parent = subject.source.parent().and_then(Path::file_name);
+                    name.push_str(&parent.unwrap_or_default().to_string_lossy());
+                }
+                Piece::Counter { width } => {
+                    let digits =
+                        format!("{:0width$}", subject.counter.unsigned_abs(), width = *width);
+                    if subject.counter < 0 {
+                        name.push('-');
+                    }
+                    name.push_str(&digits);
+                }
+                Piece::Date { source, format } => {
+                    let date = match source {
+                        DateSource::Modified => modified(),
+                        DateSource::Exif => {
+                            exif::date_taken(subject.source).unwrap_or_else(modified)
+                        }
+                        DateSource::Today => subject.today,
+                    };
+                    name.push_str(&date.format(format).to_string());
+                }
+            }
+        }
+        name
+    }
+}
+
+fn normalize_paths(paths: &[String]) -> AppResult<Vec<PathBuf>> {
+    paths
+        .iter()
+        .map(|path| {
+            let path = PathBuf::from(platforms::normalize_path(path));
+            sandbox::ensure_entry_allowed(&path)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+fn display(path: &Path) -> String {
+    platforms::normalize_path(&path.to_string_lossy())
+}
@@ -0,0 +1,70 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use std::time::Duration;
+
+pub const DEFAULT_PAGE_SIZE: usize = 500; // Entries per page when the caller doesn't ask
+pub const MAX_PAGE_SIZE: usize = 5_000; // Upper bound for a single IPC payload
+pub const PAGE_SNAPSHOT_TTL: Duration = Duration::from_secs(300); // How long a listing cursor stays valid
+pub const MAX_PAGE_SNAPSHOTS: usize = 16; // Listings kept alive at once
+pub const STREAM_BATCH_SIZE: usize = 200; // Nodes accumulated before a batch is emitted
+pub const STREAM_BATCH_INTERVAL: Duration = Duration::from_millis(100); // Flush partial batches this often
+pub const STREAM_PROGRESS_INTERVAL: Duration = Duration::from_millis(250); // Progress event cadence
+pub const DEFAULT_DISK_USAGE_TOP_N: usize = 20; // Entries in the largest files/folders report
+pub const MAX_DISK_USAGE_TOP_N: usize = 1_000;
+pub const DISK_USAGE_CANCEL_CHECK_INTERVAL: usize = 256; // Entries between cancellation checks
+pub const DISK_USAGE_CACHE_TTL: Duration = Duration::from_secs(600); // How long a finished report is reused
+pub const MAX_DISK_USAGE_REPORTS: usize = 16; // Finished reports kept at once
+pub const CONTENT_SNIFF_LEN: usize = 8_192; // Leading bytes read to detect a file's content type
+pub const COPY_CHUNK_SIZE: usize = 1024 * 1024; // Bytes copied between cancellation checks
+pub const COPY_PROGRESS_INTERVAL: Duration = Duration::from_millis(200); // Copy progress event cadence
+pub const JOURNAL_LIMIT: usize = 100; // Operations kept for undo
+pub const JOB_CONCURRENCY_PER_DEVICE: usize = 2; // Jobs running at once on one filesystem
+pub const JOB_PROGRESS_INTERVAL: Duration = Duration::from_millis(200); // Job progress event cadence
+pub const MAX_FINISHED_JOBS: usize = 50; // Finished jobs kept for list_jobs
+pub const EXIF_SCAN_LEN: usize = 128 * 1024; // Leading bytes searched for a JPEG's EXIF block
+pub const MAX_TEXT_READ_LEN: u64 = 16 * 1024 * 1024; // Bytes of a text file returned by one read
+pub const BINARY_DUMP_LEN: u64 = 64 * 1024; // Leading bytes of a binary file shown as a hex dump
+pub const ENCODING_SNIFF_LEN: usize = 64 * 1024; // Leading bytes used to guess a text file's encoding
+pub const LINE_INDEX_STRIDE: u64 = 1_024; // Lines between the offsets kept by a line index
+pub const LINE_INDEX_CHUNK: usize = 1024 * 1024; // Bytes read at a time while indexing lines
+pub const LINE_INDEX_PROGRESS_INTERVAL: Duration = Duration::from_millis(250); // Indexing progress event cadence
+pub const MAX_LINE_INDEXES: usize = 8; // Large files kept indexed at once
+pub const MAX_LINE_PAGE: u64 = 10_000; // Lines returned by one read_file_lines call
+pub const MAX_PAGE_LINE_LEN: usize = 16 * 1024; // Bytes of a single line returned before it is clipped
+pub const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(1); // A followed file is checked at least this often
+pub const FOLLOW_MAX_READ: u64 = 1024 * 1024; // Bytes read from a followed file before its lines are sent
+pub const FOLLOW_BACKFILL_CHUNK: u64 = 64 * 1024; // Bytes read at a time when looking back for the last lines
+pub const MAX_FOLLOW_BACKFILL: usize = 10_000; // Lines sent when following starts
+pub const ARCHIVE_PROGRESS_INTERVAL: Duration = Duration::from_millis(200); // Archive progress event cadence
+pub const MAX_COMPRESSION_RATIO: u64 = 100; // Default limit of unpacked bytes per byte of archive
+pub const MIN_EXTRACT_LIMIT: u64 = 1024 * 1024 * 1024; // The unpacked size limit is never lower than this
+pub const MAX_ARCHIVE_ENTRIES: u64 = 1_000_000; // Entries extracted from one archive before giving up
+pub const ARCHIVE_SEPARATOR: &str = "!/"; // Between an archive's path and the path of an entry inside it
+pub const MAX_ARCHIVE_LISTINGS: usize = 8; // Archives kept listed at once for browsing
+pub const MAX_SYMLINK_HOPS: usize = 40; // Links followed inside an archive before giving up
+pub const MAX_ARCHIVE_DEPTH: usize = 256; // Folder levels of an archive entry's path that are listed
+pub const MAX_GIT_LOCATIONS: usize = 256; // Folders whose working tree is remembered for git status
+pub const MAX_GIT_REPOS: usize = 16; // Working trees whose git status is kept at once
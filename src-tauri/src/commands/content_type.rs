@@ -0,0 +1,265 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::constants::CONTENT_SNIFF_LEN;
+use crate::models::pathinfo::{ContentType, FileCategory};
+use infer::MatcherType;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// What the name alone says about a file
+struct NameHint {
+    mime: &'static str,
+    category: FileCategory,
+    language: Option<&'static str>,
+}
+
+const fn hint(
+    mime: &'static str,
+    category: FileCategory,
+    language: Option<&'static str>,
+) -> NameHint {
+    NameHint {
+        mime,
+        category,
+        language,
+    }
+}
+
+const fn source(mime: &'static str, language: &'static str) -> NameHint {
+    hint(mime, FileCategory::SourceCode, Some(language))
+}
+
+/// Detects the content type of the file at `path` from its first bytes and its name.
+/// Symlinks are followed; returns `None` if the file can't be opened or read.
+pub fn detect_content_type(path: &Path) -> Option<ContentType> {
+    let file = File::open(path).ok()?;
+    let mut sample = Vec::with_capacity(CONTENT_SNIFF_LEN);
+    file.take(CONTENT_SNIFF_LEN as u64)
+        .read_to_end(&mut sample)
+        .ok()?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Some(content_type_of(&name, &sample))
+}
+
+/// Whether the file's content isn't text. Unreadable files count as text, so callers
+/// reading them get to report the actual error.
+pub fn is_binary(path: &Path) -> bool {
+    detect_content_type(path).is_some_and(|content_type| content_type.binary)
+}
+
+/// Combines the magic bytes in `sample` with the hints from `name`. The bytes win when
+/// they disagree with the name.
+pub fn content_type_of(name: &str, sample: &[u8]) -> ContentType {
+    let name_hint = hint_for_name(name);
+
+    if let Some(kind) = infer::get(sample) {
+        if kind.matcher_type() != MatcherType::Text {
+            let category = match kind.matcher_type() {
+                MatcherType::Image => FileCategory::Image,
+                MatcherType::Archive => FileCategory::Archive,
+                MatcherType::Doc | MatcherType::Book => FileCategory::Document,
+                _ => FileCategory::Binary,
+            };
+            return ContentType {
+                mime: kind.mime_type().to_string(),
+                category,
+                language: None,
+                binary: true,
+            };
+        }
+    }
+
+    if looks_binary(sample) {
+        return match name_hint {
+            // A format we know to be binary that the signatures didn't cover
+            Some(hint) if !is_textual(&hint) => ContentType {
+                mime: hint.mime.to_string(),
+                category: hint.category,
+                language: None,
+                binary: true,
+            },
+            _ => ContentType {
+                mime: "application/octet-stream".to_string(),
+                category: FileCategory::Binary,
+                language: None,
+                binary: true,
+            },
+        };
+    }
+
+    match name_hint.filter(is_textual) {
+        Some(hint) => ContentType {
+            mime: hint.mime.to_string(),
+            category: hint.category,
+            language: hint.language.map(str::to_string),
+            binary: false,
+        },
+        None => match shebang_language(sample) {
+            Some(language) => ContentType {
+                mime: "text/x-script".to_string(),
+                category: FileCategory::SourceCode,
+                language: Some(language.to_string()),
+                binary: false,
+            },
+            None => ContentType {
+                mime: "text/plain".to_string(),
+                category: FileCategory::Text,
+                language: None,
+                binary: false,
+            },
+        },
+    }
+}
+
+/// Text files don't contain NUL bytes; the same test git uses
+fn looks_binary(sample: &[u8]) -> bool {
+    sample.contains(&0)
+}
+
+fn is_textual(hint: &NameHint) -> bool {
+    match hint.category {
+        FileCategory::SourceCode | FileCategory::Text => true,
+        FileCategory::Image => hint.mime == "image/svg+xml",
+        FileCategory::Document => hint.mime.starts_with("text/"),
+        FileCategory::Archive | FileCategory::Binary => false,
+    }
+}
+
+fn hint_for_name(name: &str) -> Option<NameHint> {
+    let lower = name.to_lowercase();
+    match lower.as_str() {
+        "makefile" | "gnumakefile" => return Some(source("text/x-makefile", "Makefile")),
+        "dockerfile" => return Some(source("text/x-dockerfile", "Dockerfile")),
+        "cmakelists.txt" => return Some(source("text/x-cmake", "CMake")),
+        _ => {}
+    }
+
+    let (_, extension) = lower.rsplit_once('.')?;
+    let hint = match extension {
+        "rs" => source("text/x-rust", "Rust"),
+        "ts" | "mts" | "cts" => source("text/x-typescript", "TypeScript"),
+        "tsx" => source("text/x-typescript", "TSX"),
+        "js" | "mjs" | "cjs" => source("text/javascript", "JavaScript"),
+        "jsx" => source("text/javascript", "JSX"),
+        "py" | "pyw" => source("text/x-python", "Python"),
+        "go" => source("text/x-go", "Go"),
+        "java" => source("text/x-java", "Java"),
+        "kt" | "kts" => source("text/x-kotlin", "Kotlin"),
+        "scala" => source("text/x-scala", "Scala"),
+        "c" | "h" => source("text/x-c", "C"),
+        "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => source("text/x-c++", "C++"),
+        "cs" => source("text/x-csharp", "C#"),
+        "swift" => source("text/x-swift", "Swift"),
+        "rb" => source("text/x-ruby", "Ruby"),
+        "php" => source("text/x-php", "PHP"),
+        "lua" => source("text/x-lua", "Lua"),
+        "dart" => source("text/x-dart", "Dart"),
+        "sh" | "bash" | "zsh" | "fish" => source("text/x-shellscript", "Shell"),
+        "ps1" => source("text/x-powershell", "PowerShell"),
+        "sql" => source("application/sql", "SQL"),
+        "html" | "htm" => source("text/html", "HTML"),
+        "css" => source("text/css", "CSS"),
+        "scss" | "sass" => source("text/x-scss", "SCSS"),
+        "vue" => source("text/x-vue", "Vue"),
+        "svelte" => source("text/x-svelte", "Svelte"),
+        "json" => source("application/json", "JSON"),
+        "toml" => source("application/toml", "TOML"),
+        "yaml" | "yml" => source("application/yaml", "YAML"),
+        "xml" => source("application/xml", "XML"),
+
+        "txt" | "log" => hint("text/plain", FileCategory::Text, None),
+        "csv" => hint("text/csv", FileCategory::Text, None),
+        "md" | "markdown" => hint("text/markdown", FileCategory::Document, Some("Markdown")),
+        "rtf" => hint("text/rtf", FileCategory::Document, None),
+
+        "png" => hint("image/png", FileCategory::Image, None),
+        "jpg" | "jpeg" => hint("image/jpeg", FileCategory::Image, None),
+        "gif" => hint("image/gif", FileCategory::Image, None),
+        "webp" => hint("image/webp", FileCategory::Image, None),
+        "bmp" => hint("image/bmp", FileCategory::Image, None),
+        "ico" => hint("image/vnd.microsoft.icon", FileCategory::Image, None),
+        "svg" => hint("image/svg+xml", FileCategory::Image, Some("SVG")),
+
+        "pdf" => hint("application/pdf", FileCategory::Document, None),
+        "doc" => hint("application/msword", FileCategory::Document, None),
+        "docx" => hint(
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            FileCategory::Document,
+            None,
+        ),
+        "xls" => hint("application/vnd.ms-excel", FileCategory::Document, None),
+        "xlsx" => hint(
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            FileCategory::Document,
+            None,
+        ),
+        "odt" => hint(
+            "application/vnd.oasis.opendocument.text",
+            FileCategory::Document,
+            None,
+        ),
+
+        "zip" => hint("application/zip", FileCategory::Archive, None),
+        "tar" => hint("application/x-tar", FileCategory::Archive, None),
+        "gz" | "tgz" => hint("application/gzip", FileCategory::Archive, None),
+        "bz2" => hint("application/x-bzip2", FileCategory::Archive, None),
+        "xz" => hint("application/x-xz", FileCategory::Archive, None),
+        "7z" => hint("application/x-7z-compressed", FileCategory::Archive, None),
+        "rar" => hint("application/vnd.rar", FileCategory::Archive, None),
+
+        "exe" | "dll" | "so" | "dylib" | "bin" | "o" | "a" | "class" | "wasm" => {
+            hint("application/octet-stream", FileCategory::Binary, None)
+        }
+        _ => return None,
+    };
+    Some(hint)
+}
+
+/// Reads the interpreter from a `#!` line, e.g. `#!/usr/bin/env python3`
+fn shebang_language(sample: &[u8]) -> Option<&'static str> {
+    let first_line = sample.strip_prefix(b"#!")?.split(|&b| b == b'\n').next()?;
+    let first_line = String::from_utf8_lossy(first_line);
+    let mut parts = first_line.split_whitespace();
+    let mut program = parts.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = parts.find(|part| !part.starts_with('-'))?;
+    }
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    match program {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "fish" => Some("Shell"),
+        "python" => Some("Python"),
+        "node" | "deno" => Some("JavaScript"),
+        "ruby" => Some("Ruby"),
+        "perl" => Some("Perl"),
+        "php" => Some("PHP"),
+        "lua" => Some("Lua"),
+        _ => None,
+    }
+}
@@ -0,0 +1,559 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::constants::{COPY_CHUNK_SIZE, COPY_PROGRESS_INTERVAL};
+use crate::commands::filesystem_ops::ensure_directory;
+use crate::commands::journal::{self, Step};
+use crate::commands::move_ops::{available_name, remove_path};
+use crate::commands::sandbox;
+use crate::models::error::{AppError, AppResult};
+use crate::models::journal::OperationKind;
+use crate::models::transfer::{
+    ConflictPolicy, ConflictResolution, CopiedItem, CopyConflict, CopyOptions, CopyProgress,
+    CopySummary,
+};
+use crate::platforms::{self, FileIdentity};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, FileTimes, Metadata};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc as answer_mpsc, Mutex};
+use std::time::Instant;
+use tauri::Window;
+use tokio::sync::mpsc;
+use walkdir::WalkDir;
+
+/// How to reach a running copy: to cancel it, and to answer its conflict prompts
+struct CopyJobChannels {
+    cancel_tx: mpsc::Sender<()>,
+    answer_tx: answer_mpsc::Sender<ConflictResolution>,
+}
+
+// Copies in flight, keyed by job id
+static COPY_JOBS: Lazy<Mutex<HashMap<String, CopyJobChannels>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[tauri::command]
+pub async fn cancel_copy(job_id: String) -> AppResult<()> {
+    stop_copy(&job_id)
+}
+
+/// Cancels the copy registered under `job_id`, if one is running
+pub(crate) fn stop_copy(job_id: &str) -> AppResult<()> {
+    // Take ownership of the channels and drop the lock immediately. Dropping the answer
+    // channel also wakes a copy that is waiting on a conflict.
+    let channels = COPY_JOBS
+        .lock()
+        .map_err(AppError::lock_poisoned)?
+        .remove(job_id);
+
+    if let Some(channels) = channels {
+        // The channel holds one message, so a full channel already has a cancel in it
+        let _ = channels.cancel_tx.try_send(());
+    }
+    Ok(())
+}
+
+/// Answers the `copy-conflict` event a copy is waiting on
+///
+/// Parameters:
+/// - job_id: The copy that asked
+/// - resolution: What to do with the conflicting item, and whether to do the same
+///   for the rest of the job
+///
+/// Returns:
+/// - Ok(()): If the answer was delivered
+/// - Err(AppError): InvalidArgument if no copy with that id is running
+#[tauri::command]
+pub async fn resolve_copy_conflict(
+    job_id: String,
+    resolution: ConflictResolution,
+) -> AppResult<()> {
+    let jobs = COPY_JOBS.lock().map_err(AppError::lock_poisoned)?;
+    let channels = jobs.get(&job_id).ok_or_else(|| {
+        AppError::invalid_argument(format!("No copy with id '{}' is running.", job_id))
+    })?;
+    channels
+        .answer_tx
+        .send(resolution)
+        .map_err(|_| AppError::internal(format!("Copy '{}' has already finished.", job_id)))
+}
+
+/// Copies files and folders into another folder, keeping permissions and timestamps
+///
+/// Emits `copy-progress` with byte and file counts tagged with `job_id`. When an item
+/// already exists at the destination and `options.conflict` isn't set, emits
+/// `copy-conflict` and waits for `resolve_copy_conflict`.
+///
+/// Parameters:
+/// - job_id: Caller-chosen id used to tag events, answer conflicts and cancel the copy
+/// - sources: The files and folders to copy
+/// - destination: The folder to copy them into
+/// - options: Whether to keep symlinks as links, and a conflict policy to use
+///   instead of asking
+///
+/// Returns:
+/// - Ok(CopySummary): Counts, skipped items and the errors of items that couldn't be
+///   copied; a failed item doesn't stop the others
+/// - Err(AppError): If the destination isn't a folder, or InvalidArgument if the job id
+///   is already in use
+#[tauri::command]
+pub async fn copy_items(
+    window: Window,
+    job_id: String,
+    sources: Vec<String>,
+    destination: String,
+    options: Option<CopyOptions>,
+) -> AppResult<CopySummary> {
+    let destination = PathBuf::from(platforms::normalize_path(&destination));
+    sandbox::ensure_allowed(&destination)?;
+    ensure_directory(&destination)?;
+    let sources: Vec<PathBuf> = sources
+        .iter()
+        .map(|source| PathBuf::from(platforms::normalize_path(source)))
+        .collect();
+    for source in &sources {
+        sandbox::ensure_entry_allowed(source)?;
+    }
+
+    let (mut cancel_rx, answer_rx) = register_copy(&job_id)?;
+
+    // Waiting on a conflict answer blocks, so the copy gets a thread of its own
+    let id = job_id.clone();
+    let summary = tokio::task::spawn_blocking(move || {
+        let progress_window = window.clone();
+        let copier = Copier::new(
+            &id,
+            options.unwrap_or_default(),
+            &mut cancel_rx,
+            |progress| {
+                let _ = progress_window.emit("copy-progress", progress);
+            },
+            |conflict| {
+                let _ = window.emit("copy-conflict", conflict.clone());
+                answer_rx.recv().ok()
+            },
+        );
+        copier.run(&sources, &destination)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Copy '{}' stopped unexpectedly: {}", job_id, e)));
+
+    unregister_copy(&job_id);
+    if let Ok(summary) = &summary {
+        record_copies(summary);
+    }
+    summary
+}
+
+/// Makes a copy reachable under `job_id` by `cancel_copy` and `resolve_copy_conflict`.
+/// Returns the receiving ends of the cancel and conflict answer channels.
+pub(crate) fn register_copy(
+    job_id: &str,
+) -> AppResult<(
+    mpsc::Receiver<()>,
+    answer_mpsc::Receiver<ConflictResolution>,
+)> {
+    let (cancel_tx, cancel_rx) = mpsc::channel(1);
+    let (answer_tx, answer_rx) = answer_mpsc::channel();
+    let mut jobs = COPY_JOBS.lock().map_err(AppError::lock_poisoned)?;
+    if jobs.contains_key(job_id) {
+        return Err(AppError::invalid_argument(format!(
+            "A copy with id '{}' is already running.",
+            job_id
+        )));
+    }
+    jobs.insert(
+        job_id.to_string(),
+        CopyJobChannels {
+            cancel_tx,
+            answer_tx,
+        },
+    );
+    Ok((cancel_rx, answer_rx))
+}
+
+pub(crate) fn unregister_copy(job_id: &str) {
+    if let Ok(mut jobs) = COPY_JOBS.lock() {
+        jobs.remove(job_id);
+    }
+}
+
+/// Records the new items of a finished copy in the journal for undo
+pub(crate) fn record_copies(summary: &CopySummary) {
+    let steps = summary
+        .copied
+        .iter()
+        .map(|item| Step::copied(Path::new(&item.source), Path::new(&item.destination)))
+        .collect();
+    journal::record(OperationKind::Copy, steps);
+}
+
+/// One copy job. Recoverable failures are collected and the copy moves on to the next
+/// item; cancellation and a `Fail` conflict answer stop it.
+pub(crate) struct Copier<'a, P, A>
+where
+    P: FnMut(CopyProgress),
+    A: FnMut(&CopyConflict) -> Option<ConflictResolution>,
+{
+    options: CopyOptions,
+    cancel_rx: &'a mut mpsc::Receiver<()>,
+    on_progress: P,
+    /// Asks how to resolve a conflict; `None` means the job was cancelled meanwhile
+    ask: A,
+    standing_policy: Option<ConflictPolicy>,
+    progress: CopyProgress,
+    start_time: Instant,
+    last_progress: Instant,
+    skipped: Vec<String>,
+    copied: Vec<CopiedItem>,
+    errors: Vec<AppError>,
+    aborted: bool,
+    /// Folders already copied while following symlinks, so link loops end
+    visited: HashSet<FileIdentity>,
+    buffer: Vec<u8>,
+}
+
+impl<'a, P, A> Copier<'a, P, A>
+where
+    P: FnMut(CopyProgress),
+    A: FnMut(&CopyConflict) -> Option<ConflictResolution>,
+{
+    pub fn new(
+        job_id: &str,
+        options: CopyOptions,
+        cancel_rx: &'a mut mpsc::Receiver<()>,
+        on_progress: P,
+        ask: A,
+    ) -> Self {
+        Copier {
+            standing_policy: options.conflict,
+            options,
+            cancel_rx,
+            on_progress,
+            ask,
+            progress: CopyProgress {
+                job_id: job_id.to_string(),
+                ..Default::default()
+            },
+            start_time: Instant::now(),
+            last_progress: Instant::now(),
+            skipped: Vec::new(),
+            copied: Vec::new(),
+            errors: Vec::new(),
+            aborted: false,
+            visited: HashSet::new(),
+            buffer: vec![0; COPY_CHUNK_SIZE],
+        }
+    }
+
+    pub fn run(mut self, sources: &[PathBuf], destination: &Path) -> CopySummary {
+        for source in sources {
+            let (files, bytes) = self.measure(source);
+            self.progress.total_files += files;
+            self.progress.total_bytes += bytes;
+        }
+        self.report_progress(true);
+
+        let mut cancelled = false;
+        for source in sources {
+            let Some(name) = source.file_name() else {
+                self.errors.push(AppError::invalid_argument(format!(
+                    "'{}' can't be copied.",
+                    source.display()
+                )));
+                continue;
+            };
+            if destination.starts_with(source) {
+                self.errors.push(AppError::invalid_argument(format!(
+                    "Can't copy '{}' into itself.",
+                    platforms::normalize_path(&source.to_string_lossy())
+                )));
+                continue;
+            }
+
+            let target = destination.join(name);
+            let existed = fs::symlink_metadata(&target).is_ok();
+            match self.copy_entry(source, &target) {
+                // A merge into or replacement of an existing item isn't a new copy
+                Ok(Some(copy)) if !existed || copy != target => self.copied.push(CopiedItem {
+                    source: platforms::normalize_path(&source.to_string_lossy()),
+                    destination: platforms::normalize_path(&copy.to_string_lossy()),
+                }),
+                Ok(_) => {}
+                Err(AppError::Cancelled { .. }) => {
+                    cancelled = true;
+                    break;
+                }
+                Err(err) => {
+                    self.errors.push(err);
+                    if self.aborted {
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.progress.current_path = None;
+        self.report_progress(true);
+        CopySummary {
+            job_id: self.progress.job_id,
+            bytes_copied: self.progress.bytes_copied,
+            files_copied: self.progress.files_copied,
+            skipped: self.skipped,
+            copied: self.copied,
+            errors: self.errors,
+            cancelled,
+            processing_time_ms: self.start_time.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Returns where the item was copied to, or `None` if it was skipped
+    fn copy_entry(&mut self, source: &Path, target: &Path) -> AppResult<Option<PathBuf>> {
+        self.check_cancelled()?;
+        // A link followed out of the allowed folders would copy what's outside them
+        if !self.options.preserve_symlinks && source.is_symlink() {
+            sandbox::ensure_allowed(source)?;
+        }
+        let metadata = self.metadata(source)?;
+
+        let target = match fs::symlink_metadata(target) {
+            Err(_) => target.to_path_buf(),
+            Ok(existing) => match self.resolve_conflict(source, target, &metadata, &existing)? {
+                ConflictPolicy::Skip => {
+                    self.skip(source);
+                    return Ok(None);
+                }
+                ConflictPolicy::KeepBoth => available_name(target, metadata.is_dir()),
+                ConflictPolicy::Overwrite => {
+                    // Folders merge and files are replaced by a rename at the end of their
+                    // copy; anything else has to go first
+                    let merges = metadata.is_dir() && existing.is_dir();
+                    let renames_over = metadata.is_file() && existing.is_file();
+                    if !merges && !renames_over {
+                        remove_path(target).map_err(|e| AppError::from_io(e, target))?;
+                    }
+                    target.to_path_buf()
+                }
+                ConflictPolicy::Fail => {
+                    self.aborted = true;
+                    return Err(AppError::already_exists(target));
+                }
+            },
+        };
+
+        if metadata.file_type().is_symlink() {
+            self.copy_link(source, &target)?;
+        } else if metadata.is_dir() {
+            self.copy_dir(source, &target, &metadata)?;
+        } else {
+            self.copy_file(source, &target, &metadata)?;
+        }
+        Ok(Some(target))
+    }
+
+    fn copy_dir(&mut self, source: &Path, target: &Path, metadata: &Metadata) -> AppResult<()> {
+        if !self.options.preserve_symlinks {
+            if let Some(identity) = platforms::file_identity(source) {
+                if !self.visited.insert(identity) {
+                    return Ok(());
+                }
+            }
+        }
+        if !target.is_dir() {
+            fs::create_dir(target).map_err(|e| AppError::from_io(e, target))?;
+        }
+
+        let entries = fs::read_dir(source).map_err(|e| AppError::from_io(e, source))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| AppError::from_io(e, source))?;
+            match self.copy_entry(&entry.path(), &target.join(entry.file_name())) {
+                Ok(_) => {}
+                Err(err @ AppError::Cancelled { .. }) => return Err(err),
+                Err(err) if self.aborted => return Err(err),
+                Err(err) => self.errors.push(err),
+            }
+        }
+
+        // Applied last so a read-only folder can still be filled
+        fs::set_permissions(target, metadata.permissions())
+            .map_err(|e| AppError::from_io(e, target))?;
+        if let Ok(folder) = File::open(target) {
+            let _ = folder.set_times(file_times(metadata));
+        }
+        Ok(())
+    }
+
+    /// Copies through a temporary sibling that is renamed into place once complete, so
+    /// a cancelled or failed copy never leaves a truncated file at `target`
+    fn copy_file(&mut self, source: &Path, target: &Path, metadata: &Metadata) -> AppResult<()> {
+        self.progress.current_path = Some(platforms::normalize_path(&source.to_string_lossy()));
+        let name = target.file_name().unwrap_or_default().to_string_lossy();
+        let partial = available_name(&target.with_file_name(format!(".{}.copying", name)), true);
+
+        let result = self.copy_contents(source, &partial, metadata);
+        let result = result
+            .and_then(|()| fs::rename(&partial, target).map_err(|e| AppError::from_io(e, target)));
+        if result.is_err() {
+            let _ = fs::remove_file(&partial);
+        }
+        result?;
+
+        self.progress.files_copied += 1;
+        self.report_progress(false);
+        Ok(())
+    }
+
+    fn copy_contents(
+        &mut self,
+        source: &Path,
+        partial: &Path,
+        metadata: &Metadata,
+    ) -> AppResult<()> {
+        let mut reader = File::open(source).map_err(|e| AppError::from_io(e, source))?;
+        let mut writer = File::create(partial).map_err(|e| AppError::from_io(e, partial))?;
+        loop {
+            self.check_cancelled()?;
+            let read = reader
+                .read(&mut self.buffer)
+                .map_err(|e| AppError::from_io(e, source))?;
+            if read == 0 {
+                break;
+            }
+            writer
+                .write_all(&self.buffer[..read])
+                .map_err(|e| AppError::from_io(e, partial))?;
+            self.progress.bytes_copied += read as u64;
+            self.report_progress(false);
+        }
+
+        writer
+            .set_permissions(metadata.permissions())
+            .map_err(|e| AppError::from_io(e, partial))?;
+        // Not every filesystem keeps access times; the copy is still good without them
+        let _ = writer.set_times(file_times(metadata));
+        Ok(())
+    }
+
+    fn copy_link(&mut self, source: &Path, target: &Path) -> AppResult<()> {
+        let link_target = fs::read_link(source).map_err(|e| AppError::from_io(e, source))?;
+        let target_is_dir = fs::metadata(source).is_ok_and(|m| m.is_dir());
+        platforms::create_symlink(&link_target, target, target_is_dir)
+            .map_err(|e| AppError::from_io(e, target))?;
+        self.progress.files_copied += 1;
+        Ok(())
+    }
+
+    fn resolve_conflict(
+        &mut self,
+        source: &Path,
+        target: &Path,
+        metadata: &Metadata,
+        existing: &Metadata,
+    ) -> AppResult<ConflictPolicy> {
+        if let Some(policy) = self.standing_policy {
+            return Ok(policy);
+        }
+        // Show the latest counts while the user decides
+        self.report_progress(true);
+
+        let conflict = CopyConflict {
+            job_id: self.progress.job_id.clone(),
+            source: platforms::normalize_path(&source.to_string_lossy()),
+            destination: platforms::normalize_path(&target.to_string_lossy()),
+            source_is_dir: metadata.is_dir(),
+            destination_is_dir: existing.is_dir(),
+        };
+        let resolution = (self.ask)(&conflict).ok_or(AppError::Cancelled {
+            path: Some(conflict.destination),
+        })?;
+        if resolution.apply_to_all {
+            self.standing_policy = Some(resolution.policy);
+        }
+        Ok(resolution.policy)
+    }
+
+    /// Records a skipped item and takes it out of the totals
+    fn skip(&mut self, source: &Path) {
+        let (files, bytes) = self.measure(source);
+        self.progress.total_files = self.progress.total_files.saturating_sub(files);
+        self.progress.total_bytes = self.progress.total_bytes.saturating_sub(bytes);
+        self.skipped
+            .push(platforms::normalize_path(&source.to_string_lossy()));
+    }
+
+    /// Number of files (and links, when kept as links) and their bytes under `source`
+    fn measure(&self, source: &Path) -> (u64, u64) {
+        WalkDir::new(source)
+            .follow_links(!self.options.preserve_symlinks)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| !entry.file_type().is_dir())
+            .fold((0, 0), |(files, bytes), entry| {
+                let size = entry
+                    .metadata()
+                    .ok()
+                    .filter(|metadata| metadata.is_file())
+                    .map_or(0, |metadata| metadata.len());
+                (files + 1, bytes + size)
+            })
+    }
+
+    fn metadata(&self, path: &Path) -> AppResult<Metadata> {
+        let metadata = if self.options.preserve_symlinks {
+            fs::symlink_metadata(path)
+        } else {
+            fs::metadata(path)
+        };
+        metadata.map_err(|e| AppError::from_io(e, path))
+    }
+
+    fn check_cancelled(&mut self) -> AppResult<()> {
+        if self.cancel_rx.try_recv().is_ok() {
+            return Err(AppError::Cancelled { path: None });
+        }
+        Ok(())
+    }
+
+    fn report_progress(&mut self, force: bool) {
+        if force || self.last_progress.elapsed() >= COPY_PROGRESS_INTERVAL {
+            self.progress.processing_time_ms = self.start_time.elapsed().as_millis() as u64;
+            (self.on_progress)(self.progress.clone());
+            self.last_progress = Instant::now();
+        }
+    }
+}
+
+fn file_times(metadata: &Metadata) -> FileTimes {
+    let mut times = FileTimes::new();
+    if let Ok(accessed) = metadata.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    if let Ok(modified) = metadata.modified() {
+        times = times.set_modified(modified);
+    }
+    times
+}
@@ -0,0 +1,381 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+use crate::commands::constants::{
+    DEFAULT_DISK_USAGE_TOP_N, DISK_USAGE_CACHE_TTL, DISK_USAGE_CANCEL_CHECK_INTERVAL,
+    MAX_DISK_USAGE_REPORTS, MAX_DISK_USAGE_TOP_N, STREAM_PROGRESS_INTERVAL,
+};
+use crate::commands::filesystem_ops::ensure_directory;
+use crate::commands::sandbox;
+use crate::models::disk_usage::{DirectoryUsage, DiskUsageProgress, DiskUsageReport, FileUsage};
+use crate::models::error::{AppError, AppResult};
+use crate::platforms::{self, FileIdentity};
+use once_cell::sync::Lazy;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::Window;
+use tokio::sync::mpsc;
+use walkdir::WalkDir;
+
+// Cancellation channels of the scans in flight, keyed by job id
+static USAGE_CANCEL_TX: Lazy<Mutex<HashMap<String, mpsc::Sender<()>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A completed report and when it was last handed out
+struct CachedUsage {
+    report: DiskUsageReport,
+    last_used: Instant,
+}
+
+// Completed reports keyed by the canonical scanned root; dropped when the watcher sees
+// a change under the root, when they expire, or when newer reports push them out
+static USAGE_CACHE: Lazy<Mutex<HashMap<PathBuf, CachedUsage>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[tauri::command]
+pub async fn cancel_disk_usage(job_id: String) -> AppResult<()> {
+    // Take ownership of the sender and drop the lock immediately
+    let tx = USAGE_CANCEL_TX
+        .lock()
+        .map_err(AppError::lock_poisoned)?
+        .remove(&job_id);
+
+    if let Some(tx) = tx {
+        let _ = tx.send(()).await;
+    }
+    Ok(())
+}
+
+/// Computes the recursive size of a folder and reports what takes up the most space
+///
+/// Emits `disk-usage-progress` with running counts tagged with `job_id`.
+///
+/// Parameters:
+/// - job_id: Caller-chosen id used to tag events and to cancel the scan
+/// - path: The folder to analyze
+/// - top_n: Number of entries in the largest files/folders lists (defaults to 20)
+/// - refresh: Ignore a cached report and scan again
+///
+/// Returns:
+/// - Ok(DiskUsageReport): Totals for the folder and its largest files and subfolders
+/// - Err(AppError): If the path isn't a folder, or InvalidArgument if the job id is
+///   already in use
+#[tauri::command]
+pub async fn analyze_disk_usage(
+    window: Window,
+    job_id: String,
+    path: String,
+    top_n: Option<usize>,
+    refresh: Option<bool>,
+) -> AppResult<DiskUsageReport> {
+    let normalized_path = platforms::normalize_path(&path);
+    let root = PathBuf::from(&normalized_path);
+    sandbox::ensure_allowed(&root)?;
+    ensure_directory(&root)?;
+    let top_n = top_n
+        .unwrap_or(DEFAULT_DISK_USAGE_TOP_N)
+        .clamp(1, MAX_DISK_USAGE_TOP_N);
+
+    if !refresh.unwrap_or(false) {
+        if let Some(report) = cached_report(&root, &job_id, top_n) {
+            return Ok(report);
+        }
+    }
+
+    let (tx, mut rx) = mpsc::channel(1);
+    {
+        let mut channels = USAGE_CANCEL_TX.lock().map_err(AppError::lock_poisoned)?;
+        if channels.contains_key(&job_id) {
+            return Err(AppError::invalid_argument(format!(
+                "A disk usage scan with id '{}' is already running.",
+                job_id
+            )));
+        }
+        channels.insert(job_id.clone(), tx);
+    }
+
+    let id = job_id.clone();
+    let scan_root = root.clone();
+    let report = tokio::task::spawn_blocking(move || {
+        compute_disk_usage(&id, &scan_root, top_n, &mut rx, |progress| {
+            let _ = window.emit("disk-usage-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| {
+        AppError::internal(format!(
+            "Disk usage scan '{}' stopped unexpectedly: {}",
+            job_id, e
+        ))
+    });
+
+    if let Ok(mut channels) = USAGE_CANCEL_TX.lock() {
+        channels.remove(&job_id);
+    }
+    let report = report?;
+    if !report.cancelled {
+        store_report(&root, &report);
+    }
+
+    Ok(report)
+}
+
+/// Drops every cached report whose scanned folder contains one of the changed paths
+pub fn invalidate_cached_usage(changed_paths: &[PathBuf]) {
+    let changed: Vec<PathBuf> = changed_paths.iter().map(|path| cache_key(path)).collect();
+    if let Ok(mut cache) = USAGE_CACHE.lock() {
+        cache.retain(|root, _| !changed.iter().any(|path| path.starts_with(root)));
+    }
+}
+
+/// Returns the cached report for `root` if it has at least `top_n` entries per list
+pub(crate) fn cached_report(root: &Path, job_id: &str, top_n: usize) -> Option<DiskUsageReport> {
+    let key = cache_key(root);
+    let mut cache = USAGE_CACHE.lock().ok()?;
+    let entry = cache.get_mut(&key)?;
+    if entry.last_used.elapsed() >= DISK_USAGE_CACHE_TTL {
+        cache.remove(&key);
+        return None;
+    }
+    entry.last_used = Instant::now();
+    let cached = &entry.report;
+    let complete = |len: usize, total: u64| len >= top_n || len as u64 >= total;
+    if !complete(cached.largest_files.len(), cached.root.file_count)
+        || !complete(
+            cached.largest_directories.len(),
+            cached.root.directory_count,
+        )
+    {
+        return None;
+    }
+
+    let mut report = cached.clone();
+    report.job_id = job_id.to_string();
+    report.from_cache = true;
+    report.processing_time_ms = 0;
+    report.largest_files.truncate(top_n);
+    report.largest_directories.truncate(top_n);
+    Some(report)
+}
+
+pub(crate) fn store_report(root: &Path, report: &DiskUsageReport) {
+    let key = cache_key(root);
+    if let Ok(mut cache) = USAGE_CACHE.lock() {
+        cache.remove(&key);
+        evict_reports(&mut cache);
+        cache.insert(
+            key,
+            CachedUsage {
+                report: report.clone(),
+                last_used: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Drops expired reports and, if still over the limit, the least recently used ones
+fn evict_reports(cache: &mut HashMap<PathBuf, CachedUsage>) {
+    cache.retain(|_, cached| cached.last_used.elapsed() < DISK_USAGE_CACHE_TTL);
+
+    while cache.len() >= MAX_DISK_USAGE_REPORTS {
+        let oldest = cache
+            .iter()
+            .min_by_key(|(_, cached)| cached.last_used)
+            .map(|(root, _)| root.clone());
+        match oldest {
+            Some(root) => cache.remove(&root),
+            None => break,
+        };
+    }
+}
+
+/// The canonical form of `path`, so one folder is cached once however it is spelled.
+/// A path that no longer exists is resolved through its parent.
+fn cache_key(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .ok()
+        .or_else(|| {
+            let parent = path.parent()?.canonicalize().ok()?;
+            Some(parent.join(path.file_name()?))
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Walks the folder contents-first so each folder's total is known when it is
+/// reached, counting hard-linked files once and stopping when cancelled
+pub(crate) fn compute_disk_usage(
+    job_id: &str,
+    root: &Path,
+    top_n: usize,
+    cancel_rx: &mut mpsc::Receiver<()>,
+    mut on_progress: impl FnMut(DiskUsageProgress),
+) -> DiskUsageReport {
+    let start_time = Instant::now();
+    let mut last_progress = Instant::now();
+    let mut progress = DiskUsageProgress {
+        job_id: job_id.to_string(),
+        ..Default::default()
+    };
+    // Running totals of folders whose contents are still being walked
+    let mut pending: HashMap<PathBuf, DirectoryUsage> = HashMap::new();
+    let mut seen_links: HashSet<FileIdentity> = HashSet::new();
+    let mut largest_files: BinaryHeap<Reverse<(u64, String, u64)>> = BinaryHeap::new();
+    let mut largest_directories: BinaryHeap<Reverse<(u64, String, DirectoryUsage)>> =
+        BinaryHeap::new();
+    let mut root_usage = DirectoryUsage {
+        path: platforms::normalize_path(&root.to_string_lossy()),
+        ..Default::default()
+    };
+    let mut unreadable_entries = 0;
+    let mut cancelled = false;
+
+    let walker = WalkDir::new(root)
+        .follow_links(false)
+        .contents_first(true)
+        .min_depth(1);
+
+    for (entries_seen, entry) in walker.into_iter().enumerate() {
+        if entries_seen.is_multiple_of(DISK_USAGE_CANCEL_CHECK_INTERVAL)
+            && cancel_rx.try_recv().is_ok()
+        {
+            cancelled = true;
+            break;
+        }
+
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => {
+                unreadable_entries += 1;
+                continue;
+            }
+        };
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => {
+                unreadable_entries += 1;
+                continue;
+            }
+        };
+        let parent = entry.path().parent().unwrap_or(root).to_path_buf();
+
+        if entry.file_type().is_dir() {
+            let mut usage = pending.remove(entry.path()).unwrap_or_default();
+            usage.path = platforms::normalize_path(&entry.path().to_string_lossy());
+            usage.disk_size += platforms::allocated_size(&metadata);
+            progress.directories_scanned += 1;
+
+            let parent_usage = usage_for(&mut pending, &mut root_usage, root, parent);
+            parent_usage.apparent_size += usage.apparent_size;
+            parent_usage.disk_size += usage.disk_size;
+            parent_usage.file_count += usage.file_count;
+            parent_usage.directory_count += usage.directory_count + 1;
+
+            push_top(
+                &mut largest_directories,
+                top_n,
+                Reverse((usage.disk_size, usage.path.clone(), usage)),
+            );
+        } else {
+            progress.files_scanned += 1;
+            // Every link to a file shares its data; only the first one found is counted
+            if let Some(identity) = platforms::hard_link_identity(&metadata) {
+                if !seen_links.insert(identity) {
+                    continue;
+                }
+            }
+            let apparent_size = metadata.len();
+            let disk_size = platforms::allocated_size(&metadata);
+            progress.bytes_scanned += apparent_size;
+
+            let parent_usage = usage_for(&mut pending, &mut root_usage, root, parent);
+            parent_usage.apparent_size += apparent_size;
+            parent_usage.disk_size += disk_size;
+            parent_usage.file_count += 1;
+
+            let path = platforms::normalize_path(&entry.path().to_string_lossy());
+            push_top(
+                &mut largest_files,
+                top_n,
+                Reverse((disk_size, path, apparent_size)),
+            );
+        }
+
+        if last_progress.elapsed() >= STREAM_PROGRESS_INTERVAL {
+            progress.processing_time_ms = start_time.elapsed().as_millis() as u64;
+            on_progress(progress.clone());
+            last_progress = Instant::now();
+        }
+    }
+
+    let largest_files = largest_files
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((disk_size, path, apparent_size))| FileUsage {
+            path,
+            apparent_size,
+            disk_size,
+        })
+        .collect();
+    let largest_directories = largest_directories
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((_, _, usage))| usage)
+        .collect();
+
+    DiskUsageReport {
+        job_id: job_id.to_string(),
+        root: root_usage,
+        largest_files,
+        largest_directories,
+        unreadable_entries,
+        cancelled,
+        from_cache: false,
+        processing_time_ms: start_time.elapsed().as_millis() as u64,
+    }
+}
+
+/// The running totals of a folder, which is the report's root for top-level entries
+fn usage_for<'a>(
+    pending: &'a mut HashMap<PathBuf, DirectoryUsage>,
+    root_usage: &'a mut DirectoryUsage,
+    root: &Path,
+    folder: PathBuf,
+) -> &'a mut DirectoryUsage {
+    if folder == root {
+        root_usage
+    } else {
+        pending.entry(folder).or_default()
+    }
+}
+
+/// Keeps only the `limit` largest items in a min-heap
+fn push_top<T: Ord>(heap: &mut BinaryHeap<Reverse<T>>, limit: usize, item: Reverse<T>) {
+    heap.push(item);
+    if heap.len() > limit {
+        heap.pop();
+    }
+}
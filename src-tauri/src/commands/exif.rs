@@ -0,0 +1,164 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+//! Just enough of EXIF to find when a photo was taken, in JPEG files and TIFF-based
+//! raw formats
+
+use crate::commands::constants::EXIF_SCAN_LEN;
+use chrono::NaiveDateTime;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+const TAG_DATE_TIME_DIGITIZED: u16 = 0x9004;
+const TYPE_ASCII: u16 = 2;
+
+/// The date and time the photo at `path` was taken, in the camera's local time.
+/// Falls back to the digitized and then the last-changed date.
+pub fn date_taken(path: &Path) -> Option<NaiveDateTime> {
+    let mut data = Vec::with_capacity(EXIF_SCAN_LEN);
+    File::open(path)
+        .ok()?
+        .take(EXIF_SCAN_LEN as u64)
+        .read_to_end(&mut data)
+        .ok()?;
+    date_in(&data)
+}
+
+/// The EXIF date in a JPEG or TIFF file's leading bytes
+pub fn date_in(data: &[u8]) -> Option<NaiveDateTime> {
+    let tiff = if data.starts_with(&[0xFF, 0xD8]) {
+        jpeg_exif(data)?
+    } else {
+        data
+    };
+    let tiff = Tiff::new(tiff)?;
+
+    let ifd0 = tiff.u32_at(4)? as usize;
+    let exif_ifd = tiff
+        .entry(ifd0, TAG_EXIF_IFD)
+        .map(|(_, _, offset)| offset as usize);
+    let dates = [
+        exif_ifd.and_then(|ifd| tiff.ascii(ifd, TAG_DATE_TIME_ORIGINAL)),
+        exif_ifd.and_then(|ifd| tiff.ascii(ifd, TAG_DATE_TIME_DIGITIZED)),
+        tiff.ascii(ifd0, TAG_DATE_TIME),
+    ];
+    dates
+        .into_iter()
+        .flatten()
+        .find_map(|text| NaiveDateTime::parse_from_str(text.trim(), "%Y:%m:%d %H:%M:%S").ok())
+}
+
+/// The TIFF structure inside a JPEG's `Exif` APP1 segment
+fn jpeg_exif(data: &[u8]) -> Option<&[u8]> {
+    let mut pos = 2;
+    loop {
+        if *data.get(pos)? != 0xFF {
+            return None;
+        }
+        let marker = *data.get(pos + 1)?;
+        // Start of scan or end of image: the metadata segments are over
+        if marker == 0xDA || marker == 0xD9 {
+            return None;
+        }
+        let len = u16::from_be_bytes([*data.get(pos + 2)?, *data.get(pos + 3)?]) as usize;
+        let segment = data.get(pos + 4..pos + 2 + len)?;
+        if marker == 0xE1 {
+            if let Some(tiff) = segment.strip_prefix(b"Exif\0\0") {
+                return Some(tiff);
+            }
+        }
+        pos += 2 + len;
+    }
+}
+
+struct Tiff<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(..4)? {
+            b"II*\0" => true,
+            b"MM\0*" => false,
+            _ => return None,
+        };
+        Some(Tiff {
+            data,
+            little_endian,
+        })
+    }
+
+    fn u16_at(&self, pos: usize) -> Option<u16> {
+        let bytes = [*self.data.get(pos)?, *self.data.get(pos + 1)?];
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32_at(&self, pos: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(pos..pos + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    /// The type, count and value (or value offset) of `tag` in the IFD at `ifd`
+    fn entry(&self, ifd: usize, tag: u16) -> Option<(u16, u32, u32)> {
+        let count = self.u16_at(ifd)? as usize;
+        (0..count).find_map(|index| {
+            let pos = ifd + 2 + index * 12;
+            (self.u16_at(pos)? == tag)
+                .then(|| {
+                    Some((
+                        self.u16_at(pos + 2)?,
+                        self.u32_at(pos + 4)?,
+                        self.u32_at(pos + 8)?,
+                    ))
+                })
+                .flatten()
+        })
+    }
+
+    fn ascii(&self, ifd: usize, tag: u16) -> Option<&'a str> {
+        let (kind, count, value) = self.entry(ifd, tag)?;
+        // Dates are 20 bytes, so always stored at an offset rather than inline
+        if kind != TYPE_ASCII || count <= 4 {
+            return None;
+        }
+        let start = value as usize;
+        let bytes = self.data.get(start..start + count as usize)?;
+        let text = bytes.split(|&byte| byte == 0).next()?;
+        std::str::from_utf8(text).ok()
+    }
+}
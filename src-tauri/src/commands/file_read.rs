@@ -0,0 +1,197 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::constants::{BINARY_DUMP_LEN, ENCODING_SNIFF_LEN, MAX_TEXT_READ_LEN};
+use crate::commands::content_type::content_type_of;
+use crate::commands::file_write;
+use crate::models::error::{AppError, AppResult};
+use crate::models::file_content::{FileContent, LineEnding};
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Reads the file at `path` as text in whatever encoding it uses, or as a hex dump
+/// when it isn't text. Text beyond `MAX_TEXT_READ_LEN` bytes is left out.
+pub(crate) fn read_text(path: &Path) -> AppResult<FileContent> {
+    let file = File::open(path).map_err(|e| AppError::from_io(e, path))?;
+    let metadata = file.metadata().map_err(|e| AppError::from_io(e, path))?;
+    let size = metadata.len();
+    let mut bytes = Vec::new();
+    file.take(MAX_TEXT_READ_LEN)
+        .read_to_end(&mut bytes)
+        .map_err(|e| AppError::from_io(e, path))?;
+
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let etag = file_write::etag(&metadata);
+    Ok(decode(&name, &bytes, size, etag))
+}
+
+/// Turns the first bytes of a file of `size` bytes into what `read_file_content`
+/// returns
+pub(crate) fn decode(name: &str, bytes: &[u8], size: u64, etag: String) -> FileContent {
+    let truncated = (bytes.len() as u64) < size;
+
+    let Some((encoding, bom_len)) = sniff_encoding(name, bytes, truncated) else {
+        let shown = &bytes[..bytes.len().min(BINARY_DUMP_LEN as usize)];
+        return FileContent {
+            content: hex_dump(shown),
+            etag,
+            encoding: None,
+            has_bom: false,
+            line_ending: LineEnding::None,
+            size,
+            truncated: (shown.len() as u64) < size,
+            is_binary: true,
+        };
+    };
+
+    let content = decode_with(encoding, &bytes[bom_len..], !truncated);
+    FileContent {
+        line_ending: line_ending_of(&content),
+        content,
+        etag,
+        encoding: Some(encoding.name().to_string()),
+        has_bom: bom_len > 0,
+        size,
+        truncated,
+        is_binary: false,
+    }
+}
+
+/// The encoding of the text starting with `bytes` and the length of its byte order
+/// mark, or none when it isn't text. `truncated` says the file goes on past `bytes`.
+pub(crate) fn sniff_encoding(
+    name: &str,
+    bytes: &[u8],
+    truncated: bool,
+) -> Option<(&'static Encoding, usize)> {
+    if let Some(found) = Encoding::for_bom(bytes) {
+        return Some(found);
+    }
+    if let Some(encoding) = utf16_without_bom(bytes) {
+        return Some((encoding, 0));
+    }
+    if content_type_of(name, &bytes[..bytes.len().min(ENCODING_SNIFF_LEN)]).binary {
+        return None;
+    }
+    Some((guess_encoding(bytes, truncated), 0))
+}
+
+/// UTF-16 text without a BOM: mostly ASCII, so many pairs of bytes are a printable
+/// character next to a zero
+fn utf16_without_bom(bytes: &[u8]) -> Option<&'static Encoding> {
+    let sample = &bytes[..bytes.len().min(ENCODING_SNIFF_LEN)];
+    let pairs = sample.len() / 2;
+    if pairs < 2 {
+        return None;
+    }
+    let ascii_pairs = |text: usize| {
+        sample
+            .chunks_exact(2)
+            .filter(|pair| {
+                pair[1 - text] == 0
+                    && (pair[text].is_ascii_graphic() || pair[text].is_ascii_whitespace())
+            })
+            .count()
+    };
+    let (little, big) = (ascii_pairs(0), ascii_pairs(1));
+    if little * 10 >= pairs * 4 && big * 20 < pairs {
+        Some(UTF_16LE)
+    } else if big * 10 >= pairs * 4 && little * 20 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// UTF-8 when the bytes are valid UTF-8, otherwise the detector's best guess among
+/// the legacy encodings
+fn guess_encoding(bytes: &[u8], truncated: bool) -> &'static Encoding {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return UTF_8,
+        // A character cut in half by the read limit doesn't count against UTF-8
+        Err(e) if truncated && e.error_len().is_none() => return UTF_8,
+        Err(_) => {}
+    }
+    let mut detector = EncodingDetector::new();
+    detector.feed(&bytes[..bytes.len().min(ENCODING_SNIFF_LEN)], true);
+    detector.guess(None, true)
+}
+
+/// Decodes `bytes`; unless `complete`, a character cut off at the end is dropped
+/// rather than shown as a replacement character
+pub(crate) fn decode_with(encoding: &'static Encoding, bytes: &[u8], complete: bool) -> String {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let capacity = decoder
+        .max_utf8_buffer_length(bytes.len())
+        .unwrap_or(bytes.len());
+    let mut text = String::with_capacity(capacity);
+    let _ = decoder.decode_to_string(bytes, &mut text, complete);
+    text
+}
+
+fn line_ending_of(text: &str) -> LineEnding {
+    let crlf = text.matches("\r\n").count();
+    let lf = text.matches('\n').count() - crlf;
+    match (lf, crlf) {
+        (0, 0) => LineEnding::None,
+        (_, 0) => LineEnding::Lf,
+        (0, _) => LineEnding::Crlf,
+        _ => LineEnding::Mixed,
+    }
+}
+
+/// The classic `hexdump -C` layout: offset, 16 bytes in hex, then the printable ones
+pub(crate) fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::with_capacity(bytes.len() * 4 + bytes.len() / 16 * 12);
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        let _ = write!(dump, "{:08x} ", line * 16);
+        for (index, byte) in chunk.iter().enumerate() {
+            if index == 8 {
+                dump.push(' ');
+            }
+            let _ = write!(dump, " {:02x}", byte);
+        }
+        for index in chunk.len()..16 {
+            dump.push_str(if index == 8 { "    " } else { "   " });
+        }
+        dump.push_str("  |");
+        dump.extend(chunk.iter().map(|byte| {
+            if byte.is_ascii_graphic() || *byte == b' ' {
+                *byte as char
+            } else {
+                '.'
+            }
+        }));
+        dump.push_str("|\n");
+    }
+    dump
+}
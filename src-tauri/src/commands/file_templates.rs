@@ -0,0 +1,252 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::models::error::{AppError, AppResult};
+use crate::models::file_template::{FileTemplate, TemplateSource};
+use crate::platforms;
+use chrono::{Local, NaiveDateTime};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder under the user's config directory that holds the app's settings
+const APP_CONFIG_DIR: &str = "com.basab.decks";
+
+/// Name, extensions and content of the templates that ship with the app
+const BUILT_IN_TEMPLATES: &[(&str, &[&str], &str)] = &[
+    ("Markdown", &["md", "markdown"], "# {{name}}\n\n"),
+    ("JSON", &["json"], "{}\n"),
+    ("YAML", &["yml", "yaml"], "# {{file_name}}\n"),
+    ("TOML", &["toml"], "# {{file_name}}\n"),
+    (
+        "Python",
+        &["py"],
+        "\"\"\"{{name}}\n\nCreated by {{author}} on {{date}}.\n\"\"\"\n\n",
+    ),
+    (
+        "Shell script",
+        &["sh", "bash"],
+        "#!/usr/bin/env bash\nset -euo pipefail\n\n",
+    ),
+    (
+        "Source file",
+        &[
+            "rs", "js", "jsx", "mjs", "cjs", "ts", "tsx", "c", "h", "cpp", "hpp", "cc", "java",
+            "kt", "go", "cs", "swift", "scala", "dart",
+        ],
+        "// {{file_name}}\n// Created by {{author}} on {{date}}\n\n",
+    ),
+    ("Stylesheet", &["css", "scss", "less"], "/* {{file_name}} */\n"),
+    (
+        "HTML page",
+        &["html", "htm"],
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n  <meta charset=\"utf-8\">\n  <title>{{name}}</title>\n</head>\n<body>\n\n</body>\n</html>\n",
+    ),
+];
+
+/// Lists the templates new files can be created from
+///
+/// User templates are the files in the `templates` folder of the app's config
+/// directory, or of `CKS_TEMPLATES_DIR` when that is set. A template is named after its
+/// file and is for files with the same extension; `default.<ext>` is used for new
+/// `.<ext>` files instead of the built-in template. Templates may use `{{file_name}}`,
+/// `{{name}}`, `{{ext}}`, `{{date}}`, `{{time}}`, `{{year}}` and `{{author}}`.
+///
+/// Returns:
+/// - Ok(Vec<FileTemplate>): The built-in templates followed by the user's
+/// - Err(AppError): The error reading the templates folder, other than it not existing
+#[tauri::command]
+pub async fn list_file_templates() -> AppResult<Vec<FileTemplate>> {
+    let mut templates = built_in_templates();
+    if let Some(dir) = user_templates_dir() {
+        templates.extend(user_templates(&dir)?);
+    }
+    Ok(templates)
+}
+
+/// The folder user templates are loaded from
+pub(crate) fn user_templates_dir() -> Option<PathBuf> {
+    match env::var_os("CKS_TEMPLATES_DIR") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => dirs::config_dir().map(|dir| dir.join(APP_CONFIG_DIR).join("templates")),
+    }
+}
+
+fn built_in_templates() -> Vec<FileTemplate> {
+    BUILT_IN_TEMPLATES
+        .iter()
+        .map(|(name, extensions, _)| FileTemplate {
+            name: name.to_string(),
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+            source: TemplateSource::BuiltIn,
+            path: None,
+        })
+        .collect()
+}
+
+/// The template files in `dir`, by name; a missing folder has none
+fn user_templates(dir: &Path) -> AppResult<Vec<FileTemplate>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(AppError::from_io(e, dir)),
+    };
+
+    let mut templates: Vec<FileTemplate> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') {
+                return None;
+            }
+            Some(FileTemplate {
+                extensions: extension_of(&name).into_iter().collect(),
+                path: Some(platforms::normalize_path(&entry.path().to_string_lossy())),
+                source: TemplateSource::User,
+                name,
+            })
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// The content for a new file named `file_name`, with its variables filled in
+///
+/// A named template is looked up among the user's templates, then the built-in ones.
+/// Without a name, the user's `default.<ext>` is used, then the built-in template for
+/// the extension; files with other extensions start empty.
+pub(crate) fn template_content(
+    template: Option<&str>,
+    file_name: &str,
+    user_dir: Option<&Path>,
+) -> AppResult<String> {
+    let user = match user_dir {
+        Some(dir) => user_templates(dir)?,
+        None => Vec::new(),
+    };
+    let extension = extension_of(file_name);
+
+    let source = match template {
+        Some(name) => {
+            if let Some(found) = user.iter().find(|t| t.name == name) {
+                read_template(found)?
+            } else {
+                BUILT_IN_TEMPLATES
+                    .iter()
+                    .find(|(built_in, _, _)| built_in.eq_ignore_ascii_case(name))
+                    .map(|(_, _, content)| content.to_string())
+                    .ok_or_else(|| {
+                        AppError::invalid_argument(format!("Unknown template '{}'", name))
+                    })?
+            }
+        }
+        None => {
+            let user_default = user.iter().find(|t| {
+                extension.is_some()
+                    && t.extensions.first() == extension.as_ref()
+                    && Path::new(&t.name)
+                        .file_stem()
+                        .is_some_and(|stem| stem.eq_ignore_ascii_case("default"))
+            });
+            match user_default {
+                Some(found) => read_template(found)?,
+                None => extension
+                    .as_deref()
+                    .and_then(|ext| {
+                        BUILT_IN_TEMPLATES
+                            .iter()
+                            .find(|(_, extensions, _)| extensions.contains(&ext))
+                    })
+                    .map(|(_, _, content)| content.to_string())
+                    .unwrap_or_default(),
+            }
+        }
+    };
+
+    Ok(render(
+        &source,
+        file_name,
+        Local::now().naive_local(),
+        &author(),
+    ))
+}
+
+fn read_template(template: &FileTemplate) -> AppResult<String> {
+    let path = template.path.as_deref().unwrap_or_default();
+    fs::read_to_string(path).map_err(|e| AppError::from_io(e, path))
+}
+
+/// Fills in `{{variable}}` placeholders; unknown ones are left as they are, so
+/// templates for other template languages keep working
+pub(crate) fn render(template: &str, file_name: &str, now: NaiveDateTime, author: &str) -> String {
+    let (name, ext) = match file_name.rfind('.') {
+        Some(dot) if dot > 0 => (&file_name[..dot], &file_name[dot..]),
+        _ => (file_name, ""),
+    };
+
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let value = match after[..end].trim() {
+            "file_name" => Some(file_name.to_string()),
+            "name" => Some(name.to_string()),
+            "ext" => Some(ext.to_string()),
+            "date" => Some(now.format("%Y-%m-%d").to_string()),
+            "time" => Some(now.format("%H:%M").to_string()),
+            "year" => Some(now.format("%Y").to_string()),
+            "author" => Some(author.to_string()),
+            _ => None,
+        };
+        match value {
+            Some(value) => rendered.push_str(&value),
+            None => rendered.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// `CKS_AUTHOR`, or the login name
+fn author() -> String {
+    ["CKS_AUTHOR", "USER", "USERNAME"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_default()
+}
+
+fn extension_of(file_name: &str) -> Option<String> {
+    Path::new(file_name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+}
@@ -0,0 +1,277 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::archive_fs;
+use crate::commands::sandbox;
+use crate::models::error::{AppError, AppResult};
+use crate::models::file_content::{ReadFormat, WrittenFile};
+use crate::platforms;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::UNIX_EPOCH;
+
+/// Tells apart temp files written at the same time by this process
+static NEXT_TEMP_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Writes text to a file without ever leaving it half-written
+///
+/// The content goes to a temp file in the same folder, which then replaces the file
+/// in one rename, keeping its permissions. A symlink is followed, so the file it
+/// points to is replaced and the link stays.
+///
+/// Parameters:
+/// - file_path: The file to write; it is created if it doesn't exist
+/// - content: The new content
+/// - etag: The etag `read_file_content` returned. The write is refused if the file
+///   no longer matches it, or was deleted; without one the file is overwritten.
+/// - keep_backup: Keep the previous content as `<name>.bak` next to the file
+/// - format: The `encoding`, `has_bom`, `truncated` and `is_binary` that
+///   `read_file_content` returned, so the text is saved the way it was read. Without
+///   it the file is written as UTF-8 with no byte order mark.
+///
+/// Returns:
+/// - Ok(WrittenFile): The new etag and size, and where the backup went
+/// - Err(AppError): Modified if the file changed since `etag` was read, NotAFile for
+///   a folder, Unsupported for a file inside an archive, InvalidArgument if the
+///   content was only partly read, is a hex dump, or has characters the encoding
+///   can't represent, or the I/O error that stopped the write
+#[tauri::command]
+pub async fn write_file_content(
+    file_path: String,
+    content: String,
+    etag: Option<String>,
+    keep_backup: Option<bool>,
+    format: Option<ReadFormat>,
+) -> AppResult<WrittenFile> {
+    let normalized_path = platforms::normalize_path(&file_path);
+    sandbox::ensure_allowed(Path::new(&normalized_path))?;
+    if !Path::new(&normalized_path).exists()
+        && archive_fs::split_archive_path(&normalized_path).is_some()
+    {
+        return Err(AppError::unsupported(
+            "Files inside archives can't be saved; extract the archive first.",
+        ));
+    }
+    let bytes = encode(&content, &format.unwrap_or_default())?;
+    write_atomic(
+        Path::new(&normalized_path),
+        &bytes,
+        etag.as_deref(),
+        keep_backup.unwrap_or(false),
+    )
+}
+
+/// Turns the text back into the bytes of the encoding it was read from, with the byte
+/// order mark it had
+fn encode(content: &str, format: &ReadFormat) -> AppResult<Vec<u8>> {
+    if format.truncated || format.is_binary {
+        return Err(AppError::invalid_argument(
+            "Only part of the file was read, or it isn't text; saving it would lose data.",
+        ));
+    }
+    let encoding = match &format.encoding {
+        Some(label) => Encoding::for_label(label.as_bytes())
+            .ok_or_else(|| AppError::invalid_argument(format!("Unknown encoding '{}'.", label)))?,
+        None => UTF_8,
+    };
+
+    let mut bytes = Vec::with_capacity(content.len() + 3);
+    // encoding_rs only encodes to UTF-16 by way of UTF-8, so it is done here
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let little_endian = encoding == UTF_16LE;
+        let encode_unit = |unit: u16| {
+            if little_endian {
+                unit.to_le_bytes()
+            } else {
+                unit.to_be_bytes()
+            }
+        };
+        if format.has_bom {
+            bytes.extend_from_slice(&encode_unit(0xFEFF));
+        }
+        for unit in content.encode_utf16() {
+            bytes.extend_from_slice(&encode_unit(unit));
+        }
+        return Ok(bytes);
+    }
+
+    if format.has_bom && encoding == UTF_8 {
+        bytes.extend_from_slice(b"\xEF\xBB\xBF");
+    }
+    let (encoded, _, unmappable) = encoding.encode(content);
+    if unmappable {
+        return Err(AppError::invalid_argument(format!(
+            "The text has characters that {} can't represent.",
+            encoding.name()
+        )));
+    }
+    bytes.extend_from_slice(&encoded);
+    Ok(bytes)
+}
+
+/// The etag of a file: its inode where there is one, modification time and size.
+/// Editors that save by replacing the file change the inode even when the other two
+/// happen to match.
+pub(crate) fn etag(metadata: &fs::Metadata) -> String {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    #[cfg(unix)]
+    let inode = std::os::unix::fs::MetadataExt::ino(metadata);
+    #[cfg(not(unix))]
+    let inode = 0u64;
+    format!("{:x}-{:x}-{:x}", inode, modified, metadata.len())
+}
+
+/// Replaces the file at `path` with `content` through a temp file and a rename
+pub(crate) fn write_atomic(
+    path: &Path,
+    content: &[u8],
+    expected_etag: Option<&str>,
+    keep_backup: bool,
+) -> AppResult<WrittenFile> {
+    // Write through symlinks rather than replacing them
+    let target = match fs::canonicalize(path) {
+        Ok(target) => target,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => path.to_path_buf(),
+        Err(e) => return Err(AppError::from_io(e, path)),
+    };
+    let existing = current_metadata(&target, expected_etag)?;
+
+    let temp = temp_path(&target)?;
+    let result = write_and_replace(
+        &target,
+        &temp,
+        content,
+        existing.as_ref(),
+        expected_etag,
+        keep_backup,
+    );
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// The file's metadata, after checking it still matches `expected_etag`; none when
+/// it doesn't exist and no etag was given
+fn current_metadata(target: &Path, expected_etag: Option<&str>) -> AppResult<Option<fs::Metadata>> {
+    match fs::metadata(target) {
+        Ok(metadata) if !metadata.is_file() => Err(AppError::not_a_file(target)),
+        Ok(metadata) => match expected_etag {
+            Some(expected) if etag(&metadata) != expected => Err(AppError::modified(target)),
+            _ => Ok(Some(metadata)),
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound && expected_etag.is_some() => {
+            Err(AppError::modified(target))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(AppError::from_io(e, target)),
+    }
+}
+
+fn temp_path(target: &Path) -> AppResult<PathBuf> {
+    let name = target.file_name().ok_or_else(|| {
+        AppError::invalid_argument(format!("'{}' is not a file path", target.display()))
+    })?;
+    Ok(target.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        name.to_string_lossy(),
+        process::id(),
+        NEXT_TEMP_ID.fetch_add(1, Ordering::Relaxed)
+    )))
+}
+
+fn write_and_replace(
+    target: &Path,
+    temp: &Path,
+    content: &[u8],
+    existing: Option<&fs::Metadata>,
+    expected_etag: Option<&str>,
+    keep_backup: bool,
+) -> AppResult<WrittenFile> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(temp)
+        .map_err(|e| AppError::from_io(e, target))?;
+    file.write_all(content)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| AppError::from_io(e, target))?;
+    drop(file);
+
+    if let Some(metadata) = existing {
+        fs::set_permissions(temp, metadata.permissions())
+            .map_err(|e| AppError::from_io(e, target))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            // Only root can give a file away; anyone else keeps owning what they write
+            let _ = std::os::unix::fs::chown(temp, Some(metadata.uid()), Some(metadata.gid()));
+        }
+    }
+
+    // Check again just before replacing, to narrow the window for a concurrent edit
+    current_metadata(target, expected_etag)?;
+
+    let backup = match (existing, keep_backup) {
+        (Some(_), true) => {
+            let mut name = target.file_name().unwrap_or_default().to_os_string();
+            name.push(".bak");
+            let backup = target.with_file_name(name);
+            fs::copy(target, &backup).map_err(|e| AppError::from_io(e, &backup))?;
+            Some(platforms::normalize_path(&backup.to_string_lossy()))
+        }
+        _ => None,
+    };
+
+    fs::rename(temp, target).map_err(|e| AppError::from_io(e, target))?;
+    sync_parent(target);
+
+    let metadata = fs::metadata(target).map_err(|e| AppError::from_io(e, target))?;
+    Ok(WrittenFile {
+        etag: etag(&metadata),
+        size: metadata.len(),
+        backup,
+    })
+}
+
+/// Makes the rename itself durable; a failure here doesn't undo the write
+fn sync_parent(target: &Path) {
+    #[cfg(unix)]
+    if let Some(parent) = target.parent() {
+        if let Ok(dir) = fs::File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = target;
+}
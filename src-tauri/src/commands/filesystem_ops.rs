@@ -25,85 +25,111 @@
 use std::fs;
 use std::path::Path;
 use std::io::Write;
+use crate::commands::archive_fs;
+use crate::commands::file_templates;
+use crate::commands::file_read;
+use crate::commands::journal::{self, Step};
+use crate::commands::sandbox;
+use crate::commands::trash_ops;
+use crate::models::journal::OperationKind;
+use crate::models::error::{AppError, AppResult};
+use crate::models::file_content::FileContent;
 use crate::platforms;
 
 /// Creates a new file or folder at the specified path
 ///
+/// A new file starts with a template chosen by its extension (see
+/// `list_file_templates`), or with the named template.
+///
 /// Parameters:
 /// - parent_path: The directory where the item should be created
 /// - item_name: The name of the new file or folder
 /// - item_type: Either "File" or "Directory"
-/// - is_empty: Option to indicate if the file should be empty
+/// - is_empty: Create the file with no content, ignoring any template
+/// - template: The name of the template to use instead of the extension's default
 ///
 /// Returns:
 /// - Ok(()): If the item was created successfully
-/// - Err(String): If the item already exists or there was an error creating it
+/// - Err(AppError): AlreadyExists if the item already exists, InvalidArgument for an
+///   unknown item type or template, or the I/O error that stopped the creation
 #[tauri::command]
 pub async fn create_filesystem_item(
     parent_path: String,
     item_name: String,
     item_type: String,
-    // is_empty: Option<bool>,
-) -> Result<(), String> {
+    is_empty: Option<bool>,
+    template: Option<String>,
+) -> AppResult<()> {
     // Normalize paths for the current platform
     let normalized_parent = platforms::normalize_path(&parent_path);
     let normalized_name = platforms::normalize_path(&item_name);
     let path = Path::new(&normalized_parent).join(&normalized_name);
+    sandbox::ensure_allowed(&path)?;
 
     // Check if the item already exists
     if path.exists() {
-        return Err(format!(
-            "A {} named '{}' already exists.",
-            item_type, normalized_name
-        ));
+        return Err(AppError::already_exists(&path));
     }
 
-    match item_type.as_str() {
-        "Directory" => {
-            fs::create_dir(&path).map_err(|e| format!("Failed to create directory: {}", e))?;
-        }
-        "File" => {
-            let mut file = fs::File::create(&path).map_err(|e| format!("Failed to create file: {}", e))?;
-            // Only write default content if not creating an empty file
-            // if !is_empty.unwrap_or(false) {
-                file.write_all(b"// Add your code here\n")
-                    .map_err(|e| format!("Failed to write initial content: {}", e))?;
-            // }
-            // non empty files may be needed later!
-        }
-        _ => return Err("Invalid item type".to_string()),
-    }
+    let contents = match item_type.as_str() {
+        "Directory" => None,
+        "File" if is_empty.unwrap_or(false) => Some(String::new()),
+        "File" => Some(file_templates::template_content(
+            template.as_deref(),
+            &normalized_name,
+            file_templates::user_templates_dir().as_deref(),
+        )?),
+        _ => return Err(AppError::invalid_argument(format!("Invalid item type '{}'", item_type))),
+    };
+    create_item(&path, contents.as_deref().map(str::as_bytes))?;
+    journal::record(OperationKind::Create, vec![Step::created(&path)]);
 
     Ok(())
 }
 
+/// Creates the file with `contents` at `path`, or a folder when there are none
+pub(crate) fn create_item(path: &Path, contents: Option<&[u8]>) -> AppResult<()> {
+    match contents {
+        None => fs::create_dir(path).map_err(|e| AppError::from_io(e, path)),
+        Some(contents) => {
+            let mut file = fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+                .map_err(|e| AppError::from_io(e, path))?;
+            file.write_all(contents).map_err(|e| AppError::from_io(e, path))
+        }
+    }
+}
+
 /// Deletes a file at the specified path
 ///
 /// Parameters:
 /// - file_path: The path to the file to delete
+/// - permanent: Delete the file outright instead of moving it to the trash. Defaults
+///   to false where the platform has a trash and true elsewhere.
 ///
 /// Returns:
 /// - Ok(()): If the file was deleted successfully
-/// - Err(String): If there was an error deleting the file
+/// - Err(AppError): NotFound or NotAFile if the path isn't an existing file, or the
+///   error that stopped the deletion
 #[tauri::command]
-pub async fn delete_file(file_path: String) -> Result<(), String> {
+pub async fn delete_file(file_path: String, permanent: Option<bool>) -> AppResult<()> {
     // Normalize path for the current platform
     let normalized_path = platforms::normalize_path(&file_path);
     let path = Path::new(&normalized_path);
+    sandbox::ensure_entry_allowed(path)?;
     
     // Check if the path exists and is a file
-    if !path.exists() {
-        return Err(format!("File '{}' does not exist.", normalized_path));
-    }
+    ensure_file(path)?;
     
-    if !path.is_file() {
-        return Err(format!("'{}' is not a file.", normalized_path));
+    if !permanent.unwrap_or(!trash_ops::TRASH_SUPPORTED) {
+        trash_ops::trash_and_record(path)?;
+        return Ok(());
     }
-    
+
     // Attempt to delete the file
-    fs::remove_file(path).map_err(|e| {
-        format!("Failed to delete file '{}': {}", normalized_path, e)
-    })?;
+    fs::remove_file(path).map_err(|e| AppError::from_io(e, path))?;
     
     Ok(())
 }
@@ -112,58 +138,82 @@ pub async fn delete_file(file_path: String) -> Result<(), String> {
 ///
 /// Parameters:
 /// - folder_path: The path to the folder to delete
+/// - permanent: Delete the folder outright instead of moving it to the trash. Defaults
+///   to false where the platform has a trash and true elsewhere.
 ///
 /// Returns:
 /// - Ok(()): If the folder was deleted successfully
-/// - Err(String): If there was an error deleting the folder
+/// - Err(AppError): NotFound or NotADirectory if the path isn't an existing folder, or
+///   the error that stopped the deletion
 #[tauri::command]
-pub async fn delete_folder(folder_path: String) -> Result<(), String> {
+pub async fn delete_folder(folder_path: String, permanent: Option<bool>) -> AppResult<()> {
     // Normalize path for the current platform
     let normalized_path = platforms::normalize_path(&folder_path);
     let path = Path::new(&normalized_path);
+    sandbox::ensure_entry_allowed(path)?;
     
     // Check if the path exists and is a directory
-    if !path.exists() {
-        return Err(format!("Folder '{}' does not exist.", normalized_path));
-    }
+    ensure_directory(path)?;
     
-    if !path.is_dir() {
-        return Err(format!("'{}' is not a folder.", normalized_path));
+    if !permanent.unwrap_or(!trash_ops::TRASH_SUPPORTED) {
+        trash_ops::trash_and_record(path)?;
+        return Ok(());
     }
-    
+
     // Attempt to delete the folder and all its contents
-    fs::remove_dir_all(path).map_err(|e| {
-        format!("Failed to delete folder '{}': {}", normalized_path, e)
-    })?;
+    fs::remove_dir_all(path).map_err(|e| AppError::from_io(e, path))?;
     
     Ok(())
 }
 
 /// Reads the contents of a file, including hidden files
+///
+/// Text is decoded from the encoding it's in, found from its byte order mark or
+/// guessed from its bytes. Files that aren't text come back as a hex dump.
+/// Files inside archives are read without extracting them.
 /// 
 /// Parameters:
-/// - file_path: The path to the file to read
+/// - file_path: The path to the file to read, or to a file inside an archive as
+///   `archive.zip!/inner/path`
 ///
 /// Returns:
-/// - Ok(String): The contents of the file
-/// - Err(String): If there was an error reading the file
+/// - Ok(FileContent): The contents of the file with its encoding and line endings,
+///   and the etag to pass to `write_file_content` when saving it
+/// - Err(AppError): NotFound or NotAFile if the path isn't an existing file, or the
+///   I/O error that stopped the read
 #[tauri::command]
-pub async fn read_file_content(file_path: String) -> Result<String, String> {
+pub async fn read_file_content(file_path: String) -> AppResult<FileContent> {
     // Normalize path for the current platform
     let normalized_path = platforms::normalize_path(&file_path);
     let path = Path::new(&normalized_path);
+    sandbox::ensure_allowed(path)?;
     
-    // Check if the path exists and is a file
     if !path.exists() {
-        return Err(format!("File '{}' does not exist.", normalized_path));
+        if let Some((archive, inner)) = archive_fs::split_archive_path(&normalized_path) {
+            return archive_fs::read_member(&archive, &inner);
+        }
     }
+
+    // Check if the path exists and is a file
+    ensure_file(path)?;
     
-    if !path.is_file() {
-        return Err(format!("'{}' is not a file.", normalized_path));
+    file_read::read_text(path)
+}
+
+/// Fails with NotFound (or the OS error) if `path` can't be reached, NotADirectory if it isn't a folder
+pub(crate) fn ensure_directory(path: &Path) -> AppResult<()> {
+    let metadata = fs::metadata(path).map_err(|e| AppError::from_io(e, path))?;
+    if !metadata.is_dir() {
+        return Err(AppError::not_a_directory(path));
     }
-    
-    // Attempt to read the file
-    fs::read_to_string(path).map_err(|e| {
-        format!("Failed to read file '{}': {}", normalized_path, e)
-    })
+    Ok(())
+}
+
+/// Fails with NotFound (or the OS error) if `path` can't be reached, NotAFile if it isn't a file
+pub(crate) fn ensure_file(path: &Path) -> AppResult<()> {
+    let metadata = fs::metadata(path).map_err(|e| AppError::from_io(e, path))?;
+    if !metadata.is_file() {
+        return Err(AppError::not_a_file(path));
+    }
+    Ok(())
 }
@@ -0,0 +1,288 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::constants::{MAX_GIT_LOCATIONS, MAX_GIT_REPOS};
+use crate::models::pathinfo::GitStatus;
+use git2::{Repository, Status, StatusOptions};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// The status of every changed path in one working tree; anything not listed is clean
+#[derive(Default)]
+struct RepoStatus {
+    files: HashMap<PathBuf, GitStatus>,
+    /// Folders holding changes, with the most pressing status below them
+    folders: HashMap<PathBuf, GitStatus>,
+    /// Ignored folders are reported once rather than file by file
+    ignored_folders: HashSet<PathBuf>,
+}
+
+/// Where a listed folder sits: its canonical path and its working tree, if any
+#[derive(Clone)]
+struct Location {
+    canonical: PathBuf,
+    workdir: Option<PathBuf>,
+    /// The repository's index, which changes with every commit, stage or checkout
+    index: Option<PathBuf>,
+}
+
+/// A working tree's status as read at one state of its index
+struct CachedStatus {
+    index_modified: Option<SystemTime>,
+    /// `None` if the status couldn't be read, so it isn't retried for every entry
+    status: Option<RepoStatus>,
+}
+
+// Folder -> working tree lookups, least recently used first, so each listed folder
+// only runs discovery once
+static LOCATIONS: Lazy<Mutex<Vec<(PathBuf, Location)>>> = Lazy::new(|| Mutex::new(Vec::new()));
+// Working tree root -> its current status, computed on first use, least recently
+// used first
+static REPO_STATUS: Lazy<Mutex<Vec<(PathBuf, CachedStatus)>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Returns the git status of `path`, or `None` if it isn't inside a working tree.
+/// The repository's status is read once and reused until its index changes or the
+/// watcher reports a change.
+pub fn status_of(path: &Path) -> Option<GitStatus> {
+    let parent = path.parent()?;
+    let name = path.file_name()?;
+    let location = locate(parent)?;
+    let workdir = location.workdir?;
+    let relative = location.canonical.join(name);
+    let relative = relative.strip_prefix(&workdir).ok()?;
+    // The repository's own storage isn't part of the working tree
+    if relative.starts_with(".git") {
+        return None;
+    }
+
+    let index_modified = location
+        .index
+        .and_then(|index| fs::metadata(index).ok())
+        .and_then(|metadata| metadata.modified().ok());
+    let mut repos = REPO_STATUS.lock().ok()?;
+    let position = repos.iter().position(|(cached, _)| *cached == workdir);
+    let entry = match position {
+        Some(position) if repos[position].1.index_modified == index_modified => {
+            repos.remove(position)
+        }
+        _ => {
+            if let Some(position) = position {
+                repos.remove(position);
+            }
+            let status = read_repo_status(&workdir);
+            (
+                workdir.clone(),
+                CachedStatus {
+                    index_modified,
+                    status,
+                },
+            )
+        }
+    };
+    repos.push(entry);
+    if repos.len() > MAX_GIT_REPOS {
+        repos.remove(0);
+    }
+    let repo = repos.last()?.1.status.as_ref()?;
+    Some(repo.status_of(relative))
+}
+
+/// Forgets the status of every working tree containing one of the changed paths.
+/// Returns the roots of those working trees so the caller can announce the refresh.
+pub fn invalidate_git_status(changed_paths: &[PathBuf]) -> Vec<PathBuf> {
+    // Paths are resolved before any lock is taken; a deleted one only has its own
+    let changed: Vec<PathBuf> = changed_paths
+        .iter()
+        .filter(|path| affects_status(path))
+        .flat_map(|path| [Some(path.clone()), path.canonicalize().ok()])
+        .flatten()
+        .collect();
+    if changed.is_empty() {
+        return Vec::new();
+    }
+    let contains_change = |dir: &Path| changed.iter().any(|path| path.starts_with(dir));
+
+    if let Ok(mut locations) = LOCATIONS.lock() {
+        locations.retain(|(_, location)| match &location.workdir {
+            Some(workdir) => !contains_change(workdir),
+            // The folder may have just become part of a repository
+            None => !changed.iter().any(|path| {
+                repository_root(path).is_some_and(|root| location.canonical.starts_with(root))
+            }),
+        });
+    }
+    let Ok(mut repos) = REPO_STATUS.lock() else {
+        return Vec::new();
+    };
+    let mut stale = Vec::new();
+    repos.retain(|(workdir, _)| {
+        let changed = contains_change(workdir);
+        if changed {
+            stale.push(workdir.clone());
+        }
+        !changed
+    });
+    stale
+}
+
+/// Whether a change at `path` can change a status. Inside the repository's storage
+/// only the index and HEAD count: reading a status can rewrite the rest, and each
+/// refresh would otherwise set off the next.
+fn affects_status(path: &Path) -> bool {
+    match git_dir_of(path) {
+        Some(git_dir) => {
+            path == git_dir || path == git_dir.join("index") || path == git_dir.join("HEAD")
+        }
+        None => true,
+    }
+}
+
+/// The working tree whose `.git` folder holds `path`, if it is in one
+fn repository_root(path: &Path) -> Option<&Path> {
+    git_dir_of(path)?.parent()
+}
+
+fn git_dir_of(path: &Path) -> Option<&Path> {
+    path.ancestors()
+        .find(|ancestor| ancestor.file_name().is_some_and(|name| name == ".git"))
+}
+
+fn locate(folder: &Path) -> Option<Location> {
+    let mut locations = LOCATIONS.lock().ok()?;
+    if let Some(position) = locations.iter().position(|(cached, _)| cached == folder) {
+        let entry = locations.remove(position);
+        let location = entry.1.clone();
+        locations.push(entry);
+        return Some(location);
+    }
+    let canonical = folder.canonicalize().ok()?;
+    let repo = Repository::discover(&canonical).ok();
+    let workdir = repo
+        .as_ref()
+        .and_then(|repo| repo.workdir().and_then(|dir| dir.canonicalize().ok()));
+    let index = repo.as_ref().map(|repo| repo.path().join("index"));
+    let location = Location {
+        canonical,
+        workdir,
+        index,
+    };
+    locations.push((folder.to_path_buf(), location.clone()));
+    if locations.len() > MAX_GIT_LOCATIONS {
+        locations.remove(0);
+    }
+    Some(location)
+}
+
+fn read_repo_status(workdir: &Path) -> Option<RepoStatus> {
+    let repo = Repository::discover(workdir).ok()?;
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(true)
+        .recurse_ignored_dirs(false)
+        .exclude_submodules(true);
+    let statuses = repo.statuses(Some(&mut options)).ok()?;
+
+    let mut repo_status = RepoStatus::default();
+    for entry in statuses.iter() {
+        let Some(path) = entry.path() else {
+            continue;
+        };
+        let status = classify(entry.status());
+        // Folders come back with a trailing slash
+        let is_folder = path.ends_with('/');
+        let path = PathBuf::from(path.trim_end_matches('/'));
+
+        if status == GitStatus::Ignored {
+            if is_folder {
+                repo_status.ignored_folders.insert(path);
+            } else {
+                repo_status.files.insert(path, status);
+            }
+            continue;
+        }
+        for ancestor in path.ancestors().skip(1) {
+            if ancestor.as_os_str().is_empty() {
+                break;
+            }
+            let rollup = repo_status
+                .folders
+                .entry(ancestor.to_path_buf())
+                .or_insert(status);
+            *rollup = (*rollup).max(status);
+        }
+        repo_status.files.insert(path, status);
+    }
+    Some(repo_status)
+}
+
+impl RepoStatus {
+    fn status_of(&self, relative: &Path) -> GitStatus {
+        if let Some(status) = self.files.get(relative) {
+            return *status;
+        }
+        if let Some(status) = self.folders.get(relative) {
+            return *status;
+        }
+        if relative
+            .ancestors()
+            .any(|ancestor| self.ignored_folders.contains(ancestor))
+        {
+            return GitStatus::Ignored;
+        }
+        GitStatus::Clean
+    }
+}
+
+/// Reduces libgit2's flags to the single status shown on a node
+fn classify(status: Status) -> GitStatus {
+    let worktree_changes =
+        Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_TYPECHANGE | Status::WT_RENAMED;
+    let index_changes = Status::INDEX_NEW
+        | Status::INDEX_MODIFIED
+        | Status::INDEX_DELETED
+        | Status::INDEX_RENAMED
+        | Status::INDEX_TYPECHANGE;
+
+    if status.is_conflicted() {
+        GitStatus::Conflicted
+    } else if status.is_ignored() {
+        GitStatus::Ignored
+    } else if status.intersects(worktree_changes) {
+        GitStatus::Modified
+    } else if status.intersects(index_changes) {
+        GitStatus::Staged
+    } else if status.is_wt_new() {
+        GitStatus::Untracked
+    } else {
+        GitStatus::Clean
+    }
+}
@@ -0,0 +1,768 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::constants::{
+    JOB_CONCURRENCY_PER_DEVICE, JOB_PROGRESS_INTERVAL, MAX_FINISHED_JOBS,
+};
+use crate::commands::copy_ops::{self, Copier};
+use crate::commands::filesystem_ops::ensure_directory;
+use crate::commands::journal::{self, Step};
+use crate::commands::move_ops;
+use crate::commands::sandbox;
+use crate::commands::trash_ops;
+use crate::models::error::{AppError, AppResult};
+use crate::models::job::{JobInfo, JobKind, JobRequest, JobState};
+use crate::models::journal::OperationKind;
+use crate::models::transfer::{CopyConflict, CopyOptions, MoveOutcome};
+use crate::platforms;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tauri::Window;
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+use walkdir::WalkDir;
+
+static JOBS: Lazy<Arc<JobManager>> =
+    Lazy::new(|| Arc::new(JobManager::new(JOB_CONCURRENCY_PER_DEVICE)));
+
+/// Starts a copy, move or delete in the background
+///
+/// The job waits while an earlier unfinished job touches any of the same paths, and
+/// while its devices already run as many jobs as allowed. Every change of state and,
+/// at most every 200ms, of progress is emitted as `job-progress`. Copy conflicts are
+/// asked with `copy-conflict` and answered with `resolve_copy_conflict`, using the
+/// job id.
+///
+/// Parameters:
+/// - request: What to do; see `JobRequest`
+///
+/// Returns:
+/// - Ok(String): The job's id
+/// - Err(AppError): InvalidArgument if the request has no items
+#[tauri::command]
+pub async fn start_job(window: Window, request: JobRequest) -> AppResult<String> {
+    JOBS.submit(request, Arc::new(window))
+}
+
+/// Lists the queued, running and recently finished jobs, oldest first
+#[tauri::command]
+pub async fn list_jobs() -> AppResult<Vec<JobInfo>> {
+    JOBS.list()
+}
+
+/// Pauses a job. A running job stops at its next item or, for copies, within a
+/// progress interval; a queued job won't start until resumed.
+///
+/// Parameters:
+/// - job_id: The job to pause
+///
+/// Returns:
+/// - Ok(()): If the job was paused
+/// - Err(AppError): InvalidArgument if the job is unknown or already finished
+#[tauri::command]
+pub async fn pause_job(job_id: String) -> AppResult<()> {
+    JOBS.pause(&job_id)
+}
+
+/// Lets a paused job carry on
+///
+/// Parameters:
+/// - job_id: The job to resume
+///
+/// Returns:
+/// - Ok(()): If the job was resumed
+/// - Err(AppError): InvalidArgument if the job is unknown or already finished
+#[tauri::command]
+pub async fn resume_job(job_id: String) -> AppResult<()> {
+    JOBS.resume(&job_id)
+}
+
+/// Stops a job. Items already done stay done; a file being copied is discarded.
+///
+/// Parameters:
+/// - job_id: The job to cancel
+///
+/// Returns:
+/// - Ok(()): If the job was told to stop; it reports `Cancelled` once it has
+/// - Err(AppError): InvalidArgument if the job is unknown or already finished
+#[tauri::command]
+pub async fn cancel_job(job_id: String) -> AppResult<()> {
+    JOBS.cancel(&job_id)
+}
+
+/// Where a job's events go: the window in the app, a collector in tests
+pub(crate) trait JobEvents: Send + Sync {
+    fn progress(&self, job: &JobInfo);
+    fn conflict(&self, conflict: &CopyConflict);
+}
+
+impl JobEvents for Window {
+    fn progress(&self, job: &JobInfo) {
+        let _ = self.emit("job-progress", job);
+    }
+
+    fn conflict(&self, conflict: &CopyConflict) {
+        let _ = self.emit("copy-conflict", conflict);
+    }
+}
+
+/// Pause and cancel requests for one job
+#[derive(Default)]
+struct JobControl {
+    cancelled: AtomicBool,
+    paused: Mutex<bool>,
+    resumed: Condvar,
+    /// Wakes a job that is still waiting for its turn, a device slot or a resume
+    notify: Notify,
+    /// The device slots of a started job, given back while it is paused
+    slots: Mutex<DeviceSlots>,
+}
+
+#[derive(Default)]
+struct DeviceSlots {
+    semaphores: Vec<Arc<Semaphore>>,
+    permits: Vec<OwnedSemaphorePermit>,
+}
+
+impl JobControl {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        // Taking the lock first makes sure a job about to wait sees the flag or the wakeup
+        let _paused = self.paused.lock();
+        self.resumed.notify_all();
+        self.notify.notify_waiters();
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.lock().map(|paused| *paused).unwrap_or(false)
+    }
+
+    fn set_paused(&self, paused: bool) {
+        if let Ok(mut current) = self.paused.lock() {
+            *current = paused;
+        }
+        self.resumed.notify_all();
+        self.notify.notify_waiters();
+    }
+
+    /// Blocks while the job is paused, and fails with Cancelled once it is cancelled.
+    /// A paused job lets other jobs have its device slots and takes them back on resume.
+    fn checkpoint(&self) -> AppResult<()> {
+        let mut paused = self.paused.lock().map_err(AppError::lock_poisoned)?;
+        if *paused && !self.is_cancelled() {
+            let semaphores = {
+                let mut slots = self.slots.lock().map_err(AppError::lock_poisoned)?;
+                slots.permits.clear();
+                slots.semaphores.clone()
+            };
+            while *paused && !self.is_cancelled() {
+                paused = self.resumed.wait(paused).map_err(AppError::lock_poisoned)?;
+            }
+            drop(paused);
+            if !self.is_cancelled() {
+                // Only ever called on the job's blocking thread, inside the runtime
+                tokio::runtime::Handle::current().block_on(self.take_slots(semaphores))?;
+            }
+        }
+        if self.is_cancelled() {
+            return Err(AppError::Cancelled { path: None });
+        }
+        Ok(())
+    }
+
+    async fn cancelled(&self) {
+        loop {
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Waits until the job isn't paused, failing with Cancelled once it is cancelled
+    async fn unpaused(&self) -> AppResult<()> {
+        loop {
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return Err(AppError::Cancelled { path: None });
+            }
+            if !self.is_paused() {
+                return Ok(());
+            }
+            notified.await;
+        }
+    }
+
+    /// Takes a slot on each of `semaphores` once the job isn't paused, and holds them
+    /// until it is paused again or `release_slots` is called
+    async fn take_slots(&self, semaphores: Vec<Arc<Semaphore>>) -> AppResult<()> {
+        loop {
+            self.unpaused().await?;
+            let mut permits = Vec::with_capacity(semaphores.len());
+            for semaphore in &semaphores {
+                tokio::select! {
+                    permit = Arc::clone(semaphore).acquire_owned() => permits.push(
+                        permit.map_err(|_| AppError::internal("The job queue has shut down."))?,
+                    ),
+                    _ = self.cancelled() => return Err(AppError::Cancelled { path: None }),
+                }
+            }
+            // Paused while waiting for a slot: the slots go back until it is resumed
+            let mut slots = self.slots.lock().map_err(AppError::lock_poisoned)?;
+            if !self.is_paused() {
+                *slots = DeviceSlots {
+                    semaphores,
+                    permits,
+                };
+                return Ok(());
+            }
+        }
+    }
+
+    fn release_slots(&self) {
+        if let Ok(mut slots) = self.slots.lock() {
+            *slots = DeviceSlots::default();
+        }
+    }
+}
+
+struct Job {
+    info: JobInfo,
+    /// Everything the job reads or writes, for spotting overlapping jobs
+    paths: Vec<PathBuf>,
+    control: Arc<JobControl>,
+    events: Arc<dyn JobEvents>,
+    last_report: Instant,
+}
+
+/// Runs jobs in the background, one at a time per set of overlapping paths and at most
+/// `per_device` at a time per device
+pub(crate) struct JobManager {
+    /// In submission order, which is also the order overlapping jobs run in
+    jobs: Mutex<Vec<Job>>,
+    next_id: Mutex<u64>,
+    /// Woken whenever a job finishes, so queued jobs recheck their turn
+    finished: Notify,
+    devices: Mutex<HashMap<Option<u64>, Arc<Semaphore>>>,
+    per_device: usize,
+}
+
+impl JobManager {
+    pub fn new(per_device: usize) -> Self {
+        JobManager {
+            jobs: Mutex::new(Vec::new()),
+            next_id: Mutex::new(0),
+            finished: Notify::new(),
+            devices: Mutex::new(HashMap::new()),
+            per_device,
+        }
+    }
+
+    /// Queues `request` and returns its job id. Must be called inside a Tokio runtime.
+    pub fn submit(
+        self: &Arc<Self>,
+        request: JobRequest,
+        events: Arc<dyn JobEvents>,
+    ) -> AppResult<String> {
+        let (kind, sources, destination) = match &request {
+            JobRequest::Copy {
+                sources,
+                destination,
+                ..
+            } => (JobKind::Copy, sources, Some(destination)),
+            JobRequest::Move {
+                sources,
+                destination,
+                ..
+            } => (JobKind::Move, sources, Some(destination)),
+            JobRequest::Delete { paths, .. } => (JobKind::Delete, paths, None),
+        };
+        if sources.is_empty() {
+            return Err(AppError::invalid_argument("The job has no items."));
+        }
+
+        let sources: Vec<String> = sources
+            .iter()
+            .map(|source| platforms::normalize_path(source))
+            .collect();
+        let destination = destination.map(|destination| platforms::normalize_path(destination));
+        for source in &sources {
+            sandbox::ensure_entry_allowed(Path::new(source))?;
+        }
+        if let Some(destination) = &destination {
+            sandbox::ensure_allowed(Path::new(destination))?;
+        }
+        let paths: Vec<PathBuf> = sources
+            .iter()
+            .chain(destination.iter())
+            .map(|path| fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path)))
+            .collect();
+        let mut devices: Vec<Option<u64>> = paths
+            .iter()
+            .map(|path| platforms::device_id(path))
+            .collect();
+        // Slots are always taken in the same order, so two jobs can't each hold one the
+        // other needs
+        devices.sort();
+        devices.dedup();
+
+        let job_id = {
+            let mut next_id = self.next_id.lock().map_err(AppError::lock_poisoned)?;
+            *next_id += 1;
+            format!("job-{}", next_id)
+        };
+        let info = JobInfo {
+            job_id: job_id.clone(),
+            kind,
+            state: JobState::Queued,
+            sources,
+            destination,
+            items_done: 0,
+            items_total: 0,
+            bytes_done: 0,
+            bytes_total: 0,
+            current_path: None,
+            skipped: Vec::new(),
+            errors: Vec::new(),
+            created_at: now_millis(),
+            started_at: None,
+            finished_at: None,
+        };
+        events.progress(&info);
+
+        let control = Arc::new(JobControl::default());
+        self.jobs
+            .lock()
+            .map_err(AppError::lock_poisoned)?
+            .push(Job {
+                info,
+                paths,
+                control: Arc::clone(&control),
+                events,
+                last_report: Instant::now(),
+            });
+
+        let manager = Arc::clone(self);
+        let id = job_id.clone();
+        tokio::spawn(async move {
+            let result = manager.start(&id, request, &control, &devices).await;
+            manager.finish(&id, result);
+        });
+        Ok(job_id)
+    }
+
+    pub fn list(&self) -> AppResult<Vec<JobInfo>> {
+        let jobs = self.jobs.lock().map_err(AppError::lock_poisoned)?;
+        Ok(jobs.iter().map(|job| job.info.clone()).collect())
+    }
+
+    pub fn pause(&self, job_id: &str) -> AppResult<()> {
+        self.control_of(job_id)?.set_paused(true);
+        self.update(job_id, true, |info| info.state = JobState::Paused);
+        Ok(())
+    }
+
+    pub fn resume(&self, job_id: &str) -> AppResult<()> {
+        self.control_of(job_id)?.set_paused(false);
+        self.update(job_id, true, |info| {
+            info.state = match info.started_at {
+                Some(_) => JobState::Running,
+                None => JobState::Queued,
+            };
+        });
+        Ok(())
+    }
+
+    pub fn cancel(&self, job_id: &str) -> AppResult<()> {
+        self.control_of(job_id)?.cancel();
+        copy_ops::stop_copy(job_id)
+    }
+
+    /// The control of an unfinished job
+    fn control_of(&self, job_id: &str) -> AppResult<Arc<JobControl>> {
+        let jobs = self.jobs.lock().map_err(AppError::lock_poisoned)?;
+        let job = jobs
+            .iter()
+            .find(|job| job.info.job_id == job_id)
+            .ok_or_else(|| AppError::invalid_argument(format!("There is no job '{}'.", job_id)))?;
+        if job.info.state.is_finished() {
+            return Err(AppError::invalid_argument(format!(
+                "Job '{}' has already finished.",
+                job_id
+            )));
+        }
+        Ok(Arc::clone(&job.control))
+    }
+
+    /// Applies `change` to an unfinished job and reports it, at most once per
+    /// progress interval unless `force` is set
+    fn update(&self, job_id: &str, force: bool, change: impl FnOnce(&mut JobInfo)) {
+        let report = {
+            let Ok(mut jobs) = self.jobs.lock() else {
+                return;
+            };
+            let Some(job) = jobs.iter_mut().find(|job| job.info.job_id == job_id) else {
+                return;
+            };
+            if job.info.state.is_finished() {
+                return;
+            }
+            change(&mut job.info);
+            let due = force || job.last_report.elapsed() >= JOB_PROGRESS_INTERVAL;
+            if due {
+                job.last_report = Instant::now();
+            }
+            due.then(|| (Arc::clone(&job.events), job.info.clone()))
+        };
+        // Emitted outside the lock
+        if let Some((events, info)) = report {
+            events.progress(&info);
+        }
+    }
+
+    /// Waits for the job's turn, a resume if it is paused and its device slots, then runs
+    /// it on a blocking thread
+    async fn start(
+        self: &Arc<Self>,
+        job_id: &str,
+        request: JobRequest,
+        control: &Arc<JobControl>,
+        devices: &[Option<u64>],
+    ) -> AppResult<()> {
+        self.wait_for_turn(job_id, control).await?;
+        control.take_slots(self.device_slots(devices)?).await?;
+        self.update(job_id, true, |info| {
+            info.started_at = Some(now_millis());
+            if info.state == JobState::Queued {
+                info.state = JobState::Running;
+            }
+        });
+
+        let manager = Arc::clone(self);
+        let id = job_id.to_string();
+        let control = Arc::clone(control);
+        let result = tokio::task::spawn_blocking(move || {
+            let result = control
+                .checkpoint()
+                .and_then(|()| manager.execute(&id, request, &control));
+            control.release_slots();
+            result
+        })
+        .await;
+        result.map_err(|e| {
+            AppError::internal(format!("Job '{}' stopped unexpectedly: {}", job_id, e))
+        })?
+    }
+
+    fn finish(&self, job_id: &str, result: AppResult<()>) {
+        self.update(job_id, true, |info| {
+            info.state = match result {
+                Ok(()) => JobState::Completed,
+                Err(AppError::Cancelled { .. }) => JobState::Cancelled,
+                Err(err) => {
+                    info.errors.push(err);
+                    JobState::Failed
+                }
+            };
+            info.current_path = None;
+            info.finished_at = Some(now_millis());
+        });
+
+        if let Ok(mut jobs) = self.jobs.lock() {
+            let finished = jobs
+                .iter()
+                .filter(|job| job.info.state.is_finished())
+                .count();
+            let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+            jobs.retain(|job| {
+                let forget = excess > 0 && job.info.state.is_finished();
+                if forget {
+                    excess -= 1;
+                }
+                !forget
+            });
+        }
+        self.finished.notify_waiters();
+    }
+
+    /// Waits until no earlier unfinished job touches any of this job's paths
+    async fn wait_for_turn(&self, job_id: &str, control: &JobControl) -> AppResult<()> {
+        loop {
+            // Registered before the check, so a job finishing in between still wakes us
+            let finished = self.finished.notified();
+            if control.is_cancelled() {
+                return Err(AppError::Cancelled { path: None });
+            }
+            if !self.is_blocked(job_id)? {
+                return Ok(());
+            }
+            tokio::select! {
+                _ = finished => {}
+                _ = control.cancelled() => {}
+            }
+        }
+    }
+
+    fn is_blocked(&self, job_id: &str) -> AppResult<bool> {
+        let jobs = self.jobs.lock().map_err(AppError::lock_poisoned)?;
+        let Some(index) = jobs.iter().position(|job| job.info.job_id == job_id) else {
+            return Ok(false);
+        };
+        let paths = &jobs[index].paths;
+        Ok(jobs[..index].iter().any(|earlier| {
+            !earlier.info.state.is_finished() && paths_overlap(&earlier.paths, paths)
+        }))
+    }
+
+    /// The semaphore of each device, in the order given
+    fn device_slots(&self, devices: &[Option<u64>]) -> AppResult<Vec<Arc<Semaphore>>> {
+        let mut slots = self.devices.lock().map_err(AppError::lock_poisoned)?;
+        Ok(devices
+            .iter()
+            .map(|device| {
+                let slot = slots
+                    .entry(*device)
+                    .or_insert_with(|| Arc::new(Semaphore::new(self.per_device)));
+                Arc::clone(slot)
+            })
+            .collect())
+    }
+
+    fn execute(&self, job_id: &str, request: JobRequest, control: &JobControl) -> AppResult<()> {
+        let to_paths = |paths: &[String]| -> Vec<PathBuf> {
+            paths
+                .iter()
+                .map(|path| PathBuf::from(platforms::normalize_path(path)))
+                .collect()
+        };
+        match request {
+            JobRequest::Copy {
+                sources,
+                destination,
+                options,
+            } => {
+                let destination = PathBuf::from(platforms::normalize_path(&destination));
+                self.run_copy(job_id, &to_paths(&sources), &destination, options, control)
+            }
+            JobRequest::Move {
+                sources,
+                destination,
+                conflict,
+            } => {
+                let destination = PathBuf::from(platforms::normalize_path(&destination));
+                ensure_directory(&destination)?;
+                self.update(job_id, true, |info| info.items_total = sources.len() as u64);
+
+                let mut steps = Vec::new();
+                let result = to_paths(&sources).iter().try_for_each(|source| {
+                    control.checkpoint()?;
+                    self.update(job_id, false, |info| {
+                        info.current_path = Some(display(source))
+                    });
+                    let moved =
+                        move_ops::move_into(source, &destination, conflict.unwrap_or_default());
+                    steps.extend(move_ops::journal_step(&moved));
+                    self.update(job_id, false, |info| {
+                        info.items_done += 1;
+                        match moved.outcome {
+                            MoveOutcome::Moved | MoveOutcome::Replaced => {}
+                            MoveOutcome::Skipped => info.skipped.push(moved.source),
+                            MoveOutcome::Failed => info.errors.extend(moved.error),
+                        }
+                    });
+                    Ok(())
+                });
+                journal::record(OperationKind::Move, steps);
+                result
+            }
+            JobRequest::Delete { paths, permanent } => {
+                let paths = to_paths(&paths);
+                if permanent.unwrap_or(!trash_ops::TRASH_SUPPORTED) {
+                    self.run_delete(job_id, &paths, control)
+                } else {
+                    self.run_trash(job_id, &paths, control)
+                }
+            }
+        }
+    }
+
+    fn run_copy(
+        &self,
+        job_id: &str,
+        sources: &[PathBuf],
+        destination: &Path,
+        options: CopyOptions,
+        control: &JobControl,
+    ) -> AppResult<()> {
+        ensure_directory(destination)?;
+        let events = {
+            let jobs = self.jobs.lock().map_err(AppError::lock_poisoned)?;
+            let job = jobs.iter().find(|job| job.info.job_id == job_id);
+            job.map(|job| Arc::clone(&job.events))
+                .ok_or_else(|| AppError::internal(format!("Job '{}' is gone.", job_id)))?
+        };
+        let (mut cancel_rx, answer_rx) = copy_ops::register_copy(job_id)?;
+        // A cancel from before the copy was registered didn't reach its channel
+        if control.is_cancelled() {
+            copy_ops::unregister_copy(job_id);
+            return Err(AppError::Cancelled { path: None });
+        }
+
+        let copier = Copier::new(
+            job_id,
+            options,
+            &mut cancel_rx,
+            |progress| {
+                self.update(job_id, false, |info| {
+                    info.items_done = progress.files_copied;
+                    info.items_total = progress.total_files;
+                    info.bytes_done = progress.bytes_copied;
+                    info.bytes_total = progress.total_bytes;
+                    info.current_path = progress.current_path;
+                });
+                // A paused copy waits here; a cancel reaches it through its channel
+                let _ = control.checkpoint();
+            },
+            |conflict| {
+                events.conflict(conflict);
+                answer_rx.recv().ok()
+            },
+        );
+        let summary = copier.run(sources, destination);
+        copy_ops::unregister_copy(job_id);
+        copy_ops::record_copies(&summary);
+
+        let cancelled = summary.cancelled;
+        self.update(job_id, true, |info| {
+            info.items_done = summary.files_copied;
+            info.bytes_done = summary.bytes_copied;
+            info.skipped = summary.skipped;
+            info.errors = summary.errors;
+        });
+        if cancelled {
+            return Err(AppError::Cancelled { path: None });
+        }
+        Ok(())
+    }
+
+    /// Deletes entry by entry, so big trees report progress and can be paused
+    fn run_delete(&self, job_id: &str, paths: &[PathBuf], control: &JobControl) -> AppResult<()> {
+        let total: usize = paths
+            .iter()
+            .map(|path| {
+                WalkDir::new(path)
+                    .follow_root_links(false)
+                    .into_iter()
+                    .count()
+            })
+            .sum();
+        self.update(job_id, true, |info| info.items_total = total as u64);
+
+        for path in paths {
+            match self.remove_tree(job_id, path, control) {
+                Ok(()) => {}
+                Err(err @ AppError::Cancelled { .. }) => return Err(err),
+                // The rest of this item is left alone, but the other items go ahead
+                Err(err) => self.update(job_id, false, |info| info.errors.push(err)),
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_tree(&self, job_id: &str, path: &Path, control: &JobControl) -> AppResult<()> {
+        fs::symlink_metadata(path).map_err(|e| AppError::from_io(e, path))?;
+        // A symlinked folder is removed as the link; what it points to stays
+        let walk = WalkDir::new(path)
+            .follow_root_links(false)
+            .contents_first(true);
+        for entry in walk {
+            control.checkpoint()?;
+            let entry = entry.map_err(|e| AppError::from_io(e.into(), path))?;
+            let removed = if entry.file_type().is_dir() {
+                fs::remove_dir(entry.path())
+            } else {
+                fs::remove_file(entry.path())
+            };
+            removed.map_err(|e| AppError::from_io(e, entry.path()))?;
+            self.update(job_id, false, |info| {
+                info.items_done += 1;
+                info.current_path = Some(display(entry.path()));
+            });
+        }
+        Ok(())
+    }
+
+    fn run_trash(&self, job_id: &str, paths: &[PathBuf], control: &JobControl) -> AppResult<()> {
+        self.update(job_id, true, |info| info.items_total = paths.len() as u64);
+
+        let mut steps = Vec::new();
+        let result = paths.iter().try_for_each(|path| {
+            control.checkpoint()?;
+            self.update(job_id, false, |info| {
+                info.current_path = Some(display(path))
+            });
+            let trashed = trash_ops::trash_path(path);
+            if let Ok(item) = &trashed {
+                steps.push(Step::trashed(
+                    Path::new(&item.original_path),
+                    Path::new(&item.id),
+                ));
+            }
+            self.update(job_id, false, |info| {
+                info.items_done += 1;
+                info.errors.extend(trashed.err());
+            });
+            Ok(())
+        });
+        journal::record(OperationKind::Trash, steps);
+        result
+    }
+}
+
+/// Whether any path of `a` is, contains or is inside any path of `b`
+pub(crate) fn paths_overlap(a: &[PathBuf], b: &[PathBuf]) -> bool {
+    a.iter()
+        .any(|a| b.iter().any(|b| a.starts_with(b) || b.starts_with(a)))
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+fn display(path: &Path) -> String {
+    platforms::normalize_path(&path.to_string_lossy())
+}
@@ -0,0 +1,580 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::bulk_rename::rename_all;
+use crate::commands::constants::JOURNAL_LIMIT;
+use crate::commands::filesystem_ops::create_item;
+use crate::commands::move_ops::{copy_tree, move_path, remove_path};
+use crate::commands::trash_ops::{restore_path, trash_path};
+use crate::models::error::{AppError, AppResult};
+use crate::models::journal::{JournalState, OperationKind, OperationSummary};
+use crate::models::transfer::ConflictPolicy;
+use crate::platforms;
+use once_cell::sync::Lazy;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+// The operations of this session, for undo and redo
+static JOURNAL: Lazy<Mutex<Journal>> = Lazy::new(|| Mutex::new(Journal::default()));
+
+/// Reverses the most recent file operation
+///
+/// Nothing is touched unless every item is still as the operation left it; an item
+/// that was edited, replaced or removed since makes the undo fail with `Changed`.
+///
+/// Returns:
+/// - Ok(OperationSummary): The operation that was undone
+/// - Err(AppError): InvalidArgument if there is nothing to undo, Changed if an item was
+///   modified since, or the error that stopped the undo
+#[tauri::command]
+pub async fn undo_operation() -> AppResult<OperationSummary> {
+    undo(&JOURNAL)
+}
+
+/// Repeats the most recently undone file operation, with the same checks as undo
+///
+/// Returns:
+/// - Ok(OperationSummary): The operation that was redone
+/// - Err(AppError): InvalidArgument if there is nothing to redo, Changed if an item was
+///   modified since the undo, or the error that stopped the redo
+#[tauri::command]
+pub async fn redo_operation() -> AppResult<OperationSummary> {
+    redo(&JOURNAL)
+}
+
+/// Reports what undo and redo would act on, e.g. to label menu items
+#[tauri::command]
+pub async fn get_journal_state() -> AppResult<JournalState> {
+    Ok(JOURNAL.lock().map_err(AppError::lock_poisoned)?.state())
+}
+
+/// Undoes the most recent operation in `journal`. The journal is only locked to take
+/// the operation out and to put it back, so other commands can record meanwhile.
+pub(crate) fn undo(journal: &Mutex<Journal>) -> AppResult<OperationSummary> {
+    let (mut operation, next_id) = journal
+        .lock()
+        .map_err(AppError::lock_poisoned)?
+        .take(Stack::Undo)?;
+    let result = operation.undo();
+    journal.lock().map_err(AppError::lock_poisoned)?.put_back(
+        Stack::Undo,
+        operation,
+        next_id,
+        result,
+    )
+}
+
+/// Redoes the most recently undone operation in `journal`, locking it the way `undo`
+/// does
+pub(crate) fn redo(journal: &Mutex<Journal>) -> AppResult<OperationSummary> {
+    let (mut operation, next_id) = journal
+        .lock()
+        .map_err(AppError::lock_poisoned)?
+        .take(Stack::Redo)?;
+    let result = operation.redo();
+    journal.lock().map_err(AppError::lock_poisoned)?.put_back(
+        Stack::Redo,
+        operation,
+        next_id,
+        result,
+    )
+}
+
+/// Adds a completed operation to the journal. Operations without steps are ignored.
+pub(crate) fn record(kind: OperationKind, steps: Vec<Step>) {
+    if let Ok(mut journal) = JOURNAL.lock() {
+        journal.record(kind, steps);
+    }
+}
+
+/// The size and modification time of an item right after the journal last acted on
+/// it. Folders that undo would delete are summed over their whole tree.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Fingerprint {
+    is_dir: bool,
+    entries: u64,
+    bytes: u64,
+    modified: Option<SystemTime>,
+}
+
+impl Fingerprint {
+    fn of(path: &Path, deep: bool) -> Option<Self> {
+        let metadata = fs::symlink_metadata(path).ok()?;
+        let mut fingerprint = Fingerprint {
+            is_dir: metadata.is_dir(),
+            entries: 1,
+            bytes: metadata.len(),
+            modified: metadata.modified().ok(),
+        };
+        if deep && metadata.is_dir() {
+            for metadata in WalkDir::new(path)
+                .min_depth(1)
+                .into_iter()
+                .filter_map(|entry| entry.ok()?.metadata().ok())
+            {
+                fingerprint.entries += 1;
+                fingerprint.bytes += metadata.len();
+                fingerprint.modified = fingerprint.modified.max(metadata.modified().ok());
+            }
+        }
+        Some(fingerprint)
+    }
+}
+
+/// One reversible change, with what's needed to invert it
+#[derive(Debug, Clone)]
+pub(crate) enum Step {
+    Created {
+        path: PathBuf,
+        /// What a redo writes back; none for a folder
+        contents: Option<Vec<u8>>,
+        fingerprint: Option<Fingerprint>,
+    },
+    Trashed {
+        original: PathBuf,
+        /// Set while the item is in the trash
+        trash_id: Option<PathBuf>,
+        /// Set while the item is back at `original`
+        fingerprint: Option<Fingerprint>,
+    },
+    Moved {
+        from: PathBuf,
+        to: PathBuf,
+        /// Of the item wherever it is now
+        fingerprint: Option<Fingerprint>,
+    },
+    Copied {
+        source: PathBuf,
+        copy: PathBuf,
+        fingerprint: Option<Fingerprint>,
+    },
+    /// Renames applied together, so names can be swapped or shifted along
+    Renamed {
+        pairs: Vec<(PathBuf, PathBuf)>,
+        /// Of each item wherever it is now
+        fingerprints: Vec<Option<Fingerprint>>,
+    },
+}
+
+impl Step {
+    pub fn created(path: &Path) -> Self {
+        Step::Created {
+            path: path.to_path_buf(),
+            contents: if path.is_dir() {
+                None
+            } else {
+                Some(fs::read(path).unwrap_or_default())
+            },
+            fingerprint: Fingerprint::of(path, true),
+        }
+    }
+
+    pub fn trashed(original: &Path, trash_id: &Path) -> Self {
+        Step::Trashed {
+            original: original.to_path_buf(),
+            trash_id: Some(trash_id.to_path_buf()),
+            fingerprint: None,
+        }
+    }
+
+    pub fn moved(from: &Path, to: &Path) -> Self {
+        Step::Moved {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+            fingerprint: Fingerprint::of(to, false),
+        }
+    }
+
+    pub fn copied(source: &Path, copy: &Path) -> Self {
+        Step::Copied {
+            source: source.to_path_buf(),
+            copy: copy.to_path_buf(),
+            fingerprint: Fingerprint::of(copy, true),
+        }
+    }
+
+    pub fn renamed(pairs: &[(PathBuf, PathBuf)]) -> Self {
+        Step::Renamed {
+            pairs: pairs.to_vec(),
+            fingerprints: pairs
+                .iter()
+                .map(|(_, to)| Fingerprint::of(to, false))
+                .collect(),
+        }
+    }
+
+    /// Where the operation left the items
+    fn result_paths(&self) -> Vec<&Path> {
+        match self {
+            Step::Created { path, .. } => vec![path],
+            Step::Trashed { original, .. } => vec![original],
+            Step::Moved { to, .. } => vec![to],
+            Step::Copied { copy, .. } => vec![copy],
+            Step::Renamed { pairs, .. } => pairs.iter().map(|(_, to)| to.as_path()).collect(),
+        }
+    }
+
+    /// Fails without touching anything if the undo can't go ahead
+    fn check_undo(&self) -> AppResult<()> {
+        match self {
+            Step::Created {
+                path, fingerprint, ..
+            }
+            | Step::Copied {
+                copy: path,
+                fingerprint,
+                ..
+            } => unchanged(path, fingerprint, true),
+            Step::Trashed {
+                original, trash_id, ..
+            } => {
+                let trash_id = trash_id
+                    .as_deref()
+                    .ok_or_else(|| AppError::changed(original))?;
+                fs::symlink_metadata(trash_id).map_err(|_| AppError::changed(original))?;
+                vacant(original)
+            }
+            Step::Moved {
+                from,
+                to,
+                fingerprint,
+            } => {
+                unchanged(to, fingerprint, false)?;
+                vacant(from)
+            }
+            Step::Renamed {
+                pairs,
+                fingerprints,
+            } => {
+                for ((from, to), fingerprint) in pairs.iter().zip(fingerprints) {
+                    unchanged(to, fingerprint, false)?;
+                    // A name another item of the batch gives up is free
+                    if !pairs.iter().any(|(_, other)| other == from) {
+                        vacant(from)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn check_redo(&self) -> AppResult<()> {
+        match self {
+            Step::Created { path, .. } => vacant(path),
+            Step::Trashed {
+                original,
+                fingerprint,
+                ..
+            } => unchanged(original, fingerprint, false),
+            Step::Moved {
+                from,
+                to,
+                fingerprint,
+            } => {
+                unchanged(from, fingerprint, false)?;
+                vacant(to)
+            }
+            Step::Copied { source, copy, .. } => {
+                fs::symlink_metadata(source).map_err(|e| AppError::from_io(e, source))?;
+                vacant(copy)
+            }
+            Step::Renamed {
+                pairs,
+                fingerprints,
+            } => {
+                for ((from, to), fingerprint) in pairs.iter().zip(fingerprints) {
+                    unchanged(from, fingerprint, false)?;
+                    if !pairs.iter().any(|(other, _)| other == to) {
+                        vacant(to)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn undo(&mut self) -> AppResult<()> {
+        match self {
+            Step::Created { path, .. } | Step::Copied { copy: path, .. } => {
+                remove_path(path).map_err(|e| AppError::from_io(e, &*path))
+            }
+            Step::Trashed {
+                original,
+                trash_id,
+                fingerprint,
+            } => {
+                let id = trash_id
+                    .as_deref()
+                    .ok_or_else(|| AppError::changed(&*original))?;
+                restore_path(id, ConflictPolicy::Fail)?;
+                *trash_id = None;
+                *fingerprint = Fingerprint::of(original, false);
+                Ok(())
+            }
+            Step::Moved {
+                from,
+                to,
+                fingerprint,
+            } => {
+                move_path(to, from, ConflictPolicy::Fail)?;
+                *fingerprint = Fingerprint::of(from, false);
+                Ok(())
+            }
+            Step::Renamed {
+                pairs,
+                fingerprints,
+            } => {
+                let reversed: Vec<(PathBuf, PathBuf)> = pairs
+                    .iter()
+                    .map(|(from, to)| (to.clone(), from.clone()))
+                    .collect();
+                rename_all(&reversed)?;
+                *fingerprints = pairs
+                    .iter()
+                    .map(|(from, _)| Fingerprint::of(from, false))
+                    .collect();
+                Ok(())
+            }
+        }
+    }
+
+    fn redo(&mut self) -> AppResult<()> {
+        match self {
+            Step::Created {
+                path,
+                contents,
+                fingerprint,
+            } => {
+                create_item(path, contents.as_deref())?;
+                *fingerprint = Fingerprint::of(path, true);
+                Ok(())
+            }
+            Step::Trashed {
+                original,
+                trash_id,
+                fingerprint,
+            } => {
+                let item = trash_path(original)?;
+                *trash_id = Some(PathBuf::from(item.id));
+                *fingerprint = None;
+                Ok(())
+            }
+            Step::Moved {
+                from,
+                to,
+                fingerprint,
+            } => {
+                move_path(from, to, ConflictPolicy::Fail)?;
+                *fingerprint = Fingerprint::of(to, false);
+                Ok(())
+            }
+            Step::Copied {
+                source,
+                copy,
+                fingerprint,
+            } => {
+                if let Err(err) = copy_tree(source, copy) {
+                    let _ = remove_path(copy);
+                    return Err(err);
+                }
+                *fingerprint = Fingerprint::of(copy, true);
+                Ok(())
+            }
+            Step::Renamed {
+                pairs,
+                fingerprints,
+            } => {
+                rename_all(pairs)?;
+                *fingerprints = pairs
+                    .iter()
+                    .map(|(_, to)| Fingerprint::of(to, false))
+                    .collect();
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Fails with `Changed` unless `path` still matches `fingerprint`
+fn unchanged(path: &Path, fingerprint: &Option<Fingerprint>, deep: bool) -> AppResult<()> {
+    match Fingerprint::of(path, deep) {
+        Some(current) if Some(&current) == fingerprint.as_ref() => Ok(()),
+        _ => Err(AppError::changed(path)),
+    }
+}
+
+/// Fails with `AlreadyExists` if something is at `path`
+fn vacant(path: &Path) -> AppResult<()> {
+    match fs::symlink_metadata(path) {
+        Ok(_) => Err(AppError::already_exists(path)),
+        Err(_) => Ok(()),
+    }
+}
+
+#[derive(Debug)]
+struct Operation {
+    id: u64,
+    kind: OperationKind,
+    recorded_at: u128,
+    steps: Vec<Step>,
+}
+
+impl Operation {
+    fn summary(&self) -> OperationSummary {
+        OperationSummary {
+            id: self.id,
+            kind: self.kind,
+            paths: self
+                .steps
+                .iter()
+                .flat_map(Step::result_paths)
+                .map(|path| platforms::normalize_path(&path.to_string_lossy()))
+                .collect(),
+            recorded_at: self.recorded_at,
+        }
+    }
+
+    /// Undoes the steps last to first. If one fails, the steps already undone are redone
+    /// so the operation stays whole and can be tried again.
+    fn undo(&mut self) -> AppResult<()> {
+        for step in self.steps.iter().rev() {
+            step.check_undo()?;
+        }
+        for index in (0..self.steps.len()).rev() {
+            if let Err(err) = self.steps[index].undo() {
+                for step in &mut self.steps[index + 1..] {
+                    let _ = step.redo();
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    fn redo(&mut self) -> AppResult<()> {
+        for step in &self.steps {
+            step.check_redo()?;
+        }
+        for index in 0..self.steps.len() {
+            if let Err(err) = self.steps[index].redo() {
+                for step in self.steps[..index].iter_mut().rev() {
+                    let _ = step.undo();
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Stack {
+    Undo,
+    Redo,
+}
+
+/// Undo and redo stacks of operations, most recent last
+#[derive(Debug, Default)]
+pub(crate) struct Journal {
+    undo: Vec<Operation>,
+    redo: Vec<Operation>,
+    next_id: u64,
+}
+
+impl Journal {
+    /// Adds an operation to undo. A new operation ends the redo history.
+    pub fn record(&mut self, kind: OperationKind, steps: Vec<Step>) {
+        if steps.is_empty() {
+            return;
+        }
+        self.next_id += 1;
+        self.undo.push(Operation {
+            id: self.next_id,
+            kind,
+            recorded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis(),
+            steps,
+        });
+        if self.undo.len() > JOURNAL_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Takes the last operation off `stack`, along with the id count at that moment
+    fn take(&mut self, stack: Stack) -> AppResult<(Operation, u64)> {
+        let operation = match stack {
+            Stack::Undo => self
+                .undo
+                .pop()
+                .ok_or_else(|| AppError::invalid_argument("There is nothing to undo."))?,
+            Stack::Redo => self
+                .redo
+                .pop()
+                .ok_or_else(|| AppError::invalid_argument("There is nothing to redo."))?,
+        };
+        Ok((operation, self.next_id))
+    }
+
+    /// Puts an operation taken off `from` back once it has run: onto the other stack if
+    /// it succeeded, where it was if it failed. Operations recorded since `next_id` stay
+    /// above it, and as they end the redo history, nothing goes back onto it.
+    fn put_back(
+        &mut self,
+        from: Stack,
+        operation: Operation,
+        next_id: u64,
+        result: AppResult<()>,
+    ) -> AppResult<OperationSummary> {
+        let summary = operation.summary();
+        let recorded_since = (self.next_id - next_id) as usize;
+        let to_undo = match (&result, from) {
+            (Ok(()), Stack::Undo) | (Err(_), Stack::Redo) => false,
+            (Ok(()), Stack::Redo) | (Err(_), Stack::Undo) => true,
+        };
+        if to_undo {
+            let position = self.undo.len().saturating_sub(recorded_since);
+            self.undo.insert(position, operation);
+            if self.undo.len() > JOURNAL_LIMIT {
+                self.undo.remove(0);
+            }
+        } else if recorded_since == 0 {
+            self.redo.push(operation);
+        }
+        result.map(|()| summary)
+    }
+
+    pub fn state(&self) -> JournalState {
+        JournalState {
+            undo: self.undo.last().map(Operation::summary),
+            redo: self.redo.last().map(Operation::summary),
+        }
+    }
+}
@@ -0,0 +1,364 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::constants::{
+    ENCODING_SNIFF_LEN, LINE_INDEX_CHUNK, LINE_INDEX_PROGRESS_INTERVAL, LINE_INDEX_STRIDE,
+    MAX_LINE_INDEXES, MAX_LINE_PAGE, MAX_PAGE_LINE_LEN,
+};
+use crate::commands::file_read::{decode_with, sniff_encoding};
+use crate::commands::file_write;
+use crate::commands::filesystem_ops::ensure_file;
+use crate::commands::sandbox;
+use crate::models::error::{AppError, AppResult};
+use crate::models::line_index::{LineIndexStatus, LinePage};
+use crate::platforms;
+use encoding_rs::Encoding;
+use once_cell::sync::Lazy;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+use tauri::Window;
+
+// Line indexes of the large files being paged through, least recently used first
+static LINE_INDEXES: Lazy<Mutex<Vec<Arc<LineIndex>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Starts indexing the lines of a large file in the background, so any window of
+/// lines can be read with `read_file_lines`
+///
+/// Emits `line-index-progress` with the lines found so far while indexing, and once
+/// more with `total_lines` set when it's done. A file that's already indexed and
+/// hasn't changed isn't indexed again.
+///
+/// Parameters:
+/// - file_path: The file to index
+///
+/// Returns:
+/// - Ok(LineIndexStatus): How far indexing has got
+/// - Err(AppError): NotFound or NotAFile if the path isn't an existing file,
+///   InvalidArgument if it isn't text, Unsupported for UTF-16 text, or the I/O error
+///   that stopped the read
+#[tauri::command]
+pub async fn open_large_file(window: Window, file_path: String) -> AppResult<LineIndexStatus> {
+    let normalized_path = platforms::normalize_path(&file_path);
+    Ok(open_index(&window, Path::new(&normalized_path))?.status())
+}
+
+/// Reads a window of lines from a large file
+///
+/// Lines past what has been indexed so far aren't returned yet; ask again after a
+/// `line-index-progress` event. If the file changed since it was indexed, it is
+/// indexed again.
+///
+/// Parameters:
+/// - file_path: The file to read
+/// - start_line: The first line to return, counting from 0
+/// - count: How many lines to return, at most 10,000
+///
+/// Returns:
+/// - Ok(LinePage): The lines, and the index's progress
+/// - Err(AppError): As for `open_large_file`
+#[tauri::command]
+pub async fn read_file_lines(
+    window: Window,
+    file_path: String,
+    start_line: u64,
+    count: u64,
+) -> AppResult<LinePage> {
+    let normalized_path = platforms::normalize_path(&file_path);
+    let index = open_index(&window, Path::new(&normalized_path))?;
+    tokio::task::spawn_blocking(move || index.read(start_line, count))
+        .await
+        .map_err(|e| AppError::internal(e.to_string()))?
+}
+
+/// Stops indexing a file and drops its index
+///
+/// Parameters:
+/// - file_path: The file passed to `open_large_file`
+#[tauri::command]
+pub async fn close_large_file(file_path: String) -> AppResult<()> {
+    let path = PathBuf::from(platforms::normalize_path(&file_path));
+    let mut indexes = LINE_INDEXES.lock().map_err(AppError::lock_poisoned)?;
+    indexes.retain(|index| {
+        let keep = index.path != path;
+        if !keep {
+            index.cancel();
+        }
+        keep
+    });
+    Ok(())
+}
+
+/// The index of the file at `path`, starting a new one when there is none or the
+/// file has changed since
+fn open_index(window: &Window, path: &Path) -> AppResult<Arc<LineIndex>> {
+    sandbox::ensure_allowed(path)?;
+    let etag = current_etag(path)?;
+    let mut indexes = LINE_INDEXES.lock().map_err(AppError::lock_poisoned)?;
+    if let Some(position) = indexes.iter().position(|index| index.path == path) {
+        let index = indexes.remove(position);
+        if index.etag == etag {
+            indexes.push(index.clone());
+            return Ok(index);
+        }
+        index.cancel();
+    }
+
+    let index = Arc::new(LineIndex::new(path)?);
+    indexes.push(index.clone());
+    if indexes.len() > MAX_LINE_INDEXES {
+        indexes.remove(0).cancel();
+    }
+    drop(indexes);
+
+    let building = index.clone();
+    let window = window.clone();
+    tokio::task::spawn_blocking(move || {
+        let result = building.build(|status| {
+            let _ = window.emit("line-index-progress", status);
+        });
+        if let Err(e) = result {
+            if !matches!(e, AppError::Cancelled { .. }) {
+                log::warn!("Indexing lines of {:?} failed: {}", building.path, e);
+            }
+            // Forget the partial index, so the next read starts over
+            if let Ok(mut indexes) = LINE_INDEXES.lock() {
+                indexes.retain(|index| !Arc::ptr_eq(index, &building));
+            }
+        }
+    });
+    Ok(index)
+}
+
+fn current_etag(path: &Path) -> AppResult<String> {
+    ensure_file(path)?;
+    let metadata = std::fs::metadata(path).map_err(|e| AppError::from_io(e, path))?;
+    Ok(file_write::etag(&metadata))
+}
+
+/// Where every `LINE_INDEX_STRIDE`th line of a file starts. Reading a line means
+/// seeking to the checkpoint before it and skipping at most a stride of lines.
+pub(crate) struct LineIndex {
+    path: PathBuf,
+    etag: String,
+    size: u64,
+    encoding: &'static Encoding,
+    /// `checkpoints[i]` is the byte offset where line `i * LINE_INDEX_STRIDE` starts
+    checkpoints: RwLock<Vec<u64>>,
+    /// Complete lines found so far; the line count once `complete` is set
+    indexed_lines: AtomicU64,
+    complete: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+impl LineIndex {
+    /// An empty index for the file at `path`, whose encoding is sniffed from its
+    /// first bytes. Only encodings that keep `\n` a single byte can be indexed.
+    pub(crate) fn new(path: &Path) -> AppResult<Self> {
+        let file = File::open(path).map_err(|e| AppError::from_io(e, path))?;
+        let metadata = file.metadata().map_err(|e| AppError::from_io(e, path))?;
+        let mut sample = Vec::with_capacity(ENCODING_SNIFF_LEN);
+        file.take(ENCODING_SNIFF_LEN as u64)
+            .read_to_end(&mut sample)
+            .map_err(|e| AppError::from_io(e, path))?;
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let truncated = (sample.len() as u64) < metadata.len();
+        let (encoding, bom_len) = sniff_encoding(&name, &sample, truncated).ok_or_else(|| {
+            AppError::invalid_argument(format!("'{}' isn't a text file.", display(path)))
+        })?;
+        if !encoding.is_ascii_compatible() {
+            return Err(AppError::unsupported(format!(
+                "Reading {} files by line isn't supported.",
+                encoding.name()
+            )));
+        }
+
+        Ok(LineIndex {
+            path: path.to_path_buf(),
+            etag: file_write::etag(&metadata),
+            size: metadata.len(),
+            encoding,
+            checkpoints: RwLock::new(vec![bom_len as u64]),
+            indexed_lines: AtomicU64::new(0),
+            complete: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+        })
+    }
+
+    /// Counts the lines, recording checkpoints as it goes. `on_progress` is called
+    /// every `LINE_INDEX_PROGRESS_INTERVAL` and once at the end.
+    pub(crate) fn build(&self, mut on_progress: impl FnMut(LineIndexStatus)) -> AppResult<()> {
+        let io_error = |e| AppError::from_io(e, &self.path);
+        let start = self.checkpoints.read().map_err(AppError::lock_poisoned)?[0];
+        let mut file = File::open(&self.path).map_err(io_error)?;
+        file.seek(SeekFrom::Start(start)).map_err(io_error)?;
+        // Lines appended after the index was started belong to the next one
+        let mut reader = file.take(self.size.saturating_sub(start));
+
+        let mut buffer = vec![0; LINE_INDEX_CHUNK];
+        let mut offset = start;
+        let mut lines = 0u64;
+        let mut last_byte = b'\n';
+        let mut last_report = Instant::now();
+        loop {
+            if self.cancelled.load(Ordering::Relaxed) {
+                return Err(AppError::Cancelled {
+                    path: Some(display(&self.path)),
+                });
+            }
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(io_error(e)),
+            };
+
+            let mut found = Vec::new();
+            for position in memchr::memchr_iter(b'\n', &buffer[..read]) {
+                lines += 1;
+                if lines.is_multiple_of(LINE_INDEX_STRIDE) {
+                    found.push(offset + position as u64 + 1);
+                }
+            }
+            if !found.is_empty() {
+                self.checkpoints
+                    .write()
+                    .map_err(AppError::lock_poisoned)?
+                    .extend(found);
+            }
+            // Published after the checkpoints, so readers always find the one they need
+            self.indexed_lines.store(lines, Ordering::Release);
+            last_byte = buffer[read - 1];
+            offset += read as u64;
+
+            if last_report.elapsed() >= LINE_INDEX_PROGRESS_INTERVAL {
+                on_progress(self.status());
+                last_report = Instant::now();
+            }
+        }
+
+        // A last line without a line break still counts
+        if offset > start && last_byte != b'\n' {
+            lines += 1;
+        }
+        self.indexed_lines.store(lines, Ordering::Release);
+        self.complete.store(true, Ordering::Release);
+        on_progress(self.status());
+        Ok(())
+    }
+
+    pub(crate) fn status(&self) -> LineIndexStatus {
+        let complete = self.complete.load(Ordering::Acquire);
+        let indexed_lines = self.indexed_lines.load(Ordering::Acquire);
+        LineIndexStatus {
+            path: display(&self.path),
+            etag: self.etag.clone(),
+            size: self.size,
+            indexed_lines,
+            total_lines: complete.then_some(indexed_lines),
+            encoding: self.encoding.name().to_string(),
+        }
+    }
+
+    /// Up to `count` lines from `start_line` on, as far as the file has been indexed
+    pub(crate) fn read(&self, start_line: u64, count: u64) -> AppResult<LinePage> {
+        let status = self.status();
+        let end = start_line
+            .saturating_add(count.min(MAX_LINE_PAGE))
+            .min(status.indexed_lines);
+        let mut page = LinePage {
+            path: status.path,
+            etag: status.etag,
+            start_line,
+            lines: Vec::new(),
+            indexed_lines: status.indexed_lines,
+            total_lines: status.total_lines,
+            clipped: false,
+        };
+        if start_line >= end {
+            return Ok(page);
+        }
+
+        let checkpoint = start_line / LINE_INDEX_STRIDE;
+        let offset = self.checkpoints.read().map_err(AppError::lock_poisoned)?[checkpoint as usize];
+        let io_error = |e| AppError::from_io(e, &self.path);
+        let mut file = File::open(&self.path).map_err(io_error)?;
+        file.seek(SeekFrom::Start(offset)).map_err(io_error)?;
+        let mut reader = BufReader::new(file.take(self.size.saturating_sub(offset)));
+
+        let mut line = Vec::new();
+        for _ in checkpoint * LINE_INDEX_STRIDE..start_line {
+            read_line(&mut reader, 0, &mut line).map_err(io_error)?;
+        }
+        for _ in start_line..end {
+            let clipped = read_line(&mut reader, MAX_PAGE_LINE_LEN, &mut line).map_err(io_error)?;
+            if !clipped && line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            page.clipped |= clipped;
+            page.lines.push(decode_with(self.encoding, &line, !clipped));
+        }
+        Ok(page)
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Reads one line without its `\n` into `line`, keeping at most `limit` bytes of it.
+/// Returns whether the line was longer than that.
+fn read_line(reader: &mut impl BufRead, limit: usize, line: &mut Vec<u8>) -> io::Result<bool> {
+    line.clear();
+    let mut clipped = false;
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(clipped);
+        }
+        let (length, found) = match memchr::memchr(b'\n', buffer) {
+            Some(position) => (position, true),
+            None => (buffer.len(), false),
+        };
+        let room = limit.saturating_sub(line.len());
+        line.extend_from_slice(&buffer[..length.min(room)]);
+        clipped |= length > room;
+        reader.consume(if found { length + 1 } else { length });
+        if found {
+            return Ok(clipped);
+        }
+    }
+}
+
+fn display(path: &Path) -> String {
+    platforms::normalize_path(&path.to_string_lossy())
+}
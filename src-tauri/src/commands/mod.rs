@@ -23,5 +23,30 @@
 // The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
 // for providing inspiration for this project.
 
+pub mod archive_fs;
+pub mod archive_ops;
+pub mod bulk_rename;
+pub mod constants;
+pub mod content_type;
+pub mod copy_ops;
+pub mod disk_usage;
+pub mod exif;
+pub mod file_read;
+pub mod file_templates;
+pub mod file_write;
 pub mod filesystem_ops;
+pub mod git_status;
+pub mod jobs;
+pub mod journal;
+pub mod line_index;
+pub mod move_ops;
+pub mod sandbox;
+pub mod trash_ops;
 pub mod tree;
+pub mod tree_filter;
+pub mod tree_ignore;
+pub mod tree_page;
+pub mod tree_stream;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,339 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::filesystem_ops::ensure_directory;
+use crate::commands::journal::{self, Step};
+use crate::commands::sandbox;
+use crate::models::error::{AppError, AppResult};
+use crate::models::journal::OperationKind;
+use crate::models::transfer::{ConflictPolicy, MoveOutcome, MoveResult};
+use crate::platforms;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Renames a file or folder in place
+///
+/// Parameters:
+/// - path: The item to rename
+/// - new_name: The new name, without any folder part
+/// - conflict: What to do if an item with the new name exists (defaults to Fail)
+///
+/// Returns:
+/// - Ok(MoveResult): Where the item ended up, or `Skipped` if the conflict policy said so.
+///   A rename over an existing item is `Replaced` and can't be undone.
+/// - Err(AppError): InvalidArgument for a name with a folder part, AlreadyExists for a
+///   conflict under the Fail policy, or the I/O error that stopped the rename
+#[tauri::command]
+pub async fn rename_item(
+    path: String,
+    new_name: String,
+    conflict: Option<ConflictPolicy>,
+) -> AppResult<MoveResult> {
+    let normalized_path = platforms::normalize_path(&path);
+    let source = Path::new(&normalized_path);
+    sandbox::ensure_entry_allowed(source)?;
+    if !is_plain_name(&new_name) {
+        return Err(AppError::invalid_argument(format!(
+            "'{}' is not a valid name.",
+            new_name
+        )));
+    }
+    let parent = source
+        .parent()
+        .ok_or_else(|| AppError::invalid_argument("The root folder can't be renamed."))?;
+    let target = parent.join(&new_name);
+    let policy = conflict.unwrap_or_default();
+
+    let replacing = replaces(source, &target, policy);
+    let mut result = result_for(source, &target, move_path(source, &target, policy)?);
+    if replacing {
+        result.outcome = MoveOutcome::Replaced;
+    }
+    if let Some(step) = journal_step(&result) {
+        journal::record(OperationKind::Rename, vec![step]);
+    }
+    Ok(result)
+}
+
+/// Moves files and folders into another folder
+///
+/// Each item is moved with an atomic rename when source and destination share a
+/// filesystem, and copied then deleted otherwise. A failure on one item doesn't stop
+/// the others.
+///
+/// Parameters:
+/// - sources: The items to move
+/// - destination: The folder to move them into
+/// - conflict: What to do when the folder already holds an item of the same name
+///   (defaults to Fail)
+///
+/// Returns:
+/// - Ok(Vec<MoveResult>): One result per source, in order
+/// - Err(AppError): If the destination isn't an existing folder
+#[tauri::command]
+pub async fn move_items(
+    sources: Vec<String>,
+    destination: String,
+    conflict: Option<ConflictPolicy>,
+) -> AppResult<Vec<MoveResult>> {
+    let normalized_destination = platforms::normalize_path(&destination);
+    let destination = Path::new(&normalized_destination);
+    sandbox::ensure_allowed(destination)?;
+    ensure_directory(destination)?;
+    let policy = conflict.unwrap_or_default();
+
+    let results: Vec<MoveResult> = sources
+        .iter()
+        .map(|source| {
+            move_into(
+                Path::new(&platforms::normalize_path(source)),
+                destination,
+                policy,
+            )
+        })
+        .collect();
+    let steps = results.iter().filter_map(journal_step).collect();
+    journal::record(OperationKind::Move, steps);
+
+    Ok(results)
+}
+
+/// Moves `source` into the folder `destination`, keeping its name
+pub(crate) fn move_into(source: &Path, destination: &Path, policy: ConflictPolicy) -> MoveResult {
+    let Some(name) = source.file_name() else {
+        return failed(
+            source,
+            destination,
+            AppError::invalid_argument(format!(
+                "'{}' can't be moved.",
+                platforms::normalize_path(&source.to_string_lossy())
+            )),
+        );
+    };
+    let target = destination.join(name);
+    if let Err(err) = sandbox::ensure_entry_allowed(source) {
+        return failed(source, &target, err);
+    }
+    let replacing = replaces(source, &target, policy);
+    match move_path(source, &target, policy) {
+        Ok(moved_to) => {
+            let mut result = result_for(source, &target, moved_to);
+            if replacing {
+                result.outcome = MoveOutcome::Replaced;
+            }
+            result
+        }
+        Err(err) => failed(source, &target, err),
+    }
+}
+
+/// The journal step that undoes a completed move. A move that replaced an item has
+/// none, since undoing it couldn't bring that item back.
+pub(crate) fn journal_step(result: &MoveResult) -> Option<Step> {
+    (result.outcome == MoveOutcome::Moved)
+        .then(|| Step::moved(Path::new(&result.source), Path::new(&result.destination)))
+}
+
+/// Moves `source` to exactly `target`, resolving a conflict at `target` with `policy`.
+/// Returns where the item ended up, or `None` if it was skipped.
+pub(crate) fn move_path(
+    source: &Path,
+    target: &Path,
+    policy: ConflictPolicy,
+) -> AppResult<Option<PathBuf>> {
+    let metadata = fs::symlink_metadata(source).map_err(|e| AppError::from_io(e, source))?;
+    if metadata.is_dir() && target != source && target.starts_with(source) {
+        return Err(AppError::invalid_argument(format!(
+            "Can't move '{}' into itself.",
+            platforms::normalize_path(&source.to_string_lossy())
+        )));
+    }
+
+    // A change of case only is a rename of the same entry on case-insensitive filesystems
+    if is_same_entry(source, target) || fs::symlink_metadata(target).is_err() {
+        relocate(source, target)?;
+        return Ok(Some(target.to_path_buf()));
+    }
+
+    match policy {
+        ConflictPolicy::Fail => Err(AppError::already_exists(target)),
+        ConflictPolicy::Skip => Ok(None),
+        ConflictPolicy::KeepBoth => {
+            let target = available_name(target, metadata.is_dir());
+            relocate(source, &target)?;
+            Ok(Some(target))
+        }
+        ConflictPolicy::Overwrite => {
+            replace(source, target)?;
+            Ok(Some(target.to_path_buf()))
+        }
+    }
+}
+
+/// Whether moving `source` to `target` under `policy` would replace an item there
+fn replaces(source: &Path, target: &Path, policy: ConflictPolicy) -> bool {
+    policy == ConflictPolicy::Overwrite
+        && fs::symlink_metadata(target).is_ok()
+        && !is_same_entry(source, target)
+}
+
+/// Renames, or copies then deletes when the rename would cross filesystems
+fn relocate(source: &Path, target: &Path) -> AppResult<()> {
+    match fs::rename(source, target) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::CrossesDevices => copy_then_delete(source, target),
+        Err(err) => Err(AppError::from_io(err, source)),
+    }
+}
+
+/// Moves `source` over an existing `target`. The existing item is set aside first and
+/// put back if the move fails, so a failed overwrite loses nothing.
+fn replace(source: &Path, target: &Path) -> AppResult<()> {
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    let backup = available_name(&target.with_file_name(format!(".{}.replaced", name)), true);
+    fs::rename(target, &backup).map_err(|e| AppError::from_io(e, target))?;
+
+    match relocate(source, target) {
+        Ok(()) => {
+            let _ = remove_path(&backup);
+            Ok(())
+        }
+        Err(err) => {
+            let _ = fs::rename(&backup, target);
+            Err(err)
+        }
+    }
+}
+
+/// The fallback for moves across filesystems. A partial copy is removed on failure;
+/// the source is only deleted once the copy is complete.
+pub(crate) fn copy_then_delete(source: &Path, target: &Path) -> AppResult<()> {
+    if let Err(err) = copy_tree(source, target) {
+        let _ = remove_path(target);
+        return Err(err);
+    }
+    remove_path(source).map_err(|e| AppError::from_io(e, source))
+}
+
+/// Copies files with their permissions and modification time, folders recursively and
+/// symlinks as links
+pub(crate) fn copy_tree(source: &Path, target: &Path) -> AppResult<()> {
+    let metadata = fs::symlink_metadata(source).map_err(|e| AppError::from_io(e, source))?;
+    let file_type = metadata.file_type();
+
+    if file_type.is_symlink() {
+        let link_target = fs::read_link(source).map_err(|e| AppError::from_io(e, source))?;
+        let target_is_dir = fs::metadata(source).is_ok_and(|m| m.is_dir());
+        platforms::create_symlink(&link_target, target, target_is_dir)
+            .map_err(|e| AppError::from_io(e, target))?;
+    } else if file_type.is_dir() {
+        fs::create_dir(target).map_err(|e| AppError::from_io(e, target))?;
+        for entry in fs::read_dir(source).map_err(|e| AppError::from_io(e, source))? {
+            let entry = entry.map_err(|e| AppError::from_io(e, source))?;
+            copy_tree(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        fs::set_permissions(target, metadata.permissions())
+            .map_err(|e| AppError::from_io(e, target))?;
+    } else {
+        fs::copy(source, target).map_err(|e| AppError::from_io(e, source))?;
+        if let Ok(modified) = metadata.modified() {
+            let _ = fs::File::options()
+                .write(true)
+                .open(target)
+                .and_then(|file| file.set_modified(modified));
+        }
+    }
+    Ok(())
+}
+
+/// Deletes a file, a symlink (not what it points to) or a whole folder
+pub(crate) fn remove_path(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// The first of `name (1).ext`, `name (2).ext`, ... that doesn't exist yet. Folder
+/// names get the suffix at the end.
+pub(crate) fn available_name(target: &Path, is_dir: bool) -> PathBuf {
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    (1..)
+        .map(|n| target.with_file_name(numbered_name(&name, n, is_dir)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| target.to_path_buf())
+}
+
+/// `name (n).ext`, or `name (n)` for folders
+pub(crate) fn numbered_name(name: &str, n: usize, is_dir: bool) -> String {
+    // Hidden files like `.env` are all stem
+    let (stem, extension) = match name.rfind('.') {
+        Some(dot) if dot > 0 && !is_dir => name.split_at(dot),
+        _ => (name, ""),
+    };
+    format!("{} ({}){}", stem, n, extension)
+}
+
+fn is_same_entry(source: &Path, target: &Path) -> bool {
+    source.parent() == target.parent()
+        && source
+            .file_name()
+            .zip(target.file_name())
+            .is_some_and(|(a, b)| {
+                a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase()
+            })
+        && platforms::file_identity(source) == platforms::file_identity(target)
+}
+
+/// A single path component that names an entry, e.g. not `..` or `a/b`
+pub(crate) fn is_plain_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(part)) if part == name)
+        && components.next().is_none()
+}
+
+pub(crate) fn result_for(source: &Path, target: &Path, moved_to: Option<PathBuf>) -> MoveResult {
+    let (destination, outcome) = match moved_to {
+        Some(path) => (path, MoveOutcome::Moved),
+        None => (target.to_path_buf(), MoveOutcome::Skipped),
+    };
+    MoveResult {
+        source: platforms::normalize_path(&source.to_string_lossy()),
+        destination: platforms::normalize_path(&destination.to_string_lossy()),
+        outcome,
+        error: None,
+    }
+}
+
+fn failed(source: &Path, target: &Path, error: AppError) -> MoveResult {
+    MoveResult {
+        source: platforms::normalize_path(&source.to_string_lossy()),
+        destination: platforms::normalize_path(&target.to_string_lossy()),
+        outcome: MoveOutcome::Failed,
+        error: Some(error),
+    }
+}
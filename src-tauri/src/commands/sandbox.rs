@@ -0,0 +1,129 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::archive_fs;
+use crate::models::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// The environment variable holding the folders commands may reach, separated like
+/// `PATH` entries. `main` defaults it to the home folder.
+pub(crate) const ROOT_DIR_VAR: &str = "CKS_ROOT_DIR";
+
+// The sandbox every command checks its paths against, set up on first use
+static SANDBOX: Lazy<Sandbox> = Lazy::new(|| Sandbox::new(env::var_os(ROOT_DIR_VAR)));
+
+/// Fails with PermissionDenied unless `path`, with every symlink in it followed,
+/// lies inside one of the allowed folders. Paths that don't exist yet are judged by
+/// their closest existing ancestor. For use on paths whose contents are read or
+/// written.
+pub(crate) fn ensure_allowed(path: &Path) -> AppResult<()> {
+    SANDBOX.check(path, true)
+}
+
+/// Like `ensure_allowed`, but a symlink at `path` itself isn't followed, so links
+/// pointing elsewhere can still be renamed, moved or deleted
+pub(crate) fn ensure_entry_allowed(path: &Path) -> AppResult<()> {
+    SANDBOX.check(path, false)
+}
+
+/// Whether `ensure_allowed` would accept `path`
+pub(crate) fn is_allowed(path: &Path) -> bool {
+    ensure_allowed(path).is_ok()
+}
+
+/// The folders paths have to stay inside, or none when no restriction is configured
+pub(crate) struct Sandbox {
+    roots: Option<Vec<PathBuf>>,
+}
+
+impl Sandbox {
+    /// A sandbox limited to the folders listed in `roots`. Folders that don't exist
+    /// allow nothing; with `roots` unset or empty every path is allowed.
+    pub fn new(roots: Option<OsString>) -> Self {
+        let roots = roots.filter(|roots| !roots.is_empty()).map(|roots| {
+            env::split_paths(&roots)
+                .filter(|root| !root.as_os_str().is_empty())
+                .filter_map(|root| fs::canonicalize(root).ok())
+                .collect()
+        });
+        Sandbox { roots }
+    }
+
+    pub fn check(&self, path: &Path, follow_last: bool) -> AppResult<()> {
+        let Some(roots) = &self.roots else {
+            return Ok(());
+        };
+        // A path inside an archive is as allowed as the archive itself
+        if fs::symlink_metadata(path).is_err() {
+            if let Some((archive, _)) = archive_fs::split_archive_path(&path.to_string_lossy()) {
+                return self.check(&archive, true);
+            }
+        }
+
+        match resolve(path, follow_last) {
+            Some(resolved) if roots.iter().any(|root| resolved.starts_with(root)) => Ok(()),
+            _ => Err(AppError::permission_denied(path)),
+        }
+    }
+}
+
+/// The canonical form of an absolute path without `..` components. The parts that
+/// don't exist yet are appended as they are; a dangling symlink along the way, which
+/// would lead wherever it points once created, resolves to nothing.
+fn resolve(path: &Path, follow_last: bool) -> Option<PathBuf> {
+    if !path.is_absolute()
+        || path
+            .components()
+            .any(|component| component == Component::ParentDir)
+    {
+        return None;
+    }
+    if !follow_last {
+        if let (Some(parent), Some(name)) = (path.parent(), path.file_name()) {
+            return Some(resolve(parent, true)?.join(name));
+        }
+    }
+
+    for existing in path.ancestors() {
+        match fs::canonicalize(existing) {
+            Ok(canonical) => {
+                let rest = path.strip_prefix(existing).ok()?;
+                return Some(canonical.join(rest));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                if fs::symlink_metadata(existing).is_ok() {
+                    return None;
+                }
+            }
+            Err(_) => return None,
+        }
+    }
+    None
+}
@@ -0,0 +1,203 @@
+use crate::commands::archive_fs::split_archive_path;
+use crate::commands::filesystem_ops::read_file_content;
+use crate::commands::tree::get_tree_data;
+use crate::models::error::AppError;
+use crate::models::pathinfo::{FileCategory, NodeDetails, PathinfoKind};
+use crate::models::tree_options::TreeOptions;
+use crate::search::file_ops::search_archive;
+use crate::search::types::SearchStats;
+use regex::Regex;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// A jar without folder entries, so they have to be made up from the file paths
+fn create_jar(root: &Path) -> PathBuf {
+    let archive = root.join("app.jar");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    let options = SimpleFileOptions::default();
+    zip.start_file("META-INF/MANIFEST.MF", options).unwrap();
+    zip.write_all(b"Manifest-Version: 1.0\n").unwrap();
+    zip.start_file("com/example/Main.java", options).unwrap();
+    zip.write_all(b"class Main { /* TODO: greet */ }\n")
+        .unwrap();
+    zip.start_file("logo.png", options).unwrap();
+    zip.write_all(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+    zip.start_file("../escape.txt", options).unwrap();
+    zip.write_all(b"outside").unwrap();
+    #[cfg(unix)]
+    zip.add_symlink("docs/readme", "../META-INF/MANIFEST.MF", options)
+        .unwrap();
+    zip.finish().unwrap();
+    archive
+}
+
+fn create_tar_gz(root: &Path) -> PathBuf {
+    let archive = root.join("notes.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(
+        File::create(&archive).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut tar = tar::Builder::new(encoder);
+    for (name, content) in [("notes/a.txt", "first"), ("notes/b.txt", "café")] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(1_700_000_000);
+        tar.append_data(&mut header, name, content.as_bytes())
+            .unwrap();
+    }
+    tar.into_inner().unwrap().finish().unwrap();
+    archive
+}
+
+fn inside(archive: &Path, inner: &str) -> String {
+    format!("{}!/{}", archive.to_string_lossy(), inner)
+}
+
+fn find<'a>(nodes: &'a [NodeDetails], name: &str) -> &'a NodeDetails {
+    nodes
+        .iter()
+        .find(|n| n.name == name)
+        .unwrap_or_else(|| panic!("{} not found", name))
+}
+
+#[test]
+fn test_archive_paths_split_at_the_archive() {
+    let dir = tempdir().unwrap();
+    let archive = create_jar(dir.path());
+    fs::create_dir(dir.path().join("folder!")).unwrap();
+
+    let (found, inner) = split_archive_path(&inside(&archive, "com/example")).unwrap();
+    assert_eq!(found, archive);
+    assert_eq!(inner, "/com/example");
+    assert!(split_archive_path(&format!("{}!", archive.to_string_lossy())).is_some());
+    // A folder with '!' in its name isn't an archive
+    let folder = dir.path().join("folder!").join("file");
+    assert!(split_archive_path(&folder.to_string_lossy()).is_none());
+}
+
+#[test]
+fn test_jar_root_lists_like_a_folder() {
+    let dir = tempdir().unwrap();
+    let archive = create_jar(dir.path());
+    let options = TreeOptions {
+        content_types: true,
+        ..Default::default()
+    };
+
+    let nodes = get_tree_data(inside(&archive, ""), false, None, None, Some(options)).unwrap();
+    let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+
+    assert!(!names.contains(&"escape.txt"));
+    assert_eq!(find(&nodes, "com").kind, PathinfoKind::Directory);
+    assert!(find(&nodes, "com").children.is_none());
+    let logo = find(&nodes, "logo.png");
+    assert_eq!(logo.path, inside(&archive, "logo.png"));
+    assert_eq!(
+        logo.content_type.as_ref().unwrap().category,
+        FileCategory::Image
+    );
+}
+
+#[test]
+fn test_nested_listing_makes_up_missing_folders() {
+    let dir = tempdir().unwrap();
+    let archive = create_jar(dir.path());
+    let options = TreeOptions {
+        folders_first: true,
+        content_types: true,
+        ..Default::default()
+    };
+
+    let nodes = get_tree_data(
+        inside(&archive, "com"),
+        true,
+        Some(true),
+        None,
+        Some(options),
+    )
+    .unwrap();
+
+    let example = find(&nodes, "example");
+    let main = find(example.children.as_ref().unwrap(), "Main.java");
+    assert_eq!(main.size, 33);
+    assert_eq!(
+        main.content_type.as_ref().unwrap().language.as_deref(),
+        Some("Java")
+    );
+}
+
+#[test]
+fn test_deeply_nested_entries_are_left_out() {
+    let dir = tempdir().unwrap();
+    let archive = dir.path().join("deep.zip");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    let options = SimpleFileOptions::default();
+    zip.start_file(format!("{}deep.txt", "a/".repeat(20_000)), options)
+        .unwrap();
+    zip.start_file(format!("{}shallow.txt", "a/".repeat(10)), options)
+        .unwrap();
+    zip.finish().unwrap();
+
+    let nodes = get_tree_data(inside(&archive, ""), true, None, None, None).unwrap();
+    let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+
+    assert!(names.contains(&"shallow.txt"));
+    assert!(!names.contains(&"deep.txt"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinks_resolve_inside_archive() {
+    let dir = tempdir().unwrap();
+    let archive = create_jar(dir.path());
+
+    let nodes = get_tree_data(inside(&archive, "docs"), false, None, None, None).unwrap();
+    let link = find(&nodes, "readme").symlink.as_ref().unwrap();
+    assert!(!link.broken);
+    assert!(!link.points_to_directory);
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let content = rt
+        .block_on(read_file_content(inside(&archive, "docs/readme")))
+        .unwrap();
+    assert_eq!(content.content, "Manifest-Version: 1.0\n");
+}
+
+#[test]
+fn test_members_are_read_without_extracting() {
+    let dir = tempdir().unwrap();
+    let archive = create_tar_gz(dir.path());
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let content = rt
+        .block_on(read_file_content(inside(&archive, "notes/b.txt")))
+        .unwrap();
+    assert_eq!(content.content, "café");
+    assert_eq!(content.encoding.as_deref(), Some("UTF-8"));
+    assert!(!dir.path().join("notes").exists());
+
+    let missing = rt.block_on(read_file_content(inside(&archive, "notes/c.txt")));
+    assert!(matches!(missing, Err(AppError::NotFound { .. })));
+    let folder = rt.block_on(read_file_content(inside(&archive, "notes")));
+    assert!(matches!(folder, Err(AppError::NotAFile { .. })));
+    let outside = rt.block_on(read_file_content(inside(&archive, "../secret")));
+    assert!(matches!(outside, Err(AppError::InvalidArgument { .. })));
+}
+
+#[test]
+fn test_search_descends_into_archives() {
+    let dir = tempdir().unwrap();
+    let archive = create_jar(dir.path());
+    let mut stats = SearchStats::default();
+
+    let results = search_archive(&archive, &Regex::new("TODO").unwrap(), &mut stats);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].path, inside(&archive, "com/example/Main.java"));
+    assert_eq!(stats.total_matches, 1);
+}
@@ -0,0 +1,268 @@
+use crate::commands::archive_ops::{detect_format, entry_path, ArchiveJob};
+use crate::models::archive::{ArchiveFormat, ArchiveSummary, ExtractOptions};
+use crate::models::error::{AppError, AppResult};
+use crate::models::transfer::ConflictPolicy;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+use tokio::sync::mpsc;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+fn create_project(root: &Path) -> PathBuf {
+    let project = root.join("project");
+    fs::create_dir_all(project.join("src/empty")).unwrap();
+    fs::write(project.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(project.join("src/main.rs"), "fn main() {}\n".repeat(1000)).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("main.rs", project.join("src/link.rs")).unwrap();
+    project
+}
+
+fn create(sources: &[PathBuf], archive: &Path, format: ArchiveFormat) -> AppResult<ArchiveSummary> {
+    let (_tx, mut rx) = mpsc::channel(1);
+    ArchiveJob::new("job", &mut rx, |_| {}).create(sources, archive, format)
+}
+
+fn extract(
+    archive: &Path,
+    destination: &Path,
+    options: ExtractOptions,
+) -> AppResult<ArchiveSummary> {
+    let (_tx, mut rx) = mpsc::channel(1);
+    ArchiveJob::new("job", &mut rx, |_| {}).extract(archive, destination, &options)
+}
+
+fn assert_round_trip(format: ArchiveFormat, name: &str) {
+    let dir = tempdir().unwrap();
+    let project = create_project(dir.path());
+    let archive = dir.path().join(name);
+
+    let created = create(&[project], &archive, format).unwrap();
+    assert_eq!(created.format, format);
+    assert!(created.skipped.is_empty());
+    assert_eq!(detect_format(&archive).unwrap(), Some(format));
+
+    let out = dir.path().join("out");
+    fs::create_dir(&out).unwrap();
+    let extracted = extract(&archive, &out, ExtractOptions::default()).unwrap();
+    assert_eq!(extracted.entries, created.entries);
+    assert_eq!(
+        fs::read_to_string(out.join("project/Cargo.toml")).unwrap(),
+        "[package]"
+    );
+    assert_eq!(
+        fs::read(out.join("project/src/main.rs")).unwrap(),
+        fs::read(dir.path().join("project/src/main.rs")).unwrap()
+    );
+    assert!(out.join("project/src/empty").is_dir());
+    #[cfg(unix)]
+    assert_eq!(
+        fs::read_link(out.join("project/src/link.rs")).unwrap(),
+        PathBuf::from("main.rs")
+    );
+}
+
+#[test]
+fn test_zip_round_trip() {
+    assert_round_trip(ArchiveFormat::Zip, "project.zip");
+}
+
+#[test]
+fn test_tar_gz_and_zst_round_trip() {
+    assert_round_trip(ArchiveFormat::TarGz, "project.tar.gz");
+    assert_round_trip(ArchiveFormat::TarZst, "project.tzst");
+}
+
+#[test]
+fn test_format_is_detected_from_content() {
+    let dir = tempdir().unwrap();
+    let project = create_project(dir.path());
+    let archive = dir.path().join("project.tar");
+    create(&[project], &archive, ArchiveFormat::Tar).unwrap();
+
+    let renamed = dir.path().join("download.bin");
+    fs::rename(&archive, &renamed).unwrap();
+    assert_eq!(detect_format(&renamed).unwrap(), Some(ArchiveFormat::Tar));
+    assert_eq!(
+        detect_format(&dir.path().join("project/Cargo.toml")).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_entry_paths_stay_inside_destination() {
+    assert_eq!(
+        entry_path(Path::new("a/./b.txt")),
+        Some(PathBuf::from("a/b.txt"))
+    );
+    assert_eq!(entry_path(Path::new("../evil.txt")), None);
+    assert_eq!(entry_path(Path::new("a/../../evil.txt")), None);
+    assert_eq!(entry_path(Path::new("/etc/passwd")), None);
+    assert_eq!(entry_path(Path::new(".")), None);
+}
+
+#[test]
+fn test_zip_slip_entries_are_skipped() {
+    let dir = tempdir().unwrap();
+    let archive = dir.path().join("evil.zip");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    zip.start_file("../evil.txt", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"gotcha").unwrap();
+    zip.start_file("fine.txt", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"fine").unwrap();
+    #[cfg(unix)]
+    zip.add_symlink("escape", "../../etc", SimpleFileOptions::default())
+        .unwrap();
+    zip.finish().unwrap();
+
+    let out = dir.path().join("out");
+    fs::create_dir(&out).unwrap();
+    let summary = extract(&archive, &out, ExtractOptions::default()).unwrap();
+
+    assert!(!dir.path().join("evil.txt").exists());
+    assert_eq!(fs::read_to_string(out.join("fine.txt")).unwrap(), "fine");
+    assert!(summary.skipped.contains(&"../evil.txt".to_string()));
+    #[cfg(unix)]
+    {
+        assert!(out.join("escape").symlink_metadata().is_err());
+        assert!(summary.skipped.contains(&"escape".to_string()));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_links_through_extracted_links_are_skipped() {
+    let dir = tempdir().unwrap();
+    let archive = dir.path().join("links.zip");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    zip.add_symlink("l1", ".", SimpleFileOptions::default())
+        .unwrap();
+    zip.add_symlink("l2", "l1/..", SimpleFileOptions::default())
+        .unwrap();
+    zip.finish().unwrap();
+
+    let out = dir.path().join("out");
+    fs::create_dir(&out).unwrap();
+    let summary = extract(&archive, &out, ExtractOptions::default()).unwrap();
+
+    assert!(out.join("l1").symlink_metadata().is_ok());
+    assert!(out.join("l2").symlink_metadata().is_err());
+    assert_eq!(summary.skipped, vec!["l2".to_string()]);
+}
+
+#[test]
+fn test_extraction_stops_past_size_limit() {
+    let dir = tempdir().unwrap();
+    let archive = dir.path().join("bomb.zip");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    zip.start_file("first.txt", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"small").unwrap();
+    zip.start_file("zeros.bin", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(&vec![0; 1024 * 1024]).unwrap();
+    zip.finish().unwrap();
+
+    let out = dir.path().join("out");
+    fs::create_dir(&out).unwrap();
+    let options = ExtractOptions {
+        max_bytes: Some(64 * 1024),
+        ..Default::default()
+    };
+    let result = extract(&archive, &out, options);
+
+    assert!(matches!(result, Err(AppError::InvalidArgument { .. })));
+    // What was already unpacked is removed again
+    assert_eq!(fs::read_dir(&out).unwrap().count(), 0);
+}
+
+#[test]
+fn test_conflicts_follow_policy() {
+    let dir = tempdir().unwrap();
+    let project = create_project(dir.path());
+    let archive = dir.path().join("project.zip");
+    create(std::slice::from_ref(&project), &archive, ArchiveFormat::Zip).unwrap();
+
+    let result = extract(&archive, dir.path(), ExtractOptions::default());
+    assert!(matches!(result, Err(AppError::AlreadyExists { .. })));
+    assert!(project.join("src/main.rs").exists());
+
+    fs::write(project.join("Cargo.toml"), "changed").unwrap();
+    let options = ExtractOptions {
+        conflict: Some(ConflictPolicy::KeepBoth),
+        ..Default::default()
+    };
+    extract(&archive, dir.path(), options).unwrap();
+    assert_eq!(
+        fs::read_to_string(project.join("Cargo.toml")).unwrap(),
+        "changed"
+    );
+    let copies = fs::read_dir(&project)
+        .unwrap()
+        .filter(|entry| {
+            let name = entry.as_ref().unwrap().file_name();
+            name.to_string_lossy().starts_with("Cargo")
+        })
+        .count();
+    assert_eq!(copies, 2);
+}
+
+#[test]
+fn test_failed_overwrite_puts_originals_back() {
+    let dir = tempdir().unwrap();
+    let archive = dir.path().join("bomb.zip");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    zip.start_file("first.txt", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"unpacked").unwrap();
+    zip.start_file("zeros.bin", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(&vec![0; 1024 * 1024]).unwrap();
+    zip.finish().unwrap();
+    let out = dir.path().join("out");
+    fs::create_dir(&out).unwrap();
+    fs::write(out.join("first.txt"), "original").unwrap();
+
+    let options = ExtractOptions {
+        conflict: Some(ConflictPolicy::Overwrite),
+        max_bytes: Some(64 * 1024),
+    };
+    let result = extract(&archive, &out, options);
+    assert!(matches!(result, Err(AppError::InvalidArgument { .. })));
+    assert_eq!(
+        fs::read_to_string(out.join("first.txt")).unwrap(),
+        "original"
+    );
+    assert_eq!(fs::read_dir(&out).unwrap().count(), 1);
+
+    let options = ExtractOptions {
+        conflict: Some(ConflictPolicy::Overwrite),
+        max_bytes: None,
+    };
+    extract(&archive, &out, options).unwrap();
+    assert_eq!(
+        fs::read_to_string(out.join("first.txt")).unwrap(),
+        "unpacked"
+    );
+    // Nothing of the replaced file is left behind
+    assert_eq!(fs::read_dir(&out).unwrap().count(), 2);
+}
+
+#[test]
+fn test_cancelled_create_removes_archive() {
+    let dir = tempdir().unwrap();
+    let project = create_project(dir.path());
+    let archive = dir.path().join("project.tar.gz");
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.try_send(()).unwrap();
+
+    let result =
+        ArchiveJob::new("job", &mut rx, |_| {}).create(&[project], &archive, ArchiveFormat::TarGz);
+
+    assert!(matches!(result, Err(AppError::Cancelled { .. })));
+    assert!(!archive.exists());
+}
@@ -0,0 +1,235 @@
+use crate::commands::bulk_rename::{plan, rename_all};
+use crate::commands::exif::date_in;
+use crate::commands::journal::{redo, undo, Journal, Step};
+use crate::models::bulk_rename::{BulkRenameRules, CaseTransform, RenameStatus};
+use crate::models::error::AppError;
+use crate::models::journal::OperationKind;
+use chrono::NaiveDate;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tempfile::tempdir;
+
+fn names(dir: &std::path::Path, names: &[&str]) -> Vec<PathBuf> {
+    names
+        .iter()
+        .map(|name| {
+            let path = dir.join(name);
+            fs::write(&path, name).unwrap();
+            path
+        })
+        .collect()
+}
+
+#[test]
+fn test_template_with_padded_counter() {
+    let dir = tempdir().unwrap();
+    let sources = names(dir.path(), &["b.JPG", "a.jpg"]);
+    let rules = BulkRenameRules {
+        template: Some("Holiday {n:3}{ext}".to_string()),
+        counter_start: 9,
+        case: Some(CaseTransform::Lower),
+        include_extension: true,
+        ..Default::default()
+    };
+
+    let preview = plan(&sources, &rules).unwrap();
+    let new_names: Vec<&str> = preview.iter().map(|item| item.new_name.as_str()).collect();
+    assert_eq!(new_names, vec!["Holiday 009", "Holiday 010"]);
+
+    let rules = BulkRenameRules {
+        template: Some("{parent}-{name}{ext}".to_string()),
+        case: Some(CaseTransform::Upper),
+        ..Default::default()
+    };
+    let parent = dir.path().file_name().unwrap().to_string_lossy();
+    let preview = plan(&sources, &rules).unwrap();
+    assert_eq!(preview[0].new_name, format!("{}-B.JPG", parent));
+    assert_eq!(preview[1].new_name, format!("{}-A.jpg", parent));
+    assert!(preview
+        .iter()
+        .all(|item| item.status == RenameStatus::Rename));
+}
+
+#[test]
+fn test_regex_replace_with_groups() {
+    let dir = tempdir().unwrap();
+    let sources = names(dir.path(), &["IMG_2041.png", "notes.txt"]);
+    let rules = BulkRenameRules {
+        find: Some(r"img_(\d+)".to_string()),
+        replace: "photo-$1".to_string(),
+        is_regex: true,
+        is_case_sensitive: false,
+        ..Default::default()
+    };
+
+    let preview = plan(&sources, &rules).unwrap();
+    assert_eq!(preview[0].new_name, "photo-2041.png");
+    assert_eq!(preview[1].status, RenameStatus::Unchanged);
+}
+
+#[test]
+fn test_collisions_are_flagged() {
+    let dir = tempdir().unwrap();
+    let sources = names(dir.path(), &["one.txt", "two.txt"]);
+    fs::write(dir.path().join("taken.txt"), "").unwrap();
+
+    let rules = BulkRenameRules {
+        template: Some("same{ext}".to_string()),
+        ..Default::default()
+    };
+    let preview = plan(&sources, &rules).unwrap();
+    assert!(preview
+        .iter()
+        .all(|item| item.status == RenameStatus::Collision));
+
+    let rules = BulkRenameRules {
+        find: Some("one".to_string()),
+        replace: "taken".to_string(),
+        ..Default::default()
+    };
+    let preview = plan(&sources[..1], &rules).unwrap();
+    assert_eq!(preview[0].status, RenameStatus::Collision);
+    assert!(preview[0]
+        .conflict
+        .as_deref()
+        .unwrap()
+        .ends_with("taken.txt"));
+
+    let rules = BulkRenameRules {
+        template: Some("a/b".to_string()),
+        ..Default::default()
+    };
+    let preview = plan(&sources[..1], &rules).unwrap();
+    assert_eq!(preview[0].status, RenameStatus::InvalidName);
+}
+
+#[test]
+fn test_swap_and_undo() {
+    let dir = tempdir().unwrap();
+    let sources = names(dir.path(), &["1.txt", "2.txt"]);
+    let rules = BulkRenameRules {
+        template: Some("{n}{ext}".to_string()),
+        counter_start: 2,
+        counter_step: -1,
+        ..Default::default()
+    };
+    let preview = plan(&sources, &rules).unwrap();
+    assert!(preview
+        .iter()
+        .all(|item| item.status == RenameStatus::Rename));
+
+    let pairs: Vec<(PathBuf, PathBuf)> = preview
+        .iter()
+        .map(|item| {
+            (
+                PathBuf::from(&item.source),
+                PathBuf::from(&item.destination),
+            )
+        })
+        .collect();
+    rename_all(&pairs).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("1.txt")).unwrap(),
+        "2.txt"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("2.txt")).unwrap(),
+        "1.txt"
+    );
+
+    let journal = Mutex::new(Journal::default());
+    journal
+        .lock()
+        .unwrap()
+        .record(OperationKind::Rename, vec![Step::renamed(&pairs)]);
+    undo(&journal).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("1.txt")).unwrap(),
+        "1.txt"
+    );
+    redo(&journal).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("1.txt")).unwrap(),
+        "2.txt"
+    );
+}
+
+#[test]
+fn test_counter_overflow_is_rejected() {
+    let dir = tempdir().unwrap();
+    let sources = names(dir.path(), &["a.txt", "b.txt", "c.txt"]);
+    let rules = BulkRenameRules {
+        template: Some("{n}{ext}".to_string()),
+        counter_start: i64::MAX - 1,
+        ..Default::default()
+    };
+
+    let err = plan(&sources, &rules).unwrap_err();
+    assert!(matches!(err, AppError::InvalidArgument { .. }));
+    // The last item's counter may be the largest one
+    assert!(plan(&sources[..2], &rules).is_ok());
+}
+
+#[test]
+fn test_invalid_template() {
+    let dir = tempdir().unwrap();
+    let sources = names(dir.path(), &["a.txt"]);
+    for template in [
+        "{name",
+        "{unknown}",
+        "{mtime:%Q}",
+        "{mtime:%z}",
+        "{date:%Z}",
+        "{exif:%:z}",
+        "{n:1000000000}",
+    ] {
+        let rules = BulkRenameRules {
+            template: Some(template.to_string()),
+            ..Default::default()
+        };
+        let err = plan(&sources, &rules).unwrap_err();
+        assert!(
+            matches!(err, AppError::InvalidPattern { .. }),
+            "{}",
+            template
+        );
+    }
+}
+
+#[test]
+fn test_exif_date_from_jpeg() {
+    let date = b"2023:07:14 09:30:00\0";
+    // Little-endian TIFF: IFD0 at 8 with one entry pointing at the Exif IFD at 26,
+    // which holds DateTimeOriginal; the string itself follows at 44
+    let mut tiff = Vec::new();
+    tiff.extend_from_slice(b"II*\0");
+    tiff.extend_from_slice(&8u32.to_le_bytes());
+    tiff.extend_from_slice(&1u16.to_le_bytes());
+    tiff.extend_from_slice(&0x8769u16.to_le_bytes());
+    tiff.extend_from_slice(&4u16.to_le_bytes());
+    tiff.extend_from_slice(&1u32.to_le_bytes());
+    tiff.extend_from_slice(&26u32.to_le_bytes());
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    tiff.extend_from_slice(&1u16.to_le_bytes());
+    tiff.extend_from_slice(&0x9003u16.to_le_bytes());
+    tiff.extend_from_slice(&2u16.to_le_bytes());
+    tiff.extend_from_slice(&(date.len() as u32).to_le_bytes());
+    tiff.extend_from_slice(&44u32.to_le_bytes());
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    tiff.extend_from_slice(date);
+
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+    jpeg.extend_from_slice(b"Exif\0\0");
+    jpeg.extend_from_slice(&tiff);
+    jpeg.extend_from_slice(&[0xFF, 0xD9]);
+
+    let expected = NaiveDate::from_ymd_opt(2023, 7, 14)
+        .unwrap()
+        .and_hms_opt(9, 30, 0)
+        .unwrap();
+    assert_eq!(date_in(&jpeg), Some(expected));
+    assert_eq!(date_in(&tiff), Some(expected));
+    assert_eq!(date_in(b"not an image"), None);
+}
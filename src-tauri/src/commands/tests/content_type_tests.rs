@@ -0,0 +1,152 @@
+use crate::commands::content_type::{content_type_of, detect_content_type, is_binary};
+use crate::commands::tree::get_tree_data;
+use crate::models::pathinfo::FileCategory;
+use crate::models::tree_options::TreeOptions;
+use std::fs;
+use tempfile::tempdir;
+
+const PNG_HEADER: &[u8] = &[
+    0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D,
+];
+
+#[test]
+fn test_source_code_by_extension() {
+    let content_type = content_type_of("main.rs", b"fn main() {}\n");
+
+    assert_eq!(content_type.category, FileCategory::SourceCode);
+    assert_eq!(content_type.language.as_deref(), Some("Rust"));
+    assert_eq!(content_type.mime, "text/x-rust");
+    assert!(!content_type.binary);
+}
+
+#[test]
+fn test_magic_bytes_win_over_extension() {
+    let content_type = content_type_of("notes.txt", PNG_HEADER);
+
+    assert_eq!(content_type.mime, "image/png");
+    assert_eq!(content_type.category, FileCategory::Image);
+    assert!(content_type.binary);
+}
+
+#[test]
+fn test_text_with_binary_extension_is_text() {
+    let content_type = content_type_of("fake.png", b"just some words");
+
+    assert_eq!(content_type.mime, "text/plain");
+    assert_eq!(content_type.category, FileCategory::Text);
+    assert!(!content_type.binary);
+}
+
+#[test]
+fn test_unknown_bytes_with_nul_are_binary() {
+    let content_type = content_type_of("data.rs", b"abc\0def");
+
+    assert_eq!(content_type.mime, "application/octet-stream");
+    assert_eq!(content_type.category, FileCategory::Binary);
+    assert_eq!(content_type.language, None);
+    assert!(content_type.binary);
+}
+
+#[test]
+fn test_shebang_without_extension() {
+    let content_type = content_type_of("deploy", b"#!/usr/bin/env python3\nprint('hi')\n");
+
+    assert_eq!(content_type.category, FileCategory::SourceCode);
+    assert_eq!(content_type.language.as_deref(), Some("Python"));
+}
+
+#[test]
+fn test_special_file_names() {
+    let content_type = content_type_of("Makefile", b"all:\n\techo hi\n");
+
+    assert_eq!(content_type.language.as_deref(), Some("Makefile"));
+}
+
+#[test]
+fn test_detect_from_disk() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("image.png"), PNG_HEADER).unwrap();
+    fs::write(dir.path().join("readme.md"), "# Title").unwrap();
+
+    assert!(is_binary(&dir.path().join("image.png")));
+    assert!(!is_binary(&dir.path().join("readme.md")));
+    assert!(!is_binary(&dir.path().join("missing")));
+    assert_eq!(
+        detect_content_type(&dir.path().join("readme.md"))
+            .unwrap()
+            .category,
+        FileCategory::Document
+    );
+}
+
+#[test]
+fn test_listing_includes_content_type_for_files_only() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join("src/app.ts"), "export {};").unwrap();
+
+    let options = TreeOptions {
+        content_types: true,
+        ..Default::default()
+    };
+    let nodes = get_tree_data(
+        dir.path().to_string_lossy().to_string(),
+        true,
+        None,
+        None,
+        Some(options),
+    )
+    .unwrap();
+
+    let folder = nodes.iter().find(|n| n.name == "src").unwrap();
+    assert!(folder.content_type.is_none());
+    let file = nodes.iter().find(|n| n.name == "app.ts").unwrap();
+    let content_type = file.content_type.as_ref().unwrap();
+    assert_eq!(content_type.language.as_deref(), Some("TypeScript"));
+}
+
+#[test]
+fn test_listing_leaves_content_type_out_unless_asked() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("app.ts"), "export {};").unwrap();
+
+    let nodes = get_tree_data(
+        dir.path().to_string_lossy().to_string(),
+        false,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(nodes[0].content_type.is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_listing_does_not_read_fifos() {
+    let dir = tempdir().unwrap();
+    let fifo = dir.path().join("pipe");
+    let status = std::process::Command::new("mkfifo")
+        .arg(&fifo)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let options = TreeOptions {
+        content_types: true,
+        ..Default::default()
+    };
+
+    // Opening the FIFO for reading would block until a writer shows up
+    let nodes = get_tree_data(
+        dir.path().to_string_lossy().to_string(),
+        false,
+        None,
+        None,
+        Some(options),
+    )
+    .unwrap();
+
+    assert_eq!(nodes.len(), 1);
+    assert!(nodes[0].content_type.is_none());
+}
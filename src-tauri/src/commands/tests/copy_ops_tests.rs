@@ -0,0 +1,272 @@
+use crate::commands::copy_ops::Copier;
+use crate::models::error::AppError;
+use crate::models::transfer::{
+    ConflictPolicy, ConflictResolution, CopyConflict, CopyOptions, CopyProgress, CopySummary,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tempfile::tempdir;
+use tokio::sync::mpsc;
+
+fn create_project(root: &Path) -> PathBuf {
+    let project = root.join("project");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(project.join("src/main.rs"), vec![b'x'; 3 * 1024 * 1024]).unwrap();
+    project
+}
+
+fn copy(
+    sources: &[PathBuf],
+    destination: &Path,
+    options: CopyOptions,
+    answers: &mut Vec<ConflictResolution>,
+) -> (CopySummary, Vec<CopyConflict>, Vec<CopyProgress>) {
+    let (_tx, mut rx) = mpsc::channel(1);
+    let mut conflicts = Vec::new();
+    let mut progress = Vec::new();
+    let summary = Copier::new(
+        "job",
+        options,
+        &mut rx,
+        |p| progress.push(p),
+        |conflict| {
+            conflicts.push(conflict.clone());
+            (!answers.is_empty()).then(|| answers.remove(0))
+        },
+    )
+    .run(sources, destination);
+    (summary, conflicts, progress)
+}
+
+fn answer(policy: ConflictPolicy, apply_to_all: bool) -> ConflictResolution {
+    ConflictResolution {
+        policy,
+        apply_to_all,
+    }
+}
+
+#[test]
+fn test_copies_tree_with_progress() {
+    let dir = tempdir().unwrap();
+    let project = create_project(dir.path());
+    let dest = dir.path().join("dest");
+    fs::create_dir(&dest).unwrap();
+
+    let (summary, conflicts, progress) = copy(
+        std::slice::from_ref(&project),
+        &dest,
+        CopyOptions::default(),
+        &mut Vec::new(),
+    );
+
+    assert!(summary.errors.is_empty());
+    assert!(!summary.cancelled);
+    assert!(conflicts.is_empty());
+    assert_eq!(summary.files_copied, 2);
+    assert_eq!(summary.bytes_copied, 3 * 1024 * 1024 + 9);
+    assert_eq!(
+        fs::read(dest.join("project/src/main.rs")).unwrap().len(),
+        3 * 1024 * 1024
+    );
+    let last = progress.last().unwrap();
+    assert_eq!(last.total_files, 2);
+    assert_eq!(last.bytes_copied, last.total_bytes);
+}
+
+#[test]
+fn test_preserves_modified_time_and_permissions() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("old.txt");
+    fs::write(&source, "old").unwrap();
+    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    fs::File::options()
+        .write(true)
+        .open(&source)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    let mut permissions = fs::metadata(&source).unwrap().permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(&source, permissions).unwrap();
+    let dest = dir.path().join("dest");
+    fs::create_dir(&dest).unwrap();
+
+    copy(&[source], &dest, CopyOptions::default(), &mut Vec::new());
+
+    let copied = fs::metadata(dest.join("old.txt")).unwrap();
+    assert_eq!(copied.modified().unwrap(), modified);
+    assert!(copied.permissions().readonly());
+}
+
+#[test]
+fn test_conflicts_are_asked_and_answers_can_apply_to_all() {
+    let dir = tempdir().unwrap();
+    let project = create_project(dir.path());
+    let dest = dir.path().join("dest");
+    fs::create_dir_all(dest.join("project/src")).unwrap();
+    fs::write(dest.join("project/Cargo.toml"), "existing").unwrap();
+    fs::write(dest.join("project/src/main.rs"), "existing").unwrap();
+
+    // Merge the folders, then skip every file that exists on both sides
+    let mut answers = vec![
+        answer(ConflictPolicy::Overwrite, false),
+        answer(ConflictPolicy::Skip, true),
+    ];
+    let (summary, conflicts, _) = copy(&[project], &dest, CopyOptions::default(), &mut answers);
+
+    assert_eq!(conflicts.len(), 2);
+    assert!(conflicts[0].source_is_dir && conflicts[0].destination_is_dir);
+    assert_eq!(summary.skipped.len(), 2);
+    assert_eq!(
+        fs::read_to_string(dest.join("project/Cargo.toml")).unwrap(),
+        "existing"
+    );
+    assert_eq!(
+        fs::read_to_string(dest.join("project/src/main.rs")).unwrap(),
+        "existing"
+    );
+}
+
+#[test]
+fn test_keep_both_and_overwrite_policies() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("notes.txt");
+    fs::write(&source, "new").unwrap();
+    let dest = dir.path().join("dest");
+    fs::create_dir(&dest).unwrap();
+    fs::write(dest.join("notes.txt"), "old").unwrap();
+
+    let keep_both = CopyOptions {
+        conflict: Some(ConflictPolicy::KeepBoth),
+        ..Default::default()
+    };
+    copy(
+        std::slice::from_ref(&source),
+        &dest,
+        keep_both,
+        &mut Vec::new(),
+    );
+    assert_eq!(
+        fs::read_to_string(dest.join("notes (1).txt")).unwrap(),
+        "new"
+    );
+
+    let overwrite = CopyOptions {
+        conflict: Some(ConflictPolicy::Overwrite),
+        ..Default::default()
+    };
+    copy(&[source], &dest, overwrite, &mut Vec::new());
+    assert_eq!(fs::read_to_string(dest.join("notes.txt")).unwrap(), "new");
+    // No partial files left behind
+    assert_eq!(fs::read_dir(&dest).unwrap().count(), 2);
+}
+
+#[test]
+fn test_fail_answer_stops_the_copy() {
+    let dir = tempdir().unwrap();
+    let first = dir.path().join("a.txt");
+    let second = dir.path().join("b.txt");
+    fs::write(&first, "a").unwrap();
+    fs::write(&second, "b").unwrap();
+    let dest = dir.path().join("dest");
+    fs::create_dir(&dest).unwrap();
+    fs::write(dest.join("a.txt"), "existing").unwrap();
+
+    let mut answers = vec![answer(ConflictPolicy::Fail, false)];
+    let (summary, _, _) = copy(
+        &[first, second],
+        &dest,
+        CopyOptions::default(),
+        &mut answers,
+    );
+
+    assert!(matches!(
+        summary.errors[..],
+        [AppError::AlreadyExists { .. }]
+    ));
+    assert!(!dest.join("b.txt").exists());
+}
+
+#[test]
+fn test_cancelled_copy_stops_and_reports() {
+    let dir = tempdir().unwrap();
+    let project = create_project(dir.path());
+    let dest = dir.path().join("dest");
+    fs::create_dir(&dest).unwrap();
+
+    let (tx, mut rx) = mpsc::channel(1);
+    let summary = Copier::new(
+        "job",
+        CopyOptions::default(),
+        &mut rx,
+        |progress: CopyProgress| {
+            // Cancel as soon as the totals are known
+            if progress.total_files > 0 {
+                let _ = tx.try_send(());
+            }
+        },
+        |_| None,
+    )
+    .run(&[project], &dest);
+
+    assert!(summary.cancelled);
+    assert_eq!(summary.files_copied, 0);
+    assert_eq!(fs::read_dir(&dest).unwrap().count(), 0);
+}
+
+#[test]
+fn test_cancelled_while_waiting_for_an_answer() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("a.txt");
+    fs::write(&source, "a").unwrap();
+    let dest = dir.path().join("dest");
+    fs::create_dir(&dest).unwrap();
+    fs::write(dest.join("a.txt"), "existing").unwrap();
+
+    let (summary, conflicts, _) = copy(&[source], &dest, CopyOptions::default(), &mut Vec::new());
+
+    assert_eq!(conflicts.len(), 1);
+    assert!(summary.cancelled);
+    assert_eq!(fs::read_to_string(dest.join("a.txt")).unwrap(), "existing");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinks_are_followed_or_preserved() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("source");
+    fs::create_dir(&source).unwrap();
+    fs::write(source.join("real.txt"), "real").unwrap();
+    std::os::unix::fs::symlink("real.txt", source.join("link.txt")).unwrap();
+    // A loop back to the folder itself
+    std::os::unix::fs::symlink(".", source.join("self")).unwrap();
+
+    let followed = dir.path().join("followed");
+    fs::create_dir(&followed).unwrap();
+    copy(
+        std::slice::from_ref(&source),
+        &followed,
+        CopyOptions::default(),
+        &mut Vec::new(),
+    );
+    let link = followed.join("source/link.txt");
+    assert!(!fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(fs::read_to_string(&link).unwrap(), "real");
+
+    let preserved = dir.path().join("preserved");
+    fs::create_dir(&preserved).unwrap();
+    let options = CopyOptions {
+        preserve_symlinks: true,
+        ..Default::default()
+    };
+    copy(&[source], &preserved, options, &mut Vec::new());
+    assert_eq!(
+        fs::read_link(preserved.join("source/link.txt")).unwrap(),
+        Path::new("real.txt")
+    );
+}
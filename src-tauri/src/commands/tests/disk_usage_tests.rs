@@ -0,0 +1,131 @@
+use crate::commands::disk_usage::{
+    cached_report, compute_disk_usage, invalidate_cached_usage, store_report,
+};
+use crate::models::disk_usage::DiskUsageReport;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+use tokio::sync::mpsc;
+
+fn create_sample_tree(root: &Path) {
+    fs::create_dir_all(root.join("big/inner")).unwrap();
+    fs::create_dir(root.join("small")).unwrap();
+    fs::write(root.join("top.bin"), vec![0u8; 100]).unwrap();
+    fs::write(root.join("big/a.bin"), vec![0u8; 5_000]).unwrap();
+    fs::write(root.join("big/inner/b.bin"), vec![0u8; 20_000]).unwrap();
+    fs::write(root.join("small/c.bin"), vec![0u8; 10]).unwrap();
+}
+
+fn analyze(root: &Path, top_n: usize) -> DiskUsageReport {
+    let (_tx, mut rx) = mpsc::channel(1);
+    compute_disk_usage("job", root, top_n, &mut rx, |_| {})
+}
+
+#[test]
+fn test_totals_are_recursive() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+
+    let report = analyze(dir.path(), 10);
+
+    assert!(!report.cancelled);
+    assert_eq!(report.root.apparent_size, 25_110);
+    assert_eq!(report.root.file_count, 4);
+    assert_eq!(report.root.directory_count, 3);
+
+    let big = report
+        .largest_directories
+        .iter()
+        .find(|d| d.path.ends_with("big"))
+        .unwrap();
+    assert_eq!(big.apparent_size, 25_000);
+    assert_eq!(big.file_count, 2);
+    assert_eq!(big.directory_count, 1);
+}
+
+#[test]
+fn test_largest_entries_are_ranked() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+
+    let report = analyze(dir.path(), 2);
+
+    assert_eq!(report.largest_files.len(), 2);
+    assert!(report.largest_files[0].path.ends_with("b.bin"));
+    assert!(report.largest_files[1].path.ends_with("a.bin"));
+    assert_eq!(report.largest_directories.len(), 2);
+    assert!(report.largest_directories[0].path.ends_with("big"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_hard_links_are_counted_once() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("original.bin"), vec![0u8; 4_096]).unwrap();
+    fs::hard_link(dir.path().join("original.bin"), dir.path().join("link.bin")).unwrap();
+
+    let report = analyze(dir.path(), 10);
+
+    assert_eq!(report.root.apparent_size, 4_096);
+    assert_eq!(report.root.file_count, 1);
+}
+
+#[test]
+fn test_cancelled_scan_stops() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.try_send(()).unwrap();
+
+    let report = compute_disk_usage("job", dir.path(), 10, &mut rx, |_| {});
+
+    assert!(report.cancelled);
+    assert_eq!(report.root.file_count, 0);
+}
+
+#[test]
+fn test_cache_is_invalidated_by_changes_below_root() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+    let report = analyze(dir.path(), 5);
+    store_report(dir.path(), &report);
+
+    let cached = cached_report(dir.path(), "second", 3).unwrap();
+    assert!(cached.from_cache);
+    assert_eq!(cached.job_id, "second");
+    assert_eq!(cached.largest_files.len(), 3);
+
+    // A change elsewhere keeps the cache
+    invalidate_cached_usage(&[Path::new("/somewhere/else").to_path_buf()]);
+    assert!(cached_report(dir.path(), "third", 3).is_some());
+
+    invalidate_cached_usage(&[dir.path().join("big/inner/new.bin")]);
+    assert!(cached_report(dir.path(), "fourth", 3).is_none());
+}
+
+#[test]
+fn test_cache_is_bypassed_for_longer_reports() {
+    let dir = tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("a/b/c")).unwrap();
+    for i in 0..5 {
+        fs::write(dir.path().join(format!("a/file{}.bin", i)), vec![0u8; i]).unwrap();
+    }
+    let report = analyze(dir.path(), 2);
+    store_report(dir.path(), &report);
+
+    assert!(cached_report(dir.path(), "job", 2).is_some());
+    assert!(cached_report(dir.path(), "job", 4).is_none());
+}
+
+#[test]
+fn test_cache_is_shared_by_spellings_of_a_root() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(dir.path().join("data/file.bin"), vec![0u8; 8]).unwrap();
+    let root = dir.path().join("data");
+    let report = analyze(&root, 2);
+    store_report(&root, &report);
+
+    assert!(cached_report(&dir.path().join("data/"), "job", 2).is_some());
+    assert!(cached_report(&dir.path().join("data/../data"), "job", 2).is_some());
+}
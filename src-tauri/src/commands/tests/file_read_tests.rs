@@ -0,0 +1,72 @@
+use crate::commands::file_read::{decode, hex_dump, read_text};
+use crate::models::file_content::LineEnding;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_utf8_with_line_endings() {
+    let content = decode("a.txt", b"one\r\ntwo\r\n", 10, String::new());
+    assert_eq!(content.encoding.as_deref(), Some("UTF-8"));
+    assert_eq!(content.line_ending, LineEnding::Crlf);
+    assert!(!content.truncated && !content.is_binary);
+
+    let content = decode("a.txt", b"one\ntwo\r\n", 9, String::new());
+    assert_eq!(content.line_ending, LineEnding::Mixed);
+    let content = decode("a.txt", b"one", 3, String::new());
+    assert_eq!(content.line_ending, LineEnding::None);
+}
+
+#[test]
+fn test_utf16_with_and_without_bom() {
+    let text = "h\u{e9}llo\nw\u{f6}rld\n";
+    let mut le = vec![0xFF, 0xFE];
+    le.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+    let content = decode("a.txt", &le, le.len() as u64, String::new());
+    assert_eq!(content.content, text);
+    assert_eq!(content.encoding.as_deref(), Some("UTF-16LE"));
+    assert!(content.has_bom);
+    assert_eq!(content.line_ending, LineEnding::Lf);
+
+    let be: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let content = decode("a.txt", &be, be.len() as u64, String::new());
+    assert_eq!(content.content, text);
+    assert_eq!(content.encoding.as_deref(), Some("UTF-16BE"));
+    assert!(!content.has_bom);
+}
+
+#[test]
+fn test_legacy_encoding_is_detected() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    let (bytes, _, _) = encoding_rs::WINDOWS_1252
+        .encode("Le café était très bon, à ce qu'on dit, et la crème brûlée aussi.\n");
+    fs::write(&path, &bytes).unwrap();
+
+    let content = read_text(&path).unwrap();
+    assert_eq!(content.encoding.as_deref(), Some("windows-1252"));
+    assert!(content.content.contains("crème brûlée"));
+    assert_eq!(content.size, bytes.len() as u64);
+}
+
+#[test]
+fn test_binary_becomes_hex_dump() {
+    let bytes: Vec<u8> = (0u8..20).chain(*b"ABC").collect();
+    let content = decode("blob.bin", &bytes, bytes.len() as u64, String::new());
+    assert!(content.is_binary);
+    assert_eq!(content.encoding, None);
+    assert_eq!(content.content, hex_dump(&bytes));
+    assert_eq!(
+        hex_dump(b"Hello, world!\n\0\x01AB"),
+        "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|\n\
+         00000010  41 42                                             |AB|\n"
+    );
+}
+
+#[test]
+fn test_truncated_text_drops_cut_character() {
+    // "añb" with the read stopping inside the two bytes of "ñ"
+    let content = decode("a.txt", &[b'a', 0xC3], 4, String::new());
+    assert!(content.truncated);
+    assert_eq!(content.encoding.as_deref(), Some("UTF-8"));
+    assert_eq!(content.content, "a");
+}
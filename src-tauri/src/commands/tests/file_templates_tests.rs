@@ -0,0 +1,57 @@
+use crate::commands::file_templates::{render, template_content};
+use crate::models::error::AppError;
+use chrono::NaiveDate;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_render_fills_known_variables() {
+    let now = NaiveDate::from_ymd_opt(2025, 3, 1)
+        .unwrap()
+        .and_hms_opt(14, 5, 0)
+        .unwrap();
+    let rendered = render(
+        "{{name}}|{{ext}}|{{ file_name }}|{{date}} {{time}}|{{year}}|{{author}}|{{other}}|{{open",
+        "report.final.md",
+        now,
+        "ana",
+    );
+    assert_eq!(
+        rendered,
+        "report.final|.md|report.final.md|2025-03-01 14:05|2025|ana|{{other}}|{{open"
+    );
+}
+
+#[test]
+fn test_built_in_template_by_extension() {
+    let content = template_content(None, "Notes.MD", None).unwrap();
+    assert_eq!(content, "# Notes\n\n");
+    assert_eq!(template_content(None, "data.json", None).unwrap(), "{}\n");
+    // Extensions without a template, and names without one, start empty
+    assert_eq!(template_content(None, "data.bin", None).unwrap(), "");
+    assert_eq!(template_content(None, "Makefile", None).unwrap(), "");
+}
+
+#[test]
+fn test_user_templates() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("default.md"), "---\ntitle: {{name}}\n---\n").unwrap();
+    fs::write(
+        dir.path().join("Component.tsx"),
+        "export function {{name}}() {}\n",
+    )
+    .unwrap();
+
+    let content = template_content(None, "intro.md", Some(dir.path())).unwrap();
+    assert_eq!(content, "---\ntitle: intro\n---\n");
+
+    let content = template_content(Some("Component.tsx"), "Button.tsx", Some(dir.path())).unwrap();
+    assert_eq!(content, "export function Button() {}\n");
+
+    // Built-in templates can be named too, whatever the extension
+    let content = template_content(Some("json"), "x.txt", Some(dir.path())).unwrap();
+    assert_eq!(content, "{}\n");
+
+    let err = template_content(Some("Missing"), "x.txt", Some(dir.path())).unwrap_err();
+    assert!(matches!(err, AppError::InvalidArgument { .. }));
+}
@@ -0,0 +1,162 @@
+use crate::commands::file_write::{etag, write_atomic, write_file_content};
+use crate::commands::filesystem_ops::read_file_content;
+use crate::models::error::AppError;
+use crate::models::file_content::{FileContent, ReadFormat};
+use std::fs;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_write_with_etag_from_read() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    fs::write(&path, "first").unwrap();
+
+    let read = read_file_content(path.to_string_lossy().into_owned())
+        .await
+        .unwrap();
+    assert_eq!(read.content, "first");
+
+    let written = write_atomic(&path, b"second", Some(&read.etag), true).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+    assert_eq!(written.size, 6);
+    assert_eq!(written.etag, etag(&fs::metadata(&path).unwrap()));
+    let backup = written.backup.unwrap();
+    assert!(backup.ends_with("notes.txt.bak"));
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "first");
+
+    // No temp files are left behind
+    let mut names: Vec<String> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["notes.txt", "notes.txt.bak"]);
+}
+
+#[test]
+fn test_write_refused_after_outside_change() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    fs::write(&path, "first").unwrap();
+    let stale = etag(&fs::metadata(&path).unwrap());
+
+    // Another tool saves by writing a new file and renaming it over the old one
+    fs::write(dir.path().join("other"), "edited").unwrap();
+    fs::rename(dir.path().join("other"), &path).unwrap();
+
+    let err = write_atomic(&path, b"mine", Some(&stale), false).unwrap_err();
+    assert!(matches!(err, AppError::Modified { .. }));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "edited");
+
+    fs::remove_file(&path).unwrap();
+    let err = write_atomic(&path, b"mine", Some(&stale), false).unwrap_err();
+    assert!(matches!(err, AppError::Modified { .. }));
+    assert!(!path.exists());
+
+    // Without an etag the file is simply created
+    write_atomic(&path, b"mine", None, true).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "mine");
+}
+
+fn format_of(read: &FileContent) -> Option<ReadFormat> {
+    Some(ReadFormat {
+        encoding: read.encoding.clone(),
+        has_bom: read.has_bom,
+        truncated: read.truncated,
+        is_binary: read.is_binary,
+    })
+}
+
+#[tokio::test]
+async fn test_write_keeps_encoding_and_bom() {
+    let dir = tempdir().unwrap();
+    let utf16 = dir.path().join("utf16.txt");
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend("héllo".encode_utf16().flat_map(u16::to_le_bytes));
+    fs::write(&utf16, bytes).unwrap();
+    let latin = dir.path().join("latin.txt");
+    fs::write(&latin, b"caf\xe9 cr\xe8me").unwrap();
+
+    for path in [&utf16, &latin] {
+        let file_path = path.to_string_lossy().into_owned();
+        let read = read_file_content(file_path.clone()).await.unwrap();
+        let edited = read.content.to_uppercase();
+        write_file_content(
+            file_path.clone(),
+            edited.clone(),
+            Some(read.etag.clone()),
+            None,
+            format_of(&read),
+        )
+        .await
+        .unwrap();
+
+        let reread = read_file_content(file_path).await.unwrap();
+        assert_eq!(reread.content, edited);
+        assert_eq!(reread.encoding, read.encoding);
+        assert_eq!(reread.has_bom, read.has_bom);
+    }
+    assert_eq!(&fs::read(&utf16).unwrap()[..4], &[0xFF, 0xFE, b'H', 0]);
+    assert_eq!(fs::read(&latin).unwrap(), b"CAF\xc9 CR\xc8ME");
+}
+
+#[tokio::test]
+async fn test_write_refuses_what_cant_be_saved_back() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("data.txt");
+    fs::write(&path, "original").unwrap();
+    let file_path = path.to_string_lossy().into_owned();
+    let write = |content: &str, format: ReadFormat| {
+        write_file_content(
+            file_path.clone(),
+            content.to_string(),
+            None,
+            None,
+            Some(format),
+        )
+    };
+
+    let truncated = ReadFormat {
+        truncated: true,
+        ..Default::default()
+    };
+    let binary = ReadFormat {
+        is_binary: true,
+        ..Default::default()
+    };
+    let latin = ReadFormat {
+        encoding: Some("windows-1252".to_string()),
+        ..Default::default()
+    };
+    for (content, format) in [
+        ("partial", truncated),
+        ("00000000", binary),
+        ("日本", latin),
+    ] {
+        let err = write(content, format).await.unwrap_err();
+        assert!(matches!(err, AppError::InvalidArgument { .. }));
+    }
+    assert_eq!(fs::read_to_string(&path).unwrap(), "original");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_keeps_permissions_and_symlinks() {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("run.sh");
+    fs::write(&path, "echo one\n").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o754)).unwrap();
+    let link = dir.path().join("link.sh");
+    symlink(&path, &link).unwrap();
+
+    write_atomic(&link, b"echo two\n", None, false).unwrap();
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "echo two\n");
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o754);
+}
@@ -0,0 +1,149 @@
+use crate::commands::git_status::{invalidate_git_status, status_of};
+use crate::commands::tree::get_tree_data;
+use crate::models::pathinfo::GitStatus;
+use crate::models::tree_options::TreeOptions;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::tempdir;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// A repository with one committed file in each of `clean/`, `changed/` and `staged/`
+fn create_repo(root: &Path) {
+    git(root, &["init", "-q"]);
+    for folder in ["clean", "changed", "staged"] {
+        fs::create_dir(root.join(folder)).unwrap();
+        fs::write(root.join(folder).join("file.txt"), "original").unwrap();
+    }
+    fs::write(root.join(".gitignore"), "build/\n*.log\n").unwrap();
+    git(root, &["add", "."]);
+    git(root, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(root.join("changed/file.txt"), "edited").unwrap();
+    fs::write(root.join("staged/file.txt"), "edited").unwrap();
+    git(root, &["add", "staged/file.txt"]);
+    fs::write(root.join("new.txt"), "new").unwrap();
+    fs::write(root.join("debug.log"), "log").unwrap();
+    fs::create_dir_all(root.join("build/out")).unwrap();
+    fs::write(root.join("build/out/app"), "binary").unwrap();
+}
+
+#[test]
+fn test_file_statuses() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+    let root = dir.path();
+
+    assert_eq!(
+        status_of(&root.join("clean/file.txt")),
+        Some(GitStatus::Clean)
+    );
+    assert_eq!(
+        status_of(&root.join("changed/file.txt")),
+        Some(GitStatus::Modified)
+    );
+    assert_eq!(
+        status_of(&root.join("staged/file.txt")),
+        Some(GitStatus::Staged)
+    );
+    assert_eq!(status_of(&root.join("new.txt")), Some(GitStatus::Untracked));
+    assert_eq!(status_of(&root.join("debug.log")), Some(GitStatus::Ignored));
+    assert_eq!(status_of(&root.join(".git")), None);
+}
+
+#[test]
+fn test_folders_roll_up_and_ignored_folders_cover_contents() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+    let root = dir.path();
+
+    assert_eq!(status_of(&root.join("clean")), Some(GitStatus::Clean));
+    assert_eq!(status_of(&root.join("changed")), Some(GitStatus::Modified));
+    assert_eq!(status_of(&root.join("staged")), Some(GitStatus::Staged));
+    assert_eq!(status_of(&root.join("build")), Some(GitStatus::Ignored));
+    assert_eq!(
+        status_of(&root.join("build/out/app")),
+        Some(GitStatus::Ignored)
+    );
+}
+
+#[test]
+fn test_outside_a_repository_has_no_status() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("plain.txt"), "text").unwrap();
+
+    assert_eq!(status_of(&dir.path().join("plain.txt")), None);
+}
+
+#[test]
+fn test_listing_carries_status_only_when_asked() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+    let root = dir.path().to_string_lossy().to_string();
+
+    let nodes = get_tree_data(root.clone(), false, None, None, None).unwrap();
+    assert!(nodes.iter().all(|n| n.git_status.is_none()));
+
+    let options = TreeOptions {
+        git_status: true,
+        ..Default::default()
+    };
+    let nodes = get_tree_data(root, false, None, None, Some(options)).unwrap();
+    let new_file = nodes.iter().find(|n| n.name == "new.txt").unwrap();
+    assert_eq!(new_file.git_status, Some(GitStatus::Untracked));
+}
+
+#[test]
+fn test_status_refreshes_when_index_changes_or_after_invalidation() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+    assert_eq!(
+        status_of(&dir.path().join("new.txt")),
+        Some(GitStatus::Untracked)
+    );
+
+    git(dir.path(), &["add", "new.txt"]);
+    assert_eq!(
+        status_of(&dir.path().join("new.txt")),
+        Some(GitStatus::Staged)
+    );
+
+    fs::write(dir.path().join("clean/file.txt"), "edited").unwrap();
+    // Edits leave the index alone, so the cached answer holds until the watcher
+    // reports the change
+    assert_eq!(
+        status_of(&dir.path().join("clean/file.txt")),
+        Some(GitStatus::Clean)
+    );
+    let refreshed = invalidate_git_status(&[dir.path().join("clean/file.txt")]);
+    assert_eq!(refreshed.len(), 1);
+    assert_eq!(
+        status_of(&dir.path().join("clean/file.txt")),
+        Some(GitStatus::Modified)
+    );
+}
+
+#[test]
+fn test_only_index_and_head_changes_in_git_folder_invalidate() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+    let git_dir = dir.path().join(".git");
+    assert_eq!(
+        status_of(&dir.path().join("new.txt")),
+        Some(GitStatus::Untracked)
+    );
+
+    assert!(invalidate_git_status(&[git_dir.join("objects/ab/cdef")]).is_empty());
+    assert!(invalidate_git_status(&[git_dir.join("index.lock")]).is_empty());
+    assert_eq!(invalidate_git_status(&[git_dir.join("HEAD")]).len(), 1);
+}
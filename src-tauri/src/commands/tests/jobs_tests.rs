@@ -0,0 +1,215 @@
+use crate::commands::jobs::{paths_overlap, JobEvents, JobManager};
+use crate::models::job::{JobInfo, JobRequest, JobState};
+use crate::models::transfer::CopyConflict;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::tempdir;
+
+#[derive(Default)]
+struct Recorder {
+    states: Mutex<Vec<JobState>>,
+}
+
+impl JobEvents for Recorder {
+    fn progress(&self, job: &JobInfo) {
+        let mut states = self.states.lock().unwrap();
+        if states.last() != Some(&job.state) {
+            states.push(job.state);
+        }
+    }
+
+    fn conflict(&self, _conflict: &CopyConflict) {}
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+fn delete_request(path: &Path) -> JobRequest {
+    JobRequest::Delete {
+        paths: vec![path_string(path)],
+        permanent: Some(true),
+    }
+}
+
+fn job(manager: &JobManager, job_id: &str) -> JobInfo {
+    manager
+        .list()
+        .unwrap()
+        .into_iter()
+        .find(|job| job.job_id == job_id)
+        .unwrap()
+}
+
+async fn wait_until_finished(manager: &JobManager, job_id: &str) -> JobInfo {
+    for _ in 0..500 {
+        let info = job(manager, job_id);
+        if info.state.is_finished() {
+            return info;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("job {} didn't finish", job_id);
+}
+
+#[tokio::test]
+async fn test_move_job() {
+    let dir = tempdir().unwrap();
+    let target = dir.path().join("target");
+    fs::create_dir(&target).unwrap();
+    fs::write(dir.path().join("a.txt"), "a").unwrap();
+    fs::write(dir.path().join("b.txt"), "b").unwrap();
+    let manager = Arc::new(JobManager::new(2));
+    let recorder = Arc::new(Recorder::default());
+
+    let job_id = manager
+        .submit(
+            JobRequest::Move {
+                sources: vec![
+                    path_string(&dir.path().join("a.txt")),
+                    path_string(&dir.path().join("missing.txt")),
+                    path_string(&dir.path().join("b.txt")),
+                ],
+                destination: path_string(&target),
+                conflict: None,
+            },
+            recorder.clone(),
+        )
+        .unwrap();
+    let info = wait_until_finished(&manager, &job_id).await;
+
+    assert_eq!(info.state, JobState::Completed);
+    assert_eq!(info.items_done, 3);
+    assert_eq!(info.errors.len(), 1);
+    assert!(target.join("a.txt").exists());
+    assert!(target.join("b.txt").exists());
+    assert_eq!(
+        *recorder.states.lock().unwrap(),
+        vec![JobState::Queued, JobState::Running, JobState::Completed]
+    );
+}
+
+#[tokio::test]
+async fn test_permanent_delete_job_counts_entries() {
+    let dir = tempdir().unwrap();
+    let tree = dir.path().join("tree");
+    fs::create_dir_all(tree.join("a/b")).unwrap();
+    fs::write(tree.join("a/b/file.txt"), "").unwrap();
+    fs::write(tree.join("top.txt"), "").unwrap();
+    let manager = Arc::new(JobManager::new(2));
+
+    let job_id = manager
+        .submit(delete_request(&tree), Arc::new(Recorder::default()))
+        .unwrap();
+    let info = wait_until_finished(&manager, &job_id).await;
+
+    assert_eq!(info.state, JobState::Completed);
+    assert_eq!(info.items_total, 5);
+    assert_eq!(info.items_done, 5);
+    assert!(!tree.exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_delete_job_removes_symlinked_folder_as_link() {
+    let dir = tempdir().unwrap();
+    let target = dir.path().join("target");
+    fs::create_dir(&target).unwrap();
+    fs::write(target.join("kept.txt"), "").unwrap();
+    let link = dir.path().join("link");
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+    let manager = Arc::new(JobManager::new(2));
+
+    let job_id = manager
+        .submit(delete_request(&link), Arc::new(Recorder::default()))
+        .unwrap();
+    let info = wait_until_finished(&manager, &job_id).await;
+
+    assert_eq!(info.state, JobState::Completed);
+    assert_eq!(info.items_total, 1);
+    assert!(fs::symlink_metadata(&link).is_err());
+    assert!(target.join("kept.txt").exists());
+}
+
+#[tokio::test]
+async fn test_overlapping_jobs_wait_for_each_other() {
+    let dir = tempdir().unwrap();
+    let shared = dir.path().join("shared");
+    let other = dir.path().join("other");
+    fs::create_dir_all(shared.join("inner")).unwrap();
+    fs::create_dir(&other).unwrap();
+    let manager = Arc::new(JobManager::new(2));
+    let events = Arc::new(Recorder::default());
+
+    // The test runtime is single-threaded, so the first job can't start before the pause
+    let first = manager
+        .submit(delete_request(&shared), events.clone())
+        .unwrap();
+    manager.pause(&first).unwrap();
+    let second = manager
+        .submit(delete_request(&shared.join("inner")), events.clone())
+        .unwrap();
+    let unrelated = manager.submit(delete_request(&other), events).unwrap();
+
+    assert_eq!(
+        wait_until_finished(&manager, &unrelated).await.state,
+        JobState::Completed
+    );
+    assert_eq!(job(&manager, &first).state, JobState::Paused);
+    assert_eq!(job(&manager, &second).state, JobState::Queued);
+
+    manager.cancel(&first).unwrap();
+    assert_eq!(
+        wait_until_finished(&manager, &first).await.state,
+        JobState::Cancelled
+    );
+    assert_eq!(
+        wait_until_finished(&manager, &second).await.state,
+        JobState::Completed
+    );
+    assert!(shared.exists());
+    assert!(!shared.join("inner").exists());
+    assert!(manager.pause(&first).is_err());
+}
+
+#[tokio::test]
+async fn test_paused_job_leaves_device_slot_to_others() {
+    let dir = tempdir().unwrap();
+    let paused = dir.path().join("paused");
+    let other = dir.path().join("other");
+    fs::create_dir(&paused).unwrap();
+    fs::create_dir(&other).unwrap();
+    // One job at a time on the device
+    let manager = Arc::new(JobManager::new(1));
+    let events = Arc::new(Recorder::default());
+
+    let first = manager
+        .submit(delete_request(&paused), events.clone())
+        .unwrap();
+    manager.pause(&first).unwrap();
+    let second = manager.submit(delete_request(&other), events).unwrap();
+
+    assert_eq!(
+        wait_until_finished(&manager, &second).await.state,
+        JobState::Completed
+    );
+    assert_eq!(job(&manager, &first).state, JobState::Paused);
+
+    manager.resume(&first).unwrap();
+    assert_eq!(
+        wait_until_finished(&manager, &first).await.state,
+        JobState::Completed
+    );
+    assert!(!paused.exists());
+}
+
+#[test]
+fn test_paths_overlap() {
+    let paths = |list: &[&str]| list.iter().map(PathBuf::from).collect::<Vec<_>>();
+
+    assert!(paths_overlap(&paths(&["/a/b"]), &paths(&["/a"])));
+    assert!(paths_overlap(&paths(&["/x", "/a"]), &paths(&["/a/b/c"])));
+    assert!(!paths_overlap(&paths(&["/a/b"]), &paths(&["/a/bc"])));
+}
@@ -0,0 +1,125 @@
+use crate::commands::filesystem_ops::create_item;
+use crate::commands::journal::{redo, undo, Journal, Step};
+use crate::commands::move_ops::copy_tree;
+use crate::models::error::AppError;
+use crate::models::journal::OperationKind;
+use std::fs;
+use std::sync::Mutex;
+use tempfile::tempdir;
+
+#[test]
+fn test_undo_and_redo_create() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("new.txt");
+    let journal = Mutex::new(Journal::default());
+    create_item(&path, Some(b"notes")).unwrap();
+    journal
+        .lock()
+        .unwrap()
+        .record(OperationKind::Create, vec![Step::created(&path)]);
+
+    let summary = undo(&journal).unwrap();
+    assert_eq!(summary.kind, OperationKind::Create);
+    assert!(!path.exists());
+    assert_eq!(journal.lock().unwrap().state().redo, Some(summary));
+
+    redo(&journal).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "notes");
+    assert!(journal.lock().unwrap().state().redo.is_none());
+}
+
+#[test]
+fn test_undo_refuses_after_a_change() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("new.txt");
+    let journal = Mutex::new(Journal::default());
+    create_item(&path, Some(b"notes")).unwrap();
+    journal
+        .lock()
+        .unwrap()
+        .record(OperationKind::Create, vec![Step::created(&path)]);
+
+    fs::write(&path, "work that must not be lost").unwrap();
+
+    let err = undo(&journal).unwrap_err();
+    assert!(matches!(err, AppError::Changed { .. }));
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "work that must not be lost"
+    );
+    assert!(journal.lock().unwrap().state().undo.is_some());
+}
+
+#[test]
+fn test_undo_move_of_several_items() {
+    let dir = tempdir().unwrap();
+    let target = dir.path().join("target");
+    fs::create_dir(&target).unwrap();
+    let journal = Mutex::new(Journal::default());
+    let mut steps = Vec::new();
+    for name in ["a.txt", "b.txt"] {
+        fs::write(dir.path().join(name), name).unwrap();
+        fs::rename(dir.path().join(name), target.join(name)).unwrap();
+        steps.push(Step::moved(&dir.path().join(name), &target.join(name)));
+    }
+    journal.lock().unwrap().record(OperationKind::Move, steps);
+
+    undo(&journal).unwrap();
+    assert!(dir.path().join("a.txt").is_file());
+    assert!(dir.path().join("b.txt").is_file());
+    assert_eq!(fs::read_dir(&target).unwrap().count(), 0);
+
+    // Something new where an item would go back blocks the whole redo
+    fs::write(target.join("b.txt"), "other").unwrap();
+    let err = redo(&journal).unwrap_err();
+    assert!(matches!(err, AppError::AlreadyExists { .. }));
+    assert!(dir.path().join("a.txt").is_file());
+}
+
+#[test]
+fn test_copy_undo_checks_the_whole_tree() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("source");
+    let copy = dir.path().join("copy");
+    fs::create_dir_all(source.join("nested")).unwrap();
+    fs::write(source.join("nested/file.txt"), "content").unwrap();
+    copy_tree(&source, &copy).unwrap();
+
+    let journal = Mutex::new(Journal::default());
+    journal
+        .lock()
+        .unwrap()
+        .record(OperationKind::Copy, vec![Step::copied(&source, &copy)]);
+    fs::write(copy.join("nested/file.txt"), "edited in the copy").unwrap();
+
+    let err = undo(&journal).unwrap_err();
+    assert!(matches!(err, AppError::Changed { .. }));
+    assert!(copy.join("nested/file.txt").exists());
+}
+
+#[test]
+fn test_new_operation_clears_redo() {
+    let dir = tempdir().unwrap();
+    let journal = Mutex::new(Journal::default());
+    for name in ["a", "b"] {
+        let path = dir.path().join(name);
+        create_item(&path, None).unwrap();
+        journal
+            .lock()
+            .unwrap()
+            .record(OperationKind::Create, vec![Step::created(&path)]);
+    }
+
+    undo(&journal).unwrap();
+    assert!(journal.lock().unwrap().state().redo.is_some());
+    let path = dir.path().join("c");
+    create_item(&path, None).unwrap();
+    journal
+        .lock()
+        .unwrap()
+        .record(OperationKind::Create, vec![Step::created(&path)]);
+
+    assert!(journal.lock().unwrap().state().redo.is_none());
+    let err = redo(&journal).unwrap_err();
+    assert!(matches!(err, AppError::InvalidArgument { .. }));
+}
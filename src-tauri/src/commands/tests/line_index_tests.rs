@@ -0,0 +1,82 @@
+use crate::commands::constants::{LINE_INDEX_STRIDE, MAX_PAGE_LINE_LEN};
+use crate::commands::line_index::LineIndex;
+use crate::models::error::AppError;
+use std::fs;
+use std::io::Write;
+use tempfile::tempdir;
+
+#[test]
+fn test_pages_across_checkpoints() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("big.log");
+    let total = LINE_INDEX_STRIDE * 3 + 17;
+    let mut file = fs::File::create(&path).unwrap();
+    for line in 0..total {
+        write!(file, "line {}\r\n", line).unwrap();
+    }
+    // The last line has no line break
+    write!(file, "tail").unwrap();
+    drop(file);
+
+    let index = LineIndex::new(&path).unwrap();
+    assert_eq!(index.read(0, 10).unwrap().lines.len(), 0);
+    let mut reports = Vec::new();
+    index.build(|status| reports.push(status)).unwrap();
+    assert_eq!(reports.last().unwrap().total_lines, Some(total + 1));
+
+    let start = LINE_INDEX_STRIDE * 2 - 3;
+    let page = index.read(start, 5).unwrap();
+    let expected: Vec<String> = (start..start + 5).map(|n| format!("line {}", n)).collect();
+    assert_eq!(page.lines, expected);
+    assert_eq!(page.total_lines, Some(total + 1));
+
+    let page = index.read(total - 1, 100).unwrap();
+    assert_eq!(
+        page.lines,
+        vec![format!("line {}", total - 1), "tail".to_string()]
+    );
+    assert!(index.read(total + 1, 10).unwrap().lines.is_empty());
+}
+
+#[test]
+fn test_long_lines_are_clipped() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("min.js");
+    let long = "x".repeat(MAX_PAGE_LINE_LEN * 2);
+    fs::write(&path, format!("{}\nshort\n", long)).unwrap();
+
+    let index = LineIndex::new(&path).unwrap();
+    index.build(|_| {}).unwrap();
+    let page = index.read(0, 2).unwrap();
+    assert!(page.clipped);
+    assert_eq!(page.lines[0].len(), MAX_PAGE_LINE_LEN);
+    assert_eq!(page.lines[1], "short");
+    assert_eq!(page.total_lines, Some(2));
+}
+
+#[test]
+fn test_legacy_encodings_and_unsupported_files() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("latin.txt");
+    let (bytes, _, _) = encoding_rs::WINDOWS_1252
+        .encode("première ligne, déjà écrite\nseconde ligne, très longue à lire\n");
+    fs::write(&path, &bytes).unwrap();
+    let index = LineIndex::new(&path).unwrap();
+    index.build(|_| {}).unwrap();
+    assert_eq!(
+        index.read(1, 1).unwrap().lines,
+        vec!["seconde ligne, très longue à lire"]
+    );
+
+    let path = dir.path().join("wide.txt");
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend("one\ntwo\n".encode_utf16().flat_map(u16::to_le_bytes));
+    fs::write(&path, &bytes).unwrap();
+    let err = LineIndex::new(&path).err().unwrap();
+    assert!(matches!(err, AppError::Unsupported { .. }));
+
+    let path = dir.path().join("blob.bin");
+    fs::write(&path, [0u8, 1, 2, 3, 0, 5]).unwrap();
+    let err = LineIndex::new(&path).err().unwrap();
+    assert!(matches!(err, AppError::InvalidArgument { .. }));
+}
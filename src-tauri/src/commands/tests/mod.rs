@@ -0,0 +1,26 @@
+pub mod archive_fs_tests;
+pub mod archive_ops_tests;
+pub mod bulk_rename_tests;
+pub mod content_type_tests;
+pub mod copy_ops_tests;
+pub mod disk_usage_tests;
+pub mod file_read_tests;
+pub mod file_templates_tests;
+pub mod file_write_tests;
+pub mod git_status_tests;
+pub mod jobs_tests;
+pub mod journal_tests;
+pub mod line_index_tests;
+pub mod move_ops_tests;
+pub mod sandbox_tests;
+#[cfg(all(unix, not(target_os = "macos")))]
+pub mod trash_tests;
+pub mod tree_filter_tests;
+pub mod tree_ignore_tests;
+pub mod tree_page_tests;
+pub mod tree_stream_tests;
+#[cfg(unix)]
+pub mod tree_symlink_tests;
+pub mod tree_tests;
+#[cfg(unix)]
+pub mod tree_unix_metadata_tests;
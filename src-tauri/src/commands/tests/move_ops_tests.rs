@@ -0,0 +1,211 @@
+use crate::commands::move_ops::{
+    available_name, copy_then_delete, journal_step, move_items, rename_item,
+};
+use crate::models::error::AppError;
+use crate::models::transfer::{ConflictPolicy, MoveOutcome};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[tokio::test]
+async fn test_rename_file() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("old.txt"), "content").unwrap();
+
+    let result = rename_item(
+        path_string(&dir.path().join("old.txt")),
+        "new.txt".to_string(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.outcome, MoveOutcome::Moved);
+    assert_eq!(result.destination, path_string(&dir.path().join("new.txt")));
+    assert!(!dir.path().join("old.txt").exists());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("new.txt")).unwrap(),
+        "content"
+    );
+}
+
+#[tokio::test]
+async fn test_rename_rejects_paths_as_names() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("file.txt"), "").unwrap();
+    let source = path_string(&dir.path().join("file.txt"));
+
+    for name in ["../escape.txt", "sub/file.txt", "..", ""] {
+        let err = rename_item(source.clone(), name.to_string(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::InvalidArgument { .. }), "{}", name);
+    }
+}
+
+#[tokio::test]
+async fn test_conflict_policies() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("a.txt");
+    let target = dir.path().join("b.txt");
+    fs::write(&target, "existing").unwrap();
+
+    fs::write(&source, "incoming").unwrap();
+    let err = rename_item(path_string(&source), "b.txt".to_string(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::AlreadyExists { .. }));
+
+    let skipped = rename_item(
+        path_string(&source),
+        "b.txt".to_string(),
+        Some(ConflictPolicy::Skip),
+    )
+    .await
+    .unwrap();
+    assert_eq!(skipped.outcome, MoveOutcome::Skipped);
+    assert!(source.exists());
+
+    let kept = rename_item(
+        path_string(&source),
+        "b.txt".to_string(),
+        Some(ConflictPolicy::KeepBoth),
+    )
+    .await
+    .unwrap();
+    assert_eq!(kept.destination, path_string(&dir.path().join("b (1).txt")));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "existing");
+
+    fs::write(&source, "replacement").unwrap();
+    rename_item(
+        path_string(&source),
+        "b.txt".to_string(),
+        Some(ConflictPolicy::Overwrite),
+    )
+    .await
+    .unwrap();
+    assert_eq!(fs::read_to_string(&target).unwrap(), "replacement");
+    // Nothing is left over from setting the old file aside
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+}
+
+#[tokio::test]
+async fn test_overwrite_replaces_folder() {
+    let dir = tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("src/new")).unwrap();
+    fs::create_dir_all(dir.path().join("dest/src/old")).unwrap();
+
+    let results = move_items(
+        vec![path_string(&dir.path().join("src"))],
+        path_string(&dir.path().join("dest")),
+        Some(ConflictPolicy::Overwrite),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(results[0].outcome, MoveOutcome::Replaced);
+    assert!(dir.path().join("dest/src/new").is_dir());
+    assert!(!dir.path().join("dest/src/old").exists());
+    // Undo couldn't bring the old folder back, so the move isn't undoable
+    assert!(journal_step(&results[0]).is_none());
+}
+
+#[tokio::test]
+async fn test_move_items_reports_each_item() {
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("dest");
+    fs::create_dir(&dest).unwrap();
+    fs::write(dir.path().join("one.txt"), "1").unwrap();
+    fs::write(dir.path().join("two.txt"), "2").unwrap();
+    fs::write(dest.join("two.txt"), "existing").unwrap();
+
+    let results = move_items(
+        vec![
+            path_string(&dir.path().join("one.txt")),
+            path_string(&dir.path().join("two.txt")),
+            path_string(&dir.path().join("missing.txt")),
+        ],
+        path_string(&dest),
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(results[0].outcome, MoveOutcome::Moved);
+    assert_eq!(results[1].outcome, MoveOutcome::Failed);
+    assert!(matches!(
+        results[1].error,
+        Some(AppError::AlreadyExists { .. })
+    ));
+    assert!(matches!(results[2].error, Some(AppError::NotFound { .. })));
+    assert!(dest.join("one.txt").exists());
+    assert!(dir.path().join("two.txt").exists());
+}
+
+#[tokio::test]
+async fn test_folder_cannot_move_into_itself() {
+    let dir = tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("parent/child")).unwrap();
+
+    let results = move_items(
+        vec![path_string(&dir.path().join("parent"))],
+        path_string(&dir.path().join("parent/child")),
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert!(matches!(
+        results[0].error,
+        Some(AppError::InvalidArgument { .. })
+    ));
+}
+
+#[test]
+fn test_copy_then_delete_moves_tree() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("source");
+    fs::create_dir_all(source.join("nested")).unwrap();
+    fs::write(source.join("nested/file.txt"), "data").unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("nested/file.txt", source.join("link")).unwrap();
+
+    copy_then_delete(&source, &dir.path().join("target")).unwrap();
+
+    let target = dir.path().join("target");
+    assert!(!source.exists());
+    assert_eq!(
+        fs::read_to_string(target.join("nested/file.txt")).unwrap(),
+        "data"
+    );
+    #[cfg(unix)]
+    assert_eq!(
+        fs::read_link(target.join("link")).unwrap(),
+        Path::new("nested/file.txt")
+    );
+}
+
+#[test]
+fn test_available_name_suffixes() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("report.txt"), "").unwrap();
+    fs::write(dir.path().join("report (1).txt"), "").unwrap();
+    fs::write(dir.path().join(".env"), "").unwrap();
+
+    assert_eq!(
+        available_name(&dir.path().join("report.txt"), false),
+        dir.path().join("report (2).txt")
+    );
+    assert_eq!(
+        available_name(&dir.path().join(".env"), false),
+        dir.path().join(".env (1)")
+    );
+    assert_eq!(
+        available_name(&dir.path().join("v1.2"), true),
+        dir.path().join("v1.2 (1)")
+    );
+}
@@ -0,0 +1,108 @@
+use crate::commands::sandbox::Sandbox;
+use crate::models::error::AppError;
+use std::env;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn limited_to(roots: &[&Path]) -> Sandbox {
+    Sandbox::new(Some(env::join_paths(roots).unwrap()))
+}
+
+fn denied(sandbox: &Sandbox, path: &Path, follow_last: bool) -> bool {
+    matches!(
+        sandbox.check(path, follow_last),
+        Err(AppError::PermissionDenied { .. })
+    )
+}
+
+#[test]
+fn test_paths_inside_roots_are_allowed() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("root");
+    let other = dir.path().join("other");
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir(&other).unwrap();
+    let sandbox = limited_to(&[&root]);
+
+    assert!(sandbox.check(&root, true).is_ok());
+    assert!(sandbox.check(&root.join("src"), true).is_ok());
+    // Items about to be created are judged by their folder
+    assert!(sandbox.check(&root.join("src/new/file.txt"), true).is_ok());
+    assert!(denied(&sandbox, &other, true));
+    assert!(denied(&sandbox, dir.path(), true));
+    assert!(denied(&sandbox, Path::new("src"), true));
+}
+
+#[test]
+fn test_parent_components_are_rejected() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("root");
+    fs::create_dir_all(root.join("src")).unwrap();
+    let sandbox = limited_to(&[&root]);
+
+    assert!(denied(&sandbox, &root.join("src/../../etc"), true));
+    // Even when it would lead back inside
+    assert!(denied(&sandbox, &root.join("src/../src"), true));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinks_out_of_roots_are_rejected() {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("root");
+    let outside = dir.path().join("outside");
+    fs::create_dir(&root).unwrap();
+    fs::create_dir(&outside).unwrap();
+    fs::write(outside.join("secret.txt"), "secret").unwrap();
+    symlink(&outside, root.join("escape")).unwrap();
+    symlink(outside.join("later.txt"), root.join("dangling")).unwrap();
+    let sandbox = limited_to(&[&root]);
+
+    assert!(denied(&sandbox, &root.join("escape/secret.txt"), true));
+    assert!(denied(&sandbox, &root.join("escape"), true));
+    // Writing through a dangling link would create the file outside
+    assert!(denied(&sandbox, &root.join("dangling"), true));
+    // The links themselves can still be renamed or deleted
+    assert!(sandbox.check(&root.join("escape"), false).is_ok());
+    assert!(sandbox.check(&root.join("dangling"), false).is_ok());
+    assert!(denied(&sandbox, &root.join("escape/secret.txt"), false));
+}
+
+#[test]
+fn test_several_roots_can_be_allowed() {
+    let dir = tempdir().unwrap();
+    let first = dir.path().join("first");
+    let second = dir.path().join("second");
+    fs::create_dir(&first).unwrap();
+    fs::create_dir(&second).unwrap();
+
+    let sandbox = limited_to(&[&first, &second]);
+    assert!(sandbox.check(&first.join("a"), true).is_ok());
+    assert!(sandbox.check(&second.join("b"), true).is_ok());
+
+    // A root that doesn't exist allows nothing rather than everything
+    let missing = limited_to(&[&dir.path().join("missing")]);
+    assert!(denied(&missing, &first, true));
+    // Without any roots configured there is no restriction
+    assert!(Sandbox::new(None).check(&first, true).is_ok());
+}
+
+#[test]
+fn test_archive_members_follow_their_archive() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("root");
+    let outside = dir.path().join("outside");
+    fs::create_dir(&root).unwrap();
+    fs::create_dir(&outside).unwrap();
+    fs::write(root.join("inside.zip"), "PK").unwrap();
+    fs::write(outside.join("outside.zip"), "PK").unwrap();
+    let sandbox = limited_to(&[&root]);
+
+    let member = format!("{}!/docs/readme.md", root.join("inside.zip").display());
+    assert!(sandbox.check(Path::new(&member), true).is_ok());
+    let member = format!("{}!/docs/readme.md", outside.join("outside.zip").display());
+    assert!(denied(&sandbox, Path::new(&member), true));
+}
@@ -0,0 +1,151 @@
+use crate::commands::filesystem_ops::{delete_file, delete_folder};
+use crate::models::error::AppError;
+use crate::models::transfer::ConflictPolicy;
+use crate::platforms::trash::Trash;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_trash_writes_trashinfo() {
+    let dir = tempdir().unwrap();
+    let home = dir.path().join("Trash");
+    let source = dir.path().join("my notes%.txt");
+    fs::write(&source, "content").unwrap();
+
+    let item = Trash::new(&home).trash(&source).unwrap();
+
+    assert!(!source.exists());
+    assert_eq!(
+        fs::read_to_string(home.join("files/my notes%.txt")).unwrap(),
+        "content"
+    );
+    assert_eq!(
+        item.id,
+        path_string(&home.join("info/my notes%.txt.trashinfo"))
+    );
+    assert_eq!(item.name, "my notes%.txt");
+    assert_eq!(item.size, Some(7));
+
+    let info = fs::read_to_string(home.join("info/my notes%.txt.trashinfo")).unwrap();
+    let mut lines = info.lines();
+    assert_eq!(lines.next(), Some("[Trash Info]"));
+    let canonical = fs::canonicalize(dir.path()).unwrap();
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("Path={}/my%20notes%25.txt", path_string(&canonical))
+    );
+    let date = lines.next().unwrap().strip_prefix("DeletionDate=").unwrap();
+    assert_eq!(date.len(), "2025-01-31T23:59:59".len());
+    assert_eq!(&date[10..11], "T");
+}
+
+#[test]
+fn test_trash_keeps_both_items_with_the_same_name() {
+    let dir = tempdir().unwrap();
+    let trash = Trash::new(dir.path().join("Trash"));
+    let source = dir.path().join("a.txt");
+
+    fs::write(&source, "first").unwrap();
+    trash.trash(&source).unwrap();
+    fs::write(&source, "second").unwrap();
+    let second = trash.trash(&source).unwrap();
+
+    assert!(second.id.ends_with("a (1).txt.trashinfo"));
+    let mut items = trash.list();
+    items.sort_by(|a, b| a.id.cmp(&b.id));
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|item| item.original_path
+        == path_string(&fs::canonicalize(dir.path()).unwrap().join("a.txt"))));
+}
+
+#[test]
+fn test_restore_recreates_parent_and_honours_conflicts() {
+    let dir = tempdir().unwrap();
+    let trash = Trash::new(dir.path().join("Trash"));
+    let folder = dir.path().join("project");
+    fs::create_dir_all(folder.join("src")).unwrap();
+    fs::write(folder.join("src/main.rs"), "fn main() {}").unwrap();
+
+    let item = trash.trash(&folder.join("src")).unwrap();
+    assert!(item.is_dir);
+    fs::remove_dir_all(&folder).unwrap();
+
+    let (_, original, restored) = trash
+        .restore(Path::new(&item.id), ConflictPolicy::Fail)
+        .unwrap();
+    assert_eq!(restored, Some(original.clone()));
+    assert!(original.join("main.rs").is_file());
+    assert!(!Path::new(&item.id).exists());
+    assert!(trash.list().is_empty());
+
+    // Something new in the original place is kept unless the policy says otherwise
+    let item = trash.trash(&original).unwrap();
+    fs::create_dir(&original).unwrap();
+    let err = trash
+        .restore(Path::new(&item.id), ConflictPolicy::Fail)
+        .unwrap_err();
+    assert!(matches!(err, AppError::AlreadyExists { .. }));
+    let (_, _, restored) = trash
+        .restore(Path::new(&item.id), ConflictPolicy::KeepBoth)
+        .unwrap();
+    assert_eq!(restored, Some(folder.join("src (1)")));
+}
+
+#[test]
+fn test_ids_outside_the_trash_are_rejected() {
+    let dir = tempdir().unwrap();
+    let trash = Trash::new(dir.path().join("Trash"));
+    let stray = dir.path().join("info/stray.trashinfo");
+    fs::create_dir_all(stray.parent().unwrap()).unwrap();
+    fs::write(&stray, "[Trash Info]\nPath=/etc/passwd\n").unwrap();
+
+    let err = trash.erase(&stray).unwrap_err();
+    assert!(matches!(err, AppError::InvalidArgument { .. }));
+    let err = trash
+        .restore(&dir.path().join("file.txt"), ConflictPolicy::Fail)
+        .unwrap_err();
+    assert!(matches!(err, AppError::InvalidArgument { .. }));
+}
+
+#[test]
+fn test_erase_and_empty() {
+    let dir = tempdir().unwrap();
+    let home = dir.path().join("Trash");
+    let trash = Trash::new(&home);
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(dir.path().join(name), name).unwrap();
+        trash.trash(&dir.path().join(name)).unwrap();
+    }
+    fs::write(home.join("directorysizes"), "4096 1700000000 folder\n").unwrap();
+
+    trash.erase(&home.join("info/a.txt.trashinfo")).unwrap();
+    assert!(!home.join("files/a.txt").exists());
+    assert_eq!(trash.list().len(), 2);
+
+    assert_eq!(trash.empty().unwrap(), 2);
+    assert!(trash.list().is_empty());
+    assert_eq!(fs::read_dir(home.join("files")).unwrap().count(), 0);
+    assert!(!home.join("directorysizes").exists());
+}
+
+#[tokio::test]
+async fn test_permanent_delete() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("file.txt");
+    let folder = dir.path().join("folder");
+    fs::write(&file, "").unwrap();
+    fs::create_dir(&folder).unwrap();
+
+    delete_file(path_string(&file), Some(true)).await.unwrap();
+    delete_folder(path_string(&folder), Some(true))
+        .await
+        .unwrap();
+
+    assert!(!file.exists());
+    assert!(!folder.exists());
+}
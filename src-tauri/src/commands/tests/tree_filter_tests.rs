@@ -0,0 +1,145 @@
+use crate::commands::tree::get_tree_data;
+use crate::commands::tree_filter::natural_cmp;
+use crate::models::error::AppError;
+use crate::models::tree_options::TreeOptions;
+use std::cmp::Ordering;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn options(json: &str) -> Option<TreeOptions> {
+    Some(serde_json::from_str(json).unwrap())
+}
+
+fn names_in(dir: &Path, options: Option<TreeOptions>) -> Vec<String> {
+    get_tree_data(
+        dir.to_string_lossy().to_string(),
+        false,
+        None,
+        None,
+        options,
+    )
+    .unwrap()
+    .into_iter()
+    .map(|n| n.name)
+    .collect()
+}
+
+#[test]
+fn test_natural_ordering() {
+    assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+    assert_eq!(natural_cmp("File10", "file9"), Ordering::Greater);
+    assert_eq!(natural_cmp("a", "B"), Ordering::Less);
+    assert_eq!(natural_cmp("img007", "img7"), Ordering::Less);
+    assert_eq!(natural_cmp("same", "same"), Ordering::Equal);
+}
+
+#[test]
+fn test_sort_by_name_with_folders_first() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("file10.txt"), "").unwrap();
+    fs::write(dir.path().join("file2.txt"), "").unwrap();
+    fs::create_dir(dir.path().join("zeta")).unwrap();
+
+    let names = names_in(
+        dir.path(),
+        options(r#"{ "sort_by": "Name", "folders_first": true }"#),
+    );
+
+    assert_eq!(names, vec!["zeta", "file2.txt", "file10.txt"]);
+}
+
+#[test]
+fn test_descending_keeps_folders_first() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "").unwrap();
+    fs::write(dir.path().join("b.txt"), "").unwrap();
+    fs::create_dir(dir.path().join("folder")).unwrap();
+
+    let names = names_in(
+        dir.path(),
+        options(r#"{ "sort_by": "Name", "sort_direction": "Descending", "folders_first": true }"#),
+    );
+
+    assert_eq!(names, vec!["folder", "b.txt", "a.txt"]);
+}
+
+#[test]
+fn test_sort_by_extension() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("b.md"), "").unwrap();
+    fs::write(dir.path().join("a.rs"), "").unwrap();
+    fs::write(dir.path().join("c.json"), "").unwrap();
+
+    let names = names_in(dir.path(), options(r#"{ "sort_by": "Extension" }"#));
+
+    assert_eq!(names, vec!["c.json", "b.md", "a.rs"]);
+}
+
+#[test]
+fn test_hidden_entries_can_be_hidden() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".env"), "").unwrap();
+    fs::write(dir.path().join("visible.txt"), "").unwrap();
+
+    assert_eq!(names_in(dir.path(), None).len(), 2);
+    assert_eq!(
+        names_in(dir.path(), options(r#"{ "show_hidden": false }"#)),
+        vec!["visible.txt"]
+    );
+}
+
+#[test]
+fn test_include_and_exclude_globs() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("main.rs"), "").unwrap();
+    fs::write(dir.path().join("lib.rs"), "").unwrap();
+    fs::write(dir.path().join("notes.md"), "").unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+    fs::create_dir(dir.path().join("target")).unwrap();
+
+    let names = names_in(
+        dir.path(),
+        options(r#"{ "sort_by": "Name", "include": ["*.rs"], "exclude": ["lib.*", "target"] }"#),
+    );
+
+    // Folders survive include patterns so they can still be browsed
+    assert_eq!(names, vec!["main.rs", "src"]);
+}
+
+#[test]
+fn test_excluded_folders_are_not_descended() {
+    let dir = tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("node_modules/pkg")).unwrap();
+    fs::write(dir.path().join("node_modules/pkg/index.js"), "").unwrap();
+    fs::write(dir.path().join("index.js"), "").unwrap();
+
+    let nodes = get_tree_data(
+        dir.path().to_string_lossy().to_string(),
+        true,
+        None,
+        None,
+        options(r#"{ "exclude": ["node_modules"] }"#),
+    )
+    .unwrap();
+
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].name, "index.js");
+}
+
+#[test]
+fn test_invalid_glob_is_an_error() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("file.txt"), "").unwrap();
+
+    let err = get_tree_data(
+        dir.path().to_string_lossy().to_string(),
+        false,
+        None,
+        None,
+        options(r#"{ "include": ["a[b"] }"#),
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, AppError::InvalidPattern { ref pattern, .. } if pattern == "a[b"));
+}
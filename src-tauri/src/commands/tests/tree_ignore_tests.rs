@@ -0,0 +1,115 @@
+use crate::commands::tree::get_tree_data;
+use crate::models::pathinfo::NodeDetails;
+use crate::models::tree_options::TreeOptions;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn create_repo(root: &Path) {
+    fs::create_dir_all(root.join(".git/info")).unwrap();
+    fs::create_dir_all(root.join("target/debug")).unwrap();
+    fs::create_dir_all(root.join("src/generated")).unwrap();
+    fs::write(root.join(".gitignore"), "target/\n*.log\n!keep.log\n").unwrap();
+    fs::write(root.join(".git/info/exclude"), "scratch.txt\n").unwrap();
+    fs::write(root.join("src/.gitignore"), "generated/\n").unwrap();
+    fs::write(root.join("target/debug/app"), "").unwrap();
+    fs::write(root.join("build.log"), "").unwrap();
+    fs::write(root.join("keep.log"), "").unwrap();
+    fs::write(root.join("scratch.txt"), "").unwrap();
+    fs::write(root.join("src/main.rs"), "").unwrap();
+    fs::write(root.join("src/generated/out.rs"), "").unwrap();
+}
+
+fn list(root: &Path, recursive: bool, mode: &str) -> Vec<NodeDetails> {
+    let options: TreeOptions =
+        serde_json::from_str(&format!(r#"{{ "gitignore": "{}" }}"#, mode)).unwrap();
+    get_tree_data(
+        root.to_string_lossy().to_string(),
+        recursive,
+        None,
+        None,
+        Some(options),
+    )
+    .unwrap()
+}
+
+fn names(nodes: &[NodeDetails]) -> Vec<&str> {
+    let mut names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_hide_mode_drops_ignored_entries() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+
+    let nodes = list(dir.path(), false, "Hide");
+
+    assert_eq!(names(&nodes), vec![".git", ".gitignore", "keep.log", "src"]);
+}
+
+#[test]
+fn test_flag_mode_marks_ignored_entries() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+
+    let nodes = list(dir.path(), false, "Flag");
+
+    let ignored: Vec<&str> = names(&nodes)
+        .into_iter()
+        .filter(|name| nodes.iter().any(|n| n.name == *name && n.ignored))
+        .collect();
+    assert_eq!(ignored, vec!["build.log", "scratch.txt", "target"]);
+}
+
+#[test]
+fn test_recursive_walk_skips_ignored_subtrees() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+
+    let nodes = list(dir.path(), true, "Flag");
+
+    // Ignored folders are listed but never read
+    assert!(nodes.iter().any(|n| n.name == "target" && n.ignored));
+    assert!(nodes.iter().any(|n| n.name == "generated" && n.ignored));
+    assert!(!nodes
+        .iter()
+        .any(|n| n.name == "debug" || n.name == "out.rs"));
+    assert!(nodes.iter().any(|n| n.name == "main.rs" && !n.ignored));
+}
+
+#[test]
+fn test_listing_inside_ignored_folder_flags_children() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+
+    let nodes = list(&dir.path().join("target"), false, "Flag");
+
+    assert_eq!(nodes.len(), 1);
+    assert!(nodes[0].ignored);
+}
+
+#[test]
+fn test_gitignore_outside_repository_is_not_applied() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".gitignore"), "*.log\n").unwrap();
+    fs::write(dir.path().join(".ignore"), "*.tmp\n").unwrap();
+    fs::write(dir.path().join("app.log"), "").unwrap();
+    fs::write(dir.path().join("cache.tmp"), "").unwrap();
+
+    let nodes = list(dir.path(), false, "Hide");
+
+    assert_eq!(names(&nodes), vec![".gitignore", ".ignore", "app.log"]);
+}
+
+#[test]
+fn test_off_mode_ignores_nothing() {
+    let dir = tempdir().unwrap();
+    create_repo(dir.path());
+
+    let nodes = list(dir.path(), false, "Off");
+
+    assert_eq!(nodes.len(), 7);
+    assert!(nodes.iter().all(|n| !n.ignored));
+}
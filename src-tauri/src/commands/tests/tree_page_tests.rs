@@ -0,0 +1,193 @@
+use crate::commands::tree_page::get_tree_page;
+use crate::models::error::AppError;
+use crate::models::tree_options::TreeOptions;
+use std::fs;
+use tempfile::tempdir;
+
+fn create_files(dir: &std::path::Path, count: usize) {
+    for i in 0..count {
+        fs::write(dir.join(format!("file_{:03}.txt", i)), "x").unwrap();
+    }
+}
+
+#[test]
+fn test_pages_cover_directory_in_stable_order() {
+    let dir = tempdir().unwrap();
+    create_files(dir.path(), 25);
+    let path = dir.path().to_string_lossy().to_string();
+
+    let mut names = Vec::new();
+    let mut cursor = None;
+    let mut pages = 0;
+    loop {
+        let page = get_tree_page(path.clone(), cursor, Some(10), None).unwrap();
+        assert_eq!(page.total_estimate, 25);
+        names.extend(page.entries.into_iter().map(|n| n.name));
+        pages += 1;
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(pages, 3);
+    let expected: Vec<String> = (0..25).map(|i| format!("file_{:03}.txt", i)).collect();
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn test_later_pages_ignore_new_entries() {
+    let dir = tempdir().unwrap();
+    create_files(dir.path(), 4);
+    let path = dir.path().to_string_lossy().to_string();
+
+    let first = get_tree_page(path.clone(), None, Some(2), None).unwrap();
+    // A file that sorts before the cursor must not shift the second page
+    fs::write(dir.path().join("a_new.txt"), "x").unwrap();
+    let second = get_tree_page(path, first.next_cursor, Some(2), None).unwrap();
+
+    let names: Vec<String> = second.entries.into_iter().map(|n| n.name).collect();
+    assert_eq!(names, vec!["file_002.txt", "file_003.txt"]);
+    assert!(second.next_cursor.is_none());
+}
+
+#[test]
+fn test_empty_directory_has_single_empty_page() {
+    let dir = tempdir().unwrap();
+
+    let page = get_tree_page(dir.path().to_string_lossy().to_string(), None, None, None).unwrap();
+
+    assert!(page.entries.is_empty());
+    assert!(page.next_cursor.is_none());
+    assert_eq!(page.total_estimate, 0);
+}
+
+#[test]
+fn test_invalid_cursor_is_rejected() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().to_string_lossy().to_string();
+
+    assert!(get_tree_page(path, Some("not-a-cursor".to_string()), None, None).is_err());
+}
+
+#[test]
+fn test_expired_cursor_is_rejected() {
+    let dir = tempdir().unwrap();
+    create_files(dir.path(), 3);
+    let path = dir.path().to_string_lossy().to_string();
+
+    // A well-formed cursor for a listing that is no longer kept
+    let result = get_tree_page(path, Some("ffffffffffff.1".to_string()), None, None);
+
+    assert!(matches!(result, Err(AppError::InvalidArgument { .. })));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_names_that_are_not_unicode_are_listed() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempdir().unwrap();
+    create_files(dir.path(), 2);
+    let name = OsStr::from_bytes(b"latin1_\xe9.txt");
+    // Some filesystems only take valid UTF-8 names
+    if fs::write(dir.path().join(name), "x").is_err() {
+        return;
+    }
+
+    let page = get_tree_page(dir.path().to_string_lossy().to_string(), None, None, None).unwrap();
+
+    assert_eq!(page.total_estimate, 3);
+    assert_eq!(page.entries.len(), 3);
+}
+
+#[test]
+fn test_cursor_from_other_directory_is_rejected() {
+    let first_dir = tempdir().unwrap();
+    let second_dir = tempdir().unwrap();
+    create_files(first_dir.path(), 3);
+
+    let page = get_tree_page(
+        first_dir.path().to_string_lossy().to_string(),
+        None,
+        Some(1),
+        None,
+    )
+    .unwrap();
+    let result = get_tree_page(
+        second_dir.path().to_string_lossy().to_string(),
+        page.next_cursor,
+        Some(1),
+        None,
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_pages_follow_requested_sort_order() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("small.txt"), "x").unwrap();
+    fs::write(dir.path().join("large.txt"), "x".repeat(100)).unwrap();
+    fs::write(dir.path().join("medium.txt"), "x".repeat(10)).unwrap();
+    let options: TreeOptions =
+        serde_json::from_str(r#"{ "sort_by": "Size", "sort_direction": "Descending" }"#).unwrap();
+
+    let first = get_tree_page(
+        dir.path().to_string_lossy().to_string(),
+        None,
+        Some(2),
+        Some(options),
+    )
+    .unwrap();
+    let second = get_tree_page(
+        dir.path().to_string_lossy().to_string(),
+        first.next_cursor,
+        Some(2),
+        None,
+    )
+    .unwrap();
+
+    let names: Vec<String> = first
+        .entries
+        .into_iter()
+        .chain(second.entries)
+        .map(|n| n.name)
+        .collect();
+    assert_eq!(names, vec!["large.txt", "medium.txt", "small.txt"]);
+}
+
+#[test]
+fn test_pages_flag_ignored_entries() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join(".git")).unwrap();
+    fs::write(dir.path().join(".gitignore"), "*.log\n").unwrap();
+    fs::write(dir.path().join("app.log"), "").unwrap();
+    fs::write(dir.path().join("main.rs"), "").unwrap();
+    let options: TreeOptions = serde_json::from_str(r#"{ "gitignore": "Flag" }"#).unwrap();
+
+    let first = get_tree_page(
+        dir.path().to_string_lossy().to_string(),
+        None,
+        Some(2),
+        Some(options),
+    )
+    .unwrap();
+    let second = get_tree_page(
+        dir.path().to_string_lossy().to_string(),
+        first.next_cursor,
+        Some(2),
+        None,
+    )
+    .unwrap();
+
+    let ignored: Vec<String> = first
+        .entries
+        .into_iter()
+        .chain(second.entries)
+        .filter(|n| n.ignored)
+        .map(|n| n.name)
+        .collect();
+    assert_eq!(ignored, vec!["app.log"]);
+}
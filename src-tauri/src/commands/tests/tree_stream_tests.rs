@@ -0,0 +1,80 @@
+use crate::commands::tree_stream::walk_tree_streaming;
+use crate::models::pathinfo::NodeDetails;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+use tokio::sync::mpsc;
+
+fn create_sample_tree(root: &Path) {
+    fs::create_dir_all(root.join("a/b/c")).unwrap();
+    fs::write(root.join("top.txt"), "x").unwrap();
+    fs::write(root.join("a/one.txt"), "x").unwrap();
+    fs::write(root.join("a/b/two.txt"), "x").unwrap();
+    fs::write(root.join("a/b/c/three.txt"), "x").unwrap();
+}
+
+#[test]
+fn test_stream_emits_every_entry() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+    let (_tx, mut rx) = mpsc::channel(1);
+    let mut streamed: Vec<NodeDetails> = Vec::new();
+
+    let summary = walk_tree_streaming(
+        "job",
+        dir.path().to_string_lossy().to_string(),
+        None,
+        &mut rx,
+        |batch| {
+            assert_eq!(batch.job_id, "job");
+            streamed.extend(batch.nodes);
+        },
+        |_| {},
+    );
+
+    assert!(!summary.cancelled);
+    assert_eq!(summary.entries_found, 7);
+    assert_eq!(streamed.len(), 7);
+    assert_eq!(summary.directories_read, 4);
+    assert_eq!(summary.unreadable_directories, 0);
+}
+
+#[test]
+fn test_stream_respects_max_depth() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+    let (_tx, mut rx) = mpsc::channel(1);
+    let mut streamed = 0;
+
+    let summary = walk_tree_streaming(
+        "job",
+        dir.path().to_string_lossy().to_string(),
+        Some(1),
+        &mut rx,
+        |batch| streamed += batch.nodes.len(),
+        |_| {},
+    );
+
+    assert_eq!(summary.directories_read, 1);
+    assert_eq!(streamed, 2);
+}
+
+#[test]
+fn test_stream_stops_when_cancelled() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.try_send(()).unwrap();
+
+    let summary = walk_tree_streaming(
+        "job",
+        dir.path().to_string_lossy().to_string(),
+        None,
+        &mut rx,
+        |_| panic!("no batch expected after cancellation"),
+        |_| {},
+    );
+
+    assert!(summary.cancelled);
+    assert_eq!(summary.entries_found, 0);
+}
@@ -0,0 +1,123 @@
+use crate::commands::tree::get_tree_data;
+use crate::models::pathinfo::{NodeDetails, PathinfoKind};
+use crate::models::tree_options::TreeOptions;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn follow_links() -> Option<TreeOptions> {
+    Some(serde_json::from_str(r#"{ "follow_symlinks": true }"#).unwrap())
+}
+
+fn list(root: &Path, recursive: bool, options: Option<TreeOptions>) -> Vec<NodeDetails> {
+    get_tree_data(
+        root.to_string_lossy().to_string(),
+        recursive,
+        None,
+        None,
+        options,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_symlinks_are_reported_with_target() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("real")).unwrap();
+    fs::write(dir.path().join("file.txt"), "x").unwrap();
+    symlink("real", dir.path().join("dir_link")).unwrap();
+    symlink("file.txt", dir.path().join("file_link")).unwrap();
+
+    let nodes = list(dir.path(), false, None);
+
+    let dir_link = nodes.iter().find(|n| n.name == "dir_link").unwrap();
+    assert_eq!(dir_link.kind, PathinfoKind::Symlink);
+    let details = dir_link.symlink.as_ref().unwrap();
+    assert_eq!(details.target, "real");
+    assert!(!details.broken);
+    assert!(details.points_to_directory);
+
+    let file_link = nodes.iter().find(|n| n.name == "file_link").unwrap();
+    assert!(!file_link.symlink.as_ref().unwrap().points_to_directory);
+
+    let real = nodes.iter().find(|n| n.name == "real").unwrap();
+    assert_eq!(real.kind, PathinfoKind::Directory);
+    assert!(real.symlink.is_none());
+}
+
+#[test]
+fn test_broken_symlink_is_flagged() {
+    let dir = tempdir().unwrap();
+    symlink("missing", dir.path().join("dangling")).unwrap();
+
+    let nodes = list(dir.path(), false, None);
+
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].kind, PathinfoKind::Symlink);
+    assert!(nodes[0].symlink.as_ref().unwrap().broken);
+}
+
+#[test]
+fn test_symlinked_folders_are_not_followed_by_default() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("real")).unwrap();
+    fs::write(dir.path().join("real/inner.txt"), "x").unwrap();
+    let other = tempdir().unwrap();
+    fs::write(other.path().join("outside.txt"), "x").unwrap();
+    symlink(other.path(), dir.path().join("link")).unwrap();
+
+    let nodes = list(dir.path(), true, None);
+
+    assert!(nodes.iter().any(|n| n.name == "inner.txt"));
+    assert!(!nodes.iter().any(|n| n.name == "outside.txt"));
+}
+
+#[test]
+fn test_followed_symlinks_are_descended() {
+    let dir = tempdir().unwrap();
+    let other = tempdir().unwrap();
+    fs::write(other.path().join("outside.txt"), "x").unwrap();
+    symlink(other.path(), dir.path().join("link")).unwrap();
+
+    let nodes = list(dir.path(), true, follow_links());
+
+    assert!(nodes.iter().any(|n| n.name == "outside.txt"));
+}
+
+#[test]
+fn test_symlink_loop_terminates() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("a")).unwrap();
+    fs::write(dir.path().join("a/file.txt"), "x").unwrap();
+    // a/back -> .. forms a cycle through the root
+    symlink("..", dir.path().join("a/back")).unwrap();
+    symlink("a", dir.path().join("alias")).unwrap();
+
+    let nodes = list(dir.path(), true, follow_links());
+
+    // a, alias, a/file.txt, a/back; neither link is read again
+    assert_eq!(nodes.len(), 4);
+    assert_eq!(nodes.iter().filter(|n| n.name == "file.txt").count(), 1);
+}
+
+#[test]
+fn test_nested_listing_follows_links_once() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("a")).unwrap();
+    symlink("..", dir.path().join("a/back")).unwrap();
+
+    let nodes = get_tree_data(
+        dir.path().to_string_lossy().to_string(),
+        true,
+        Some(true),
+        None,
+        follow_links(),
+    )
+    .unwrap();
+
+    let a = nodes.iter().find(|n| n.name == "a").unwrap();
+    let back = &a.children.as_ref().unwrap()[0];
+    assert_eq!(back.kind, PathinfoKind::Symlink);
+    assert!(back.children.is_none());
+}
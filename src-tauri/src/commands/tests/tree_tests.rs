@@ -0,0 +1,135 @@
+use crate::commands::tree::get_tree_data;
+use crate::models::error::AppError;
+use crate::models::pathinfo::{NodeDetails, PathinfoKind};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn create_sample_tree(root: &Path) {
+    fs::create_dir_all(root.join("src/nested/deeper")).unwrap();
+    fs::create_dir(root.join("empty")).unwrap();
+    fs::write(root.join("README.md"), "readme").unwrap();
+    fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+    fs::write(root.join("src/nested/deeper/leaf.txt"), "leaf").unwrap();
+}
+
+fn find<'a>(nodes: &'a [NodeDetails], name: &str) -> &'a NodeDetails {
+    nodes
+        .iter()
+        .find(|n| n.name == name)
+        .unwrap_or_else(|| panic!("{} not found", name))
+}
+
+fn root_path(dir: &tempfile::TempDir) -> String {
+    dir.path().to_string_lossy().to_string()
+}
+
+#[test]
+fn test_flat_listing_is_single_level() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+
+    let nodes = get_tree_data(root_path(&dir), false, None, None, None).unwrap();
+
+    assert_eq!(nodes.len(), 3);
+    assert!(nodes.iter().all(|n| n.children.is_none()));
+}
+
+#[test]
+fn test_flat_recursive_listing_includes_all_entries() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+
+    let nodes = get_tree_data(root_path(&dir), true, None, None, None).unwrap();
+
+    // README.md, src, empty, src/main.rs, src/nested, nested/deeper, deeper/leaf.txt
+    assert_eq!(nodes.len(), 7);
+}
+
+#[test]
+fn test_flat_recursive_listing_respects_max_depth() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+
+    let nodes = get_tree_data(root_path(&dir), true, None, Some(2), None).unwrap();
+
+    assert_eq!(nodes.len(), 5);
+    assert!(!nodes.iter().any(|n| n.name == "deeper"));
+}
+
+#[test]
+fn test_nested_listing_builds_hierarchy() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+
+    let nodes = get_tree_data(root_path(&dir), true, Some(true), None, None).unwrap();
+
+    assert_eq!(nodes.len(), 3);
+    let src = find(&nodes, "src");
+    let src_children = src.children.as_ref().expect("src should be loaded");
+    assert_eq!(src_children.len(), 2);
+    let nested = find(src_children, "nested");
+    let deeper = find(nested.children.as_ref().unwrap(), "deeper");
+    let leaf = find(deeper.children.as_ref().unwrap(), "leaf.txt");
+    assert_eq!(leaf.kind, PathinfoKind::File);
+    assert!(leaf.children.is_none());
+}
+
+#[test]
+fn test_nested_listing_distinguishes_empty_from_unloaded() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+
+    let nodes = get_tree_data(root_path(&dir), true, Some(true), Some(2), None).unwrap();
+
+    // An empty directory within the depth limit is loaded with no children
+    let empty = find(&nodes, "empty");
+    assert_eq!(empty.children.as_deref(), Some(&[][..]));
+
+    // A directory at the depth limit is left unloaded
+    let src = find(&nodes, "src");
+    let nested = find(src.children.as_ref().unwrap(), "nested");
+    assert!(nested.children.is_none());
+}
+
+#[test]
+fn test_nested_non_recursive_leaves_directories_unloaded() {
+    let dir = tempdir().unwrap();
+    create_sample_tree(dir.path());
+
+    let nodes = get_tree_data(root_path(&dir), false, Some(true), Some(5), None).unwrap();
+
+    assert_eq!(nodes.len(), 3);
+    assert!(find(&nodes, "src").children.is_none());
+}
+
+#[test]
+fn test_missing_directory_is_not_found() {
+    let dir = tempdir().unwrap();
+    let missing = dir.path().join("missing");
+
+    let err = get_tree_data(
+        missing.to_string_lossy().to_string(),
+        true,
+        Some(true),
+        None,
+        None,
+    )
+    .unwrap_err();
+
+    assert_eq!(err.kind(), "NotFound");
+    assert_eq!(err.path(), Some(missing.to_string_lossy().as_ref()));
+    assert!(err.code().is_some());
+}
+
+#[test]
+fn test_file_is_not_a_directory() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("README.md");
+    fs::write(&file, "readme").unwrap();
+
+    let err =
+        get_tree_data(file.to_string_lossy().to_string(), false, None, None, None).unwrap_err();
+
+    assert!(matches!(err, AppError::NotADirectory { .. }));
+}
@@ -0,0 +1,60 @@
+use crate::commands::tree::get_tree_data;
+use crate::models::tree_options::TreeOptions;
+use crate::platforms::unix::format_mode;
+use std::fs;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use tempfile::tempdir;
+
+#[test]
+fn test_format_mode() {
+    assert_eq!(format_mode(0o755), "rwxr-xr-x");
+    assert_eq!(format_mode(0o644), "rw-r--r--");
+    assert_eq!(format_mode(0o100600), "rw-------");
+    assert_eq!(format_mode(0o4755), "rwsr-xr-x");
+    assert_eq!(format_mode(0o2745), "rwxr-Sr-x");
+    assert_eq!(format_mode(0o1777), "rwxrwxrwt");
+    assert_eq!(format_mode(0o1776), "rwxrwxrwT");
+}
+
+#[test]
+fn test_extended_metadata_is_opt_in() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("file.txt"), "x").unwrap();
+    let path = dir.path().to_string_lossy().to_string();
+
+    let plain = get_tree_data(path.clone(), false, None, None, None).unwrap();
+    assert!(plain[0].unix.is_none());
+
+    let options: TreeOptions = serde_json::from_str(r#"{ "extended_metadata": true }"#).unwrap();
+    let extended = get_tree_data(path, false, None, None, Some(options)).unwrap();
+    assert!(extended[0].unix.is_some());
+}
+
+#[test]
+fn test_extended_metadata_matches_filesystem() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("file.txt");
+    fs::write(&file, "x").unwrap();
+    fs::set_permissions(&file, fs::Permissions::from_mode(0o640)).unwrap();
+    fs::hard_link(&file, dir.path().join("second_link.txt")).unwrap();
+    let options: TreeOptions = serde_json::from_str(r#"{ "extended_metadata": true }"#).unwrap();
+
+    let nodes = get_tree_data(
+        dir.path().to_string_lossy().to_string(),
+        false,
+        None,
+        None,
+        Some(options),
+    )
+    .unwrap();
+
+    let node = nodes.iter().find(|n| n.name == "file.txt").unwrap();
+    let unix = node.unix.as_ref().unwrap();
+    let metadata = fs::metadata(&file).unwrap();
+    assert_eq!(unix.permissions, "rw-r-----");
+    assert_eq!(unix.mode, 0o640);
+    assert_eq!(unix.inode, metadata.ino());
+    assert_eq!(unix.device, metadata.dev());
+    assert_eq!(unix.uid, metadata.uid());
+    assert_eq!(unix.hard_links, 2);
+}
@@ -0,0 +1,180 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::journal::{self, Step};
+use crate::commands::move_ops::result_for;
+use crate::commands::sandbox;
+use crate::models::error::AppResult;
+use crate::models::journal::OperationKind;
+use crate::models::transfer::{ConflictPolicy, MoveResult};
+use crate::models::trash::TrashItem;
+use crate::platforms;
+use std::path::{Path, PathBuf};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::platforms::trash::Trash;
+
+/// Whether this platform has a trash we can use. Deletes are permanent where it doesn't.
+pub(crate) const TRASH_SUPPORTED: bool = cfg!(all(unix, not(target_os = "macos")));
+
+/// Moves a file or folder to the trash
+///
+/// Items go to the trash folder of their own volume when it has one, and to the home
+/// trash otherwise.
+///
+/// Parameters:
+/// - path: The item to trash; a symlink is trashed, not what it points to
+///
+/// Returns:
+/// - Ok(TrashItem): The item as it now appears in the trash
+/// - Err(AppError): Unsupported on platforms without a trash, or the error that
+///   stopped the move
+#[tauri::command]
+pub async fn move_to_trash(path: String) -> AppResult<TrashItem> {
+    let normalized_path = platforms::normalize_path(&path);
+    sandbox::ensure_entry_allowed(Path::new(&normalized_path))?;
+    trash_and_record(Path::new(&normalized_path))
+}
+
+/// Lists the items in the home trash and the trash folders of mounted volumes
+///
+/// Returns:
+/// - Ok(Vec<TrashItem>): The items, most recently trashed first
+/// - Err(AppError): Unsupported on platforms without a trash
+#[tauri::command]
+pub async fn list_trash() -> AppResult<Vec<TrashItem>> {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let mut items = Trash::for_current_user()?.list();
+        items.sort_by(|a, b| b.deletion_date.cmp(&a.deletion_date));
+        Ok(items)
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        Err(trash_unsupported())
+    }
+}
+
+/// Puts a trashed item back where it was, recreating missing parent folders
+///
+/// Parameters:
+/// - id: The item's `id` from `list_trash`
+/// - conflict: What to do if something now exists at the original path (defaults to Fail)
+///
+/// Returns:
+/// - Ok(MoveResult): Where the item ended up, or `Skipped` if the conflict policy said so,
+///   in which case it stays in the trash
+/// - Err(AppError): InvalidArgument for an id that isn't in the trash, AlreadyExists for
+///   a conflict under the Fail policy, or the error that stopped the restore
+#[tauri::command]
+pub async fn restore_from_trash(
+    id: String,
+    conflict: Option<ConflictPolicy>,
+) -> AppResult<MoveResult> {
+    let normalized_id = platforms::normalize_path(&id);
+    let (source, original, restored) =
+        restore_path(Path::new(&normalized_id), conflict.unwrap_or_default())?;
+    Ok(result_for(&source, &original, restored))
+}
+
+/// Permanently deletes items from the trash
+///
+/// Parameters:
+/// - ids: The items to delete, by their `id` from `list_trash`; everything in every
+///   trash when omitted
+///
+/// Returns:
+/// - Ok(usize): The number of items deleted
+/// - Err(AppError): InvalidArgument for an id that isn't in the trash, or the error that
+///   stopped the deletion. Items deleted before the error stay deleted.
+#[tauri::command]
+pub async fn empty_trash(ids: Option<Vec<String>>) -> AppResult<usize> {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let trash = Trash::for_current_user()?;
+        let Some(ids) = ids else {
+            return trash.empty();
+        };
+        for id in &ids {
+            let normalized_id = platforms::normalize_path(id);
+            trash.erase(Path::new(&normalized_id))?;
+        }
+        Ok(ids.len())
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        let _ = ids;
+        Err(trash_unsupported())
+    }
+}
+
+/// Moves `path` to the current user's trash
+pub(crate) fn trash_path(path: &Path) -> AppResult<TrashItem> {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        Trash::for_current_user()?.trash(path)
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        let _ = path;
+        Err(trash_unsupported())
+    }
+}
+
+/// Moves `path` to the trash and records it in the journal for undo
+pub(crate) fn trash_and_record(path: &Path) -> AppResult<TrashItem> {
+    let item = trash_path(path)?;
+    journal::record(
+        OperationKind::Trash,
+        vec![Step::trashed(Path::new(&item.original_path), Path::new(&item.id))],
+    );
+    Ok(item)
+}
+
+/// Restores the trashed item `id` from the current user's trash. Returns its place in
+/// the trash, its original path, and where it ended up (`None` if skipped).
+pub(crate) fn restore_path(
+    id: &Path,
+    policy: ConflictPolicy,
+) -> AppResult<(PathBuf, PathBuf, Option<PathBuf>)> {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        Trash::for_current_user()?.restore(id, policy)
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        let _ = (id, policy);
+        Err(trash_unsupported())
+    }
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn trash_unsupported() -> crate::models::error::AppError {
+    crate::models::error::AppError::unsupported("The trash isn't supported on this platform yet.")
+}
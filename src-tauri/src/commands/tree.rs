@@ -22,95 +22,258 @@
 //
 // The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
 // for providing inspiration for this project.
-use crate::models::pathinfo::{NodeDetails, PathinfoKind};
-use crate::platforms;
+use crate::commands::archive_fs;
+use crate::commands::filesystem_ops::ensure_directory;
+use crate::commands::sandbox;
+use crate::commands::tree_filter::TreeFilter;
+use crate::models::error::{AppError, AppResult};
+use crate::models::pathinfo::{NodeDetails, PathinfoKind, SymlinkDetails};
+use crate::models::tree_options::TreeOptions;
+use crate::platforms::{self, FileIdentity};
+use std::collections::HashSet;
 use std::path::Path;
 use std::{fs, io::ErrorKind, time::UNIX_EPOCH};
 
+/// Lists the contents of a directory
+///
+/// Parameters:
+/// - directory_path: The directory to list, or a folder inside an archive as
+///   `archive.zip!/inner/path` (`archive.zip!/` for its root)
+/// - recursive: Whether to descend into subdirectories
+/// - nested: Return a nested tree with populated `children` instead of a flat list
+/// - max_depth: Maximum number of levels to read below `directory_path` when recursive
+/// - options: Sorting and filtering applied to each directory's entries
+///
+/// Returns:
+/// - Ok(Vec<NodeDetails>): A flat list of every entry found, or the direct children
+///   of `directory_path` with their subtrees attached when `nested` is set. A
+///   directory whose `children` is `None` has not been loaded; `Some` of an empty
+///   list is an empty directory. Unreadable subdirectories are skipped.
+/// - Err(AppError): If `directory_path` is missing, not a folder or unreadable, or
+///   an include/exclude pattern is invalid
 #[tauri::command]
-pub fn get_tree_data(directory_path: String, recursive: bool) -> Vec<NodeDetails> {
-    let mut pathinfo_list: Vec<NodeDetails> = Vec::new();
-    let mut stack: Vec<String> = Vec::new();
-
+pub fn get_tree_data(
+    directory_path: String,
+    recursive: bool,
+    nested: Option<bool>,
+    max_depth: Option<usize>,
+    options: Option<TreeOptions>,
+) -> AppResult<Vec<NodeDetails>> {
     // Normalize the input path for platform consistency
     let normalized_path = platforms::normalize_path(&directory_path);
     let path = Path::new(&normalized_path);
+    sandbox::ensure_allowed(path)?;
 
-    // Early validation of the directory
     if !path.exists() {
-        eprintln!("Directory does not exist: {}", normalized_path);
-        return pathinfo_list;
+        if let Some((archive, inner)) = archive_fs::split_archive_path(&normalized_path) {
+            let filter = TreeFilter::new(options.unwrap_or_default())?;
+            let depth_limit = if recursive { max_depth } else { Some(1) };
+            let nested = nested.unwrap_or(false);
+            return archive_fs::tree_data(&archive, &inner, depth_limit, nested, &filter);
+        }
     }
 
-    if !path.is_dir() {
-        eprintln!("Path is not a directory: {}", normalized_path);
-        return pathinfo_list;
-    }
+    // Early validation of the directory
+    ensure_directory(path)?;
+    // An unreadable root is an error rather than an empty listing
+    fs::read_dir(path).map_err(|e| AppError::from_io(e, path))?;
 
     let _directory_path_is_hidden = platforms::is_hidden(path);
-    stack.push(normalized_path);
-
-    while let Some(current_path) = stack.pop() {
-        match fs::read_dir(&current_path) {
-            Ok(files) => {
-                for file in files {
-                    if let Ok(entry) = file {
-                        let path_buf = entry.path();
-                        if let Ok(metadata) = entry.metadata() {
-                            let name = entry.file_name().to_string_lossy().to_string();
-                            // Ensure path is normalized for the current platform
-                            let path = platforms::normalize_path(&path_buf.to_string_lossy());
-                            let kind = if metadata.is_dir() {
-                                PathinfoKind::Directory
-                            } else {
-                                PathinfoKind::File
-                            };
-                            let hidden = platforms::is_hidden(&path_buf);
-
-                            // Get file metadata in a platform-independent way
-                            let size = metadata.len();
-                            let created = metadata
-                                .created()
-                                .unwrap_or(UNIX_EPOCH)
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_millis();
-                            let modified = metadata
-                                .modified()
-                                .unwrap_or(UNIX_EPOCH)
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_millis();
-
-                            pathinfo_list.push(NodeDetails {
-                                name,
-                                path: path.clone(),
-                                kind: kind.clone(),
-                                hidden,
-                                size,
-                                created,
-                                modified,
-                                children: Some(Vec::new()),
-                            });
-
-                            if recursive && kind == PathinfoKind::Directory {
-                                stack.push(path);
-                            }
-                        }
-                    }
+
+    let filter = TreeFilter::new(options.unwrap_or_default())?;
+
+    // A non-recursive listing only ever reads one level
+    let depth_limit = if recursive { max_depth } else { Some(1) };
+    let mut walker = TreeWalker::new(&filter, depth_limit, path);
+
+    if nested.unwrap_or(false) {
+        Ok(walker.read_nested(&normalized_path, 1))
+    } else {
+        Ok(walker.read_flat(normalized_path))
+    }
+}
+
+/// State shared across one listing: the filter, how deep to go and which
+/// folders have been read, so symlink loops are only followed once
+struct TreeWalker<'a> {
+    filter: &'a TreeFilter,
+    depth_limit: Option<usize>,
+    visited: HashSet<FileIdentity>,
+}
+
+impl<'a> TreeWalker<'a> {
+    fn new(filter: &'a TreeFilter, depth_limit: Option<usize>, root: &Path) -> Self {
+        let mut visited = HashSet::new();
+        if let Some(identity) = platforms::file_identity(root) {
+            visited.insert(identity);
+        }
+        TreeWalker {
+            filter,
+            depth_limit,
+            visited,
+        }
+    }
+
+    /// Walks the directory tree, collecting every entry into one flat list
+    fn read_flat(&mut self, root: String) -> Vec<NodeDetails> {
+        let mut pathinfo_list: Vec<NodeDetails> = Vec::new();
+        let mut stack: Vec<(String, usize)> = vec![(root, 1)];
+
+        while let Some((current_path, depth)) = stack.pop() {
+            let Some(entries) = read_entries(&current_path, self.filter) else {
+                continue;
+            };
+
+            for node in entries {
+                if self.should_descend(&node, depth) {
+                    stack.push((node.path.clone(), depth + 1));
                 }
+                pathinfo_list.push(node);
             }
-            Err(err) => {
-                if err.kind() == ErrorKind::PermissionDenied {
-                    // Log permission errors but continue processing
-                    eprintln!("Permission denied: {}", current_path);
-                    continue;
-                }
+        }
+
+        pathinfo_list
+    }
+
+    /// Reads a directory and attaches the children of each subdirectory until the
+    /// depth limit is reached
+    fn read_nested(&mut self, current_path: &str, depth: usize) -> Vec<NodeDetails> {
+        let Some(mut entries) = read_entries(current_path, self.filter) else {
+            return Vec::new();
+        };
+
+        for node in entries.iter_mut() {
+            if self.should_descend(node, depth) {
+                node.children = Some(self.read_nested(&node.path, depth + 1));
+            }
+        }
+
+        entries
+    }
+
+    /// Whether a node's contents should be read, marking its folder as visited if so
+    fn should_descend(&mut self, node: &NodeDetails, depth: usize) -> bool {
+        let is_folder = match node.kind {
+            PathinfoKind::Directory => true,
+            // Links leading out of the allowed folders are listed but not followed
+            PathinfoKind::Symlink => {
+                self.filter.options().follow_symlinks
+                    && node.is_directory_like()
+                    && sandbox::is_allowed(Path::new(&node.path))
+            }
+            PathinfoKind::File => false,
+        };
+        if !is_folder || node.ignored || self.depth_limit.is_some_and(|limit| depth >= limit) {
+            return false;
+        }
+
+        // A folder reached a second time (through a link) is listed but not re-read
+        match platforms::file_identity(Path::new(&node.path)) {
+            Some(identity) => self.visited.insert(identity),
+            None => false,
+        }
+    }
+}
+
+/// Reads, filters and sorts the direct entries of one directory, logging and
+/// skipping unreadable ones
+fn read_entries(current_path: &str, filter: &TreeFilter) -> Option<Vec<NodeDetails>> {
+    match fs::read_dir(current_path) {
+        Ok(files) => Some(
+            filter.apply(
+                files
+                    .filter_map(Result::ok)
+                    .filter_map(|entry| node_from_entry(&entry))
+                    .collect(),
+            ),
+        ),
+        Err(err) => {
+            if err.kind() == ErrorKind::PermissionDenied {
+                // Log permission errors but continue processing
+                eprintln!("Permission denied: {}", current_path);
+            } else {
                 eprintln!("Error reading directory {}: {}", current_path, err);
-                continue;
             }
+            None
         }
     }
+}
+
+/// Builds the details of a single directory entry with its children not yet loaded
+pub(crate) fn node_from_entry(entry: &fs::DirEntry) -> Option<NodeDetails> {
+    let metadata = entry.metadata().ok()?;
+    Some(node_from_metadata(&entry.path(), &metadata))
+}
+
+/// Builds the details of the entry at `path` without following a final symlink
+pub(crate) fn node_from_path(path: &Path) -> Option<NodeDetails> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    Some(node_from_metadata(path, &metadata))
+}
+
+fn node_from_metadata(path_buf: &Path, metadata: &fs::Metadata) -> NodeDetails {
+    let name = path_buf
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // Ensure path is normalized for the current platform
+    let path = platforms::normalize_path(&path_buf.to_string_lossy());
+    let (kind, symlink) = if metadata.file_type().is_symlink() {
+        (PathinfoKind::Symlink, Some(symlink_details(path_buf)))
+    } else if metadata.is_dir() {
+        (PathinfoKind::Directory, None)
+    } else {
+        (PathinfoKind::File, None)
+    };
+    let hidden = platforms::is_hidden(path_buf);
+
+    // Get file metadata in a platform-independent way
+    let size = metadata.len();
+    let created = metadata
+        .created()
+        .unwrap_or(UNIX_EPOCH)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let modified = metadata
+        .modified()
+        .unwrap_or(UNIX_EPOCH)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+
+    NodeDetails {
+        name,
+        path,
+        kind,
+        hidden,
+        ignored: false,
+        size,
+        created,
+        modified,
+        symlink,
+        unix: None,
+        content_type: None,
+        git_status: None,
+        children: None,
+    }
+}
 
-    pathinfo_list
+/// Reads where a symlink points and whether that target can be reached
+fn symlink_details(link: &Path) -> SymlinkDetails {
+    let target = fs::read_link(link)
+        .map(|target| platforms::normalize_path(&target.to_string_lossy()))
+        .unwrap_or_default();
+    match fs::metadata(link) {
+        Ok(metadata) => SymlinkDetails {
+            target,
+            broken: false,
+            points_to_directory: metadata.is_dir(),
+        },
+        Err(_) => SymlinkDetails {
+            target,
+            broken: true,
+            points_to_directory: false,
+        },
+    }
 }
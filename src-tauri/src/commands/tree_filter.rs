@@ -0,0 +1,267 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+use crate::commands::tree_ignore::IgnoreRules;
+use crate::commands::{content_type, git_status};
+use crate::models::error::{AppError, AppResult};
+use crate::models::pathinfo::NodeDetails;
+use crate::models::tree_options::{GitignoreMode, SortDirection, SortKey, TreeOptions};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::cmp::Ordering;
+use std::fs;
+use std::path::Path;
+
+/// Compiled form of `TreeOptions`, applied to each directory's entries as they are read
+pub(crate) struct TreeFilter {
+    options: TreeOptions,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    ignore_rules: Option<IgnoreRules>,
+}
+
+impl TreeFilter {
+    pub fn new(options: TreeOptions) -> AppResult<Self> {
+        let include = build_globset(&options.include)?;
+        let exclude = build_globset(&options.exclude)?;
+        let ignore_rules = match options.gitignore {
+            GitignoreMode::Off => None,
+            GitignoreMode::Hide | GitignoreMode::Flag => Some(IgnoreRules::default()),
+        };
+        Ok(TreeFilter {
+            options,
+            include,
+            exclude,
+            ignore_rules,
+        })
+    }
+
+    /// Whether a node should appear in the listing (and, for folders, be descended into)
+    pub fn allows(&self, node: &NodeDetails) -> bool {
+        if node.hidden && !self.options.show_hidden {
+            return false;
+        }
+        if let Some(exclude) = &self.exclude {
+            if exclude.is_match(&node.name) {
+                return false;
+            }
+        }
+        match &self.include {
+            Some(include) if !node.is_directory_like() => include.is_match(&node.name),
+            _ => true,
+        }
+    }
+
+    /// Drops the entries that aren't allowed, applies the gitignore mode and sorts the rest
+    pub fn apply(&self, nodes: Vec<NodeDetails>) -> Vec<NodeDetails> {
+        let mut nodes: Vec<NodeDetails> = nodes
+            .into_iter()
+            .filter(|n| self.allows(n))
+            .filter_map(|n| self.mark_ignored(n))
+            .map(|n| self.add_details(n))
+            .collect();
+        self.sort(&mut nodes);
+        nodes
+    }
+
+    /// Flags an ignored node, or drops it when ignored entries are hidden
+    pub(crate) fn mark_ignored(&self, mut node: NodeDetails) -> Option<NodeDetails> {
+        node.ignored = self.is_ignored(&node);
+        if node.ignored && self.options.gitignore == GitignoreMode::Hide {
+            return None;
+        }
+        Some(node)
+    }
+
+    fn is_ignored(&self, node: &NodeDetails) -> bool {
+        self.ignore_rules
+            .as_ref()
+            .is_some_and(|rules| rules.is_ignored(Path::new(&node.path), node.is_directory_like()))
+    }
+
+    /// Fills in the details that cost extra reads, when asked for
+    pub fn add_details(&self, mut node: NodeDetails) -> NodeDetails {
+        let path = Path::new(&node.path);
+        // Other platforms have no Unix details to add
+        #[cfg(unix)]
+        if self.options.extended_metadata {
+            node.unix = crate::platforms::unix::unix_metadata(path);
+        }
+        // Only regular files are read, or a FIFO would block the listing
+        if self.options.content_types && fs::metadata(path).is_ok_and(|m| m.is_file()) {
+            node.content_type = content_type::detect_content_type(path);
+        }
+        if self.options.git_status {
+            node.git_status = git_status::status_of(path);
+        }
+        node
+    }
+
+    pub fn options(&self) -> &TreeOptions {
+        &self.options
+    }
+
+    /// Whether a node stays in the listing: it is allowed, and not hidden as ignored
+    pub fn keeps(&self, node: &NodeDetails) -> bool {
+        self.allows(node)
+            && !(self.options.gitignore == GitignoreMode::Hide && self.is_ignored(node))
+    }
+
+    pub fn sort(&self, nodes: &mut [NodeDetails]) {
+        if self.is_ordered() {
+            nodes.sort_by(|a, b| self.compare(a, b));
+        }
+    }
+
+    /// Whether filtering or sorting looks at more than the names of entries
+    pub fn needs_details(&self) -> bool {
+        let options = &self.options;
+        !options.show_hidden
+            || options.folders_first
+            || !options.include.is_empty()
+            || !options.exclude.is_empty()
+            || options.gitignore != GitignoreMode::Off
+            || !matches!(options.sort_by, None | Some(SortKey::Name))
+    }
+
+    /// Whether the options ask for any order other than the filesystem's
+    pub fn is_ordered(&self) -> bool {
+        self.options.sort_by.is_some() || self.options.folders_first
+    }
+
+    /// The order of two nodes in the listing
+    pub fn compare(&self, a: &NodeDetails, b: &NodeDetails) -> Ordering {
+        let options = &self.options;
+        let folders = if options.folders_first {
+            folder_rank(a).cmp(&folder_rank(b))
+        } else {
+            Ordering::Equal
+        };
+        folders.then_with(|| {
+            let ordering = match options.sort_by {
+                Some(key) => compare_by_key(a, b, key),
+                None => Ordering::Equal,
+            };
+            match options.sort_direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        })
+    }
+}
+
+fn build_globset(patterns: &[String]) -> AppResult<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| AppError::InvalidPattern {
+            pattern: pattern.clone(),
+            reason: e.kind().to_string(),
+        })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| AppError::InvalidPattern {
+            pattern: patterns.join(", "),
+            reason: e.to_string(),
+        })
+}
+
+fn folder_rank(node: &NodeDetails) -> u8 {
+    if node.is_directory_like() {
+        0
+    } else {
+        1
+    }
+}
+
+/// Compares two nodes by the given key, falling back to the name so the order is total
+fn compare_by_key(a: &NodeDetails, b: &NodeDetails, key: SortKey) -> Ordering {
+    let primary = match key {
+        SortKey::Name => Ordering::Equal,
+        SortKey::Size => a.size.cmp(&b.size),
+        SortKey::Modified => a.modified.cmp(&b.modified),
+        SortKey::Created => a.created.cmp(&b.created),
+        SortKey::Extension => extension_of(&a.name).cmp(&extension_of(&b.name)),
+    };
+    primary.then_with(|| natural_cmp(&a.name, &b.name))
+}
+
+fn extension_of(name: &str) -> String {
+    match name.rfind('.') {
+        Some(index) if index > 0 => name[index + 1..].to_lowercase(),
+        _ => String::new(),
+    }
+}
+
+/// Orders strings the way people expect, so "file2" sorts before "file10"
+///
+/// Runs of digits compare by numeric value, everything else case-insensitively;
+/// ties are broken by the raw string so the order is total.
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let a_digits = take_digits(&mut a_chars);
+                let b_digits = take_digits(&mut b_chars);
+                let a_trimmed = a_digits.trim_start_matches('0');
+                let b_trimmed = b_digits.trim_start_matches('0');
+                let ordering = a_trimmed
+                    .len()
+                    .cmp(&b_trimmed.len())
+                    .then_with(|| a_trimmed.cmp(b_trimmed));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_lowercase().cmp(y.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.peek().copied().filter(char::is_ascii_digit) {
+        digits.push(c);
+        chars.next();
+    }
+    digits
+}
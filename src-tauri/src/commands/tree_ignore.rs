@@ -0,0 +1,150 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+use ignore::gitignore::{gitconfig_excludes_path, Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Answers whether paths are ignored by git, following `.gitignore` and `.ignore`
+/// files from the repository root down, `.git/info/exclude` and the global
+/// `core.excludesFile`. Matchers are built lazily and cached per directory, so
+/// one instance should be reused for a whole listing.
+#[derive(Default)]
+pub(crate) struct IgnoreRules {
+    // .gitignore/.ignore of each directory seen; None when it has neither
+    dir_matchers: RefCell<HashMap<PathBuf, Option<Gitignore>>>,
+    // info/exclude and the global excludes file, per repository root
+    repo_matchers: RefCell<HashMap<PathBuf, Gitignore>>,
+    // Enclosing repository root of each directory seen
+    repo_roots: RefCell<HashMap<PathBuf, Option<PathBuf>>>,
+}
+
+impl IgnoreRules {
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(parent) = path.parent() else {
+            return false;
+        };
+        let repo_root = self.repo_root(parent);
+
+        // Deeper ignore files take precedence over shallower ones
+        for dir in parent.ancestors() {
+            if let Some(ignored) = self.dir_verdict(dir, repo_root.is_some(), path, is_dir) {
+                return ignored;
+            }
+            if repo_root.as_deref().is_none_or(|root| dir == root) {
+                break;
+            }
+        }
+
+        match repo_root {
+            Some(root) => {
+                let mut repo_matchers = self.repo_matchers.borrow_mut();
+                let matcher = repo_matchers
+                    .entry(root.clone())
+                    .or_insert_with(|| build_repo_matcher(&root));
+                matcher
+                    .matched_path_or_any_parents(path, is_dir)
+                    .is_ignore()
+            }
+            None => false,
+        }
+    }
+
+    fn repo_root(&self, dir: &Path) -> Option<PathBuf> {
+        if let Some(root) = self.repo_roots.borrow().get(dir) {
+            return root.clone();
+        }
+        let root = dir
+            .ancestors()
+            .find(|ancestor| ancestor.join(".git").exists())
+            .map(Path::to_path_buf);
+        self.repo_roots
+            .borrow_mut()
+            .insert(dir.to_path_buf(), root.clone());
+        root
+    }
+
+    /// Matches `path` against the ignore files of `dir`; None if they don't mention it
+    fn dir_verdict(&self, dir: &Path, in_repo: bool, path: &Path, is_dir: bool) -> Option<bool> {
+        let mut dir_matchers = self.dir_matchers.borrow_mut();
+        let matcher = dir_matchers
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| build_dir_matcher(dir, in_repo))
+            .as_ref()?;
+        match matcher.matched_path_or_any_parents(path, is_dir) {
+            Match::Ignore(_) => Some(true),
+            Match::Whitelist(_) => Some(false),
+            Match::None => None,
+        }
+    }
+}
+
+/// Builds the matcher for a directory's own ignore files; `.gitignore` only counts
+/// inside a repository, and `.ignore` is added last so it wins over `.gitignore`
+fn build_dir_matcher(dir: &Path, in_repo: bool) -> Option<Gitignore> {
+    let mut builder = GitignoreBuilder::new(dir);
+    let mut found = false;
+    let candidates = if in_repo {
+        vec![".gitignore", ".ignore"]
+    } else {
+        vec![".ignore"]
+    };
+
+    for name in candidates {
+        let file = dir.join(name);
+        if file.is_file() {
+            found = true;
+            if let Some(err) = builder.add(&file) {
+                eprintln!("Error reading {}: {}", file.display(), err);
+            }
+        }
+    }
+
+    if !found {
+        return None;
+    }
+    builder.build().ok().filter(|matcher| !matcher.is_empty())
+}
+
+/// Builds the repository-wide matcher; `info/exclude` is added last so it wins
+/// over the global excludes file, as in git
+fn build_repo_matcher(root: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    let files = [
+        gitconfig_excludes_path(),
+        Some(root.join(".git").join("info").join("exclude")),
+    ];
+
+    for file in files.into_iter().flatten() {
+        if file.is_file() {
+            if let Some(err) = builder.add(&file) {
+                eprintln!("Error reading {}: {}", file.display(), err);
+            }
+        }
+    }
+
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
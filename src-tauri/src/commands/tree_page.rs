@@ -0,0 +1,214 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+use crate::commands::constants::{
+    DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, MAX_PAGE_SNAPSHOTS, PAGE_SNAPSHOT_TTL,
+};
+use crate::commands::filesystem_ops::ensure_directory;
+use crate::commands::sandbox;
+use crate::commands::tree::{node_from_entry, node_from_path};
+use crate::commands::tree_filter::{natural_cmp, TreeFilter};
+use crate::models::error::{AppError, AppResult};
+use crate::models::pathinfo::{NodeDetails, TreePage};
+use crate::models::tree_options::{SortDirection, SortKey, TreeOptions};
+use crate::platforms;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// The sorted entry names of a directory, captured when its first page is requested
+/// so later pages are served from the same order and with the same options
+struct PageSnapshot {
+    directory: String,
+    names: Vec<OsString>,
+    options: TreeOptions,
+    last_used: Instant,
+}
+
+static PAGE_SNAPSHOTS: Lazy<Mutex<HashMap<u64, PageSnapshot>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_SNAPSHOT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Lists one page of a directory's direct children
+///
+/// Parameters:
+/// - directory_path: The directory to list
+/// - cursor: The `next_cursor` of the previous page, or None for the first page
+/// - page_size: Number of entries per page (defaults to 500, capped at 5000)
+/// - options: Sorting and filtering for the listing; only read with the first page,
+///   later pages keep the order and options the first one established (name order
+///   by default)
+///
+/// Returns:
+/// - Ok(TreePage): The entries of this page, the cursor for the next one and the
+///   number of entries in the directory
+/// - Err(AppError): If the path isn't a readable directory, or InvalidArgument if the
+///   cursor is malformed, has expired or belongs to another directory
+#[tauri::command]
+pub fn get_tree_page(
+    directory_path: String,
+    cursor: Option<String>,
+    page_size: Option<usize>,
+    options: Option<TreeOptions>,
+) -> AppResult<TreePage> {
+    let normalized_path = platforms::normalize_path(&directory_path);
+    sandbox::ensure_allowed(Path::new(&normalized_path))?;
+    let page_size = page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let mut options = options.unwrap_or_default();
+    // Pages are only stable if the listing has an order
+    options.sort_by.get_or_insert(SortKey::Name);
+
+    let (snapshot_id, offset) = match cursor {
+        Some(cursor) => decode_cursor(&cursor)?,
+        None => (take_snapshot(&normalized_path, options)?, 0),
+    };
+
+    let mut snapshots = PAGE_SNAPSHOTS.lock().map_err(AppError::lock_poisoned)?;
+    // Rebuilding an expired listing could shift entries past the offset, so the caller
+    // starts over instead
+    let snapshot = snapshots
+        .get_mut(&snapshot_id)
+        .filter(|snapshot| snapshot.last_used.elapsed() < PAGE_SNAPSHOT_TTL)
+        .ok_or_else(|| {
+            AppError::invalid_argument("The listing cursor expired; request the first page again.")
+        })?;
+
+    if snapshot.directory != normalized_path {
+        return Err(AppError::invalid_argument(format!(
+            "Cursor does not belong to directory '{}'.",
+            normalized_path
+        )));
+    }
+    snapshot.last_used = Instant::now();
+
+    let total = snapshot.names.len();
+    let end = offset.saturating_add(page_size).min(total);
+    let page_names: Vec<OsString> = snapshot.names.get(offset..end).unwrap_or_default().to_vec();
+    let options = snapshot.options.clone();
+    drop(snapshots);
+    let filter = TreeFilter::new(options)?;
+
+    // Entries removed since the snapshot was taken are skipped
+    let directory = Path::new(&normalized_path);
+    let entries: Vec<NodeDetails> = page_names
+        .iter()
+        .filter_map(|name| node_from_path(&directory.join(name)))
+        .filter_map(|node| filter.mark_ignored(node))
+        .map(|node| filter.add_details(node))
+        .collect();
+
+    let next_cursor = (end < total).then(|| encode_cursor(snapshot_id, end));
+
+    Ok(TreePage {
+        entries,
+        next_cursor,
+        total_estimate: total,
+    })
+}
+
+/// Reads, filters and sorts the directory's entries and stores their names as a new snapshot
+fn take_snapshot(directory: &str, options: TreeOptions) -> AppResult<u64> {
+    let path = Path::new(directory);
+    ensure_directory(path)?;
+    let filter = TreeFilter::new(options)?;
+
+    // The names are kept as the filesystem has them, so names that aren't valid
+    // Unicode can still be found again for their page
+    let entries = fs::read_dir(path)
+        .map_err(|e| AppError::from_io(e, path))?
+        .filter_map(Result::ok);
+    let names: Vec<OsString> = if filter.needs_details() {
+        let mut entries: Vec<(OsString, NodeDetails)> = entries
+            .filter_map(|entry| Some((entry.file_name(), node_from_entry(&entry)?)))
+            .filter(|(_, node)| filter.keeps(node))
+            .collect();
+        if filter.is_ordered() {
+            entries.sort_by(|(_, a), (_, b)| filter.compare(a, b));
+        }
+        entries.into_iter().map(|(name, _)| name).collect()
+    } else {
+        // Name order alone needs nothing but the names
+        let mut names: Vec<OsString> = entries.map(|entry| entry.file_name()).collect();
+        names.sort_by(|a, b| {
+            let ordering =
+                natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()).then_with(|| a.cmp(b));
+            match filter.options().sort_direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+        names
+    };
+
+    let id = NEXT_SNAPSHOT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut snapshots = PAGE_SNAPSHOTS.lock().map_err(AppError::lock_poisoned)?;
+    evict_snapshots(&mut snapshots);
+    snapshots.insert(
+        id,
+        PageSnapshot {
+            directory: directory.to_string(),
+            names,
+            options: filter.options().clone(),
+            last_used: Instant::now(),
+        },
+    );
+
+    Ok(id)
+}
+
+/// Drops expired snapshots and, if still over the limit, the least recently used ones
+fn evict_snapshots(snapshots: &mut HashMap<u64, PageSnapshot>) {
+    snapshots.retain(|_, snapshot| snapshot.last_used.elapsed() < PAGE_SNAPSHOT_TTL);
+
+    while snapshots.len() >= MAX_PAGE_SNAPSHOTS {
+        let oldest = snapshots
+            .iter()
+            .min_by_key(|(_, snapshot)| snapshot.last_used)
+            .map(|(id, _)| *id);
+        match oldest {
+            Some(id) => snapshots.remove(&id),
+            None => break,
+        };
+    }
+}
+
+fn encode_cursor(snapshot_id: u64, offset: usize) -> String {
+    format!("{:x}.{:x}", snapshot_id, offset)
+}
+
+fn decode_cursor(cursor: &str) -> AppResult<(u64, usize)> {
+    let invalid = || AppError::invalid_argument(format!("Invalid listing cursor '{}'.", cursor));
+    let (id, offset) = cursor.split_once('.').ok_or_else(invalid)?;
+    let id = u64::from_str_radix(id, 16).map_err(|_| invalid())?;
+    let offset = usize::from_str_radix(offset, 16).map_err(|_| invalid())?;
+    Ok((id, offset))
+}
@@ -0,0 +1,218 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+use crate::commands::constants::{
+    STREAM_BATCH_INTERVAL, STREAM_BATCH_SIZE, STREAM_PROGRESS_INTERVAL,
+};
+use crate::commands::filesystem_ops::ensure_directory;
+use crate::commands::sandbox;
+use crate::commands::tree::node_from_entry;
+use crate::models::error::{AppError, AppResult};
+use crate::models::pathinfo::{
+    NodeDetails, PathinfoKind, TreeStreamBatch, TreeStreamProgress, TreeStreamSummary,
+};
+use crate::platforms;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::Window;
+use tokio::sync::mpsc;
+
+// Cancellation channels of the walks in flight, keyed by job id
+static STREAM_CANCEL_TX: Lazy<Mutex<HashMap<String, mpsc::Sender<()>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[tauri::command]
+pub async fn cancel_tree_stream(job_id: String) -> AppResult<()> {
+    // Take ownership of the sender and drop the lock immediately
+    let tx = STREAM_CANCEL_TX
+        .lock()
+        .map_err(AppError::lock_poisoned)?
+        .remove(&job_id);
+
+    if let Some(tx) = tx {
+        let _ = tx.send(()).await;
+    }
+    Ok(())
+}
+
+/// Recursively lists a directory, emitting the nodes as they are found
+///
+/// Emits `tree-stream-batch` with batches of flat `NodeDetails` and
+/// `tree-stream-progress` with running counts, both tagged with `job_id`.
+///
+/// Parameters:
+/// - job_id: Caller-chosen id used to tag events and to cancel the walk
+/// - directory_path: The directory to walk
+/// - max_depth: Maximum number of levels to read below `directory_path`
+///
+/// Returns:
+/// - Ok(TreeStreamSummary): Final counts, including directories that couldn't be read
+/// - Err(AppError): If the path isn't a directory, or InvalidArgument if the job id is
+///   already in use
+#[tauri::command]
+pub async fn stream_tree_data(
+    window: Window,
+    job_id: String,
+    directory_path: String,
+    max_depth: Option<usize>,
+) -> AppResult<TreeStreamSummary> {
+    let normalized_path = platforms::normalize_path(&directory_path);
+    sandbox::ensure_allowed(Path::new(&normalized_path))?;
+    ensure_directory(Path::new(&normalized_path))?;
+
+    let (tx, mut rx) = mpsc::channel(1);
+    {
+        let mut channels = STREAM_CANCEL_TX.lock().map_err(AppError::lock_poisoned)?;
+        if channels.contains_key(&job_id) {
+            return Err(AppError::invalid_argument(format!(
+                "A tree stream with id '{}' is already running.",
+                job_id
+            )));
+        }
+        channels.insert(job_id.clone(), tx);
+    }
+
+    let id = job_id.clone();
+    let summary = tokio::task::spawn_blocking(move || {
+        walk_tree_streaming(
+            &id,
+            normalized_path,
+            max_depth,
+            &mut rx,
+            |batch| {
+                let _ = window.emit("tree-stream-batch", batch);
+            },
+            |progress| {
+                let _ = window.emit("tree-stream-progress", progress);
+            },
+        )
+    })
+    .await
+    .map_err(|e| {
+        AppError::internal(format!(
+            "Tree stream '{}' stopped unexpectedly: {}",
+            job_id, e
+        ))
+    });
+
+    if let Ok(mut channels) = STREAM_CANCEL_TX.lock() {
+        channels.remove(&job_id);
+    }
+
+    summary
+}
+
+/// Walks the tree depth-first, handing batches and progress to the callbacks
+/// and checking for cancellation between directories
+pub(crate) fn walk_tree_streaming(
+    job_id: &str,
+    root: String,
+    max_depth: Option<usize>,
+    cancel_rx: &mut mpsc::Receiver<()>,
+    mut on_batch: impl FnMut(TreeStreamBatch),
+    mut on_progress: impl FnMut(TreeStreamProgress),
+) -> TreeStreamSummary {
+    let start_time = Instant::now();
+    let mut last_batch = Instant::now();
+    let mut last_progress = Instant::now();
+    let mut progress = TreeStreamProgress {
+        job_id: job_id.to_string(),
+        ..Default::default()
+    };
+    let mut batch: Vec<NodeDetails> = Vec::new();
+    let mut stack: Vec<(String, usize)> = vec![(root, 1)];
+    let mut cancelled = false;
+
+    while let Some((current_path, depth)) = stack.pop() {
+        if cancel_rx.try_recv().is_ok() {
+            cancelled = true;
+            break;
+        }
+
+        let entries = match fs::read_dir(&current_path) {
+            Ok(entries) => entries,
+            Err(_) => {
+                progress.unreadable_directories += 1;
+                continue;
+            }
+        };
+        progress.directories_read += 1;
+
+        for node in entries
+            .filter_map(Result::ok)
+            .filter_map(|e| node_from_entry(&e))
+        {
+            if node.kind == PathinfoKind::Directory && max_depth.is_none_or(|limit| depth < limit) {
+                stack.push((node.path.clone(), depth + 1));
+            }
+            progress.entries_found += 1;
+            batch.push(node);
+
+            if batch.len() >= STREAM_BATCH_SIZE {
+                flush_batch(job_id, &mut batch, &mut on_batch);
+                last_batch = Instant::now();
+            }
+        }
+
+        if !batch.is_empty() && last_batch.elapsed() >= STREAM_BATCH_INTERVAL {
+            flush_batch(job_id, &mut batch, &mut on_batch);
+            last_batch = Instant::now();
+        }
+
+        if last_progress.elapsed() >= STREAM_PROGRESS_INTERVAL {
+            progress.processing_time_ms = start_time.elapsed().as_millis() as u64;
+            on_progress(progress.clone());
+            last_progress = Instant::now();
+        }
+    }
+
+    // Send any remaining nodes
+    if !batch.is_empty() {
+        flush_batch(job_id, &mut batch, &mut on_batch);
+    }
+
+    TreeStreamSummary {
+        job_id: job_id.to_string(),
+        entries_found: progress.entries_found,
+        directories_read: progress.directories_read,
+        unreadable_directories: progress.unreadable_directories,
+        cancelled,
+        processing_time_ms: start_time.elapsed().as_millis() as u64,
+    }
+}
+
+fn flush_batch(
+    job_id: &str,
+    batch: &mut Vec<NodeDetails>,
+    on_batch: &mut impl FnMut(TreeStreamBatch),
+) {
+    on_batch(TreeStreamBatch {
+        job_id: job_id.to_string(),
+        nodes: std::mem::take(batch),
+    });
+}
@@ -32,27 +32,63 @@ mod search;
 mod notifications;
 mod platforms;
 
+use commands::sandbox::ROOT_DIR_VAR;
 use std::env;
 
 #[tokio::main]
 async fn main() {
-    // Set default CKS_ROOT_DIR if not set
-    if env::var("CKS_ROOT_DIR").is_err() {
+    // Set default CKS_ROOT_DIR if not set. Commands only reach paths inside these
+    // folders; list several the way PATH does.
+    if env::var(ROOT_DIR_VAR).is_err() {
         if let Some(home) = dirs::home_dir() {
-            env::set_var("CKS_ROOT_DIR", home.to_string_lossy().to_string());
+            env::set_var(ROOT_DIR_VAR, home.to_string_lossy().to_string());
         }
     }
 
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
             commands::tree::get_tree_data,
+            commands::tree_page::get_tree_page,
+            commands::tree_stream::stream_tree_data,
+            commands::tree_stream::cancel_tree_stream,
+            commands::disk_usage::analyze_disk_usage,
+            commands::disk_usage::cancel_disk_usage,
             search::search_ops::search_folder,
             search::search_ops::cancel_search,
             commands::filesystem_ops::create_filesystem_item,
+            commands::file_templates::list_file_templates,
             commands::filesystem_ops::delete_file,
             commands::filesystem_ops::delete_folder,
             commands::filesystem_ops::read_file_content,
+            commands::file_write::write_file_content,
+            commands::line_index::open_large_file,
+            commands::line_index::read_file_lines,
+            commands::line_index::close_large_file,
+            commands::move_ops::rename_item,
+            commands::move_ops::move_items,
+            commands::bulk_rename::preview_bulk_rename,
+            commands::bulk_rename::bulk_rename,
+            commands::copy_ops::copy_items,
+            commands::copy_ops::cancel_copy,
+            commands::copy_ops::resolve_copy_conflict,
+            commands::archive_ops::create_archive,
+            commands::archive_ops::extract_archive,
+            commands::archive_ops::cancel_archive,
+            commands::trash_ops::move_to_trash,
+            commands::trash_ops::list_trash,
+            commands::trash_ops::restore_from_trash,
+            commands::trash_ops::empty_trash,
+            commands::journal::undo_operation,
+            commands::journal::redo_operation,
+            commands::journal::get_journal_state,
+            commands::jobs::start_job,
+            commands::jobs::list_jobs,
+            commands::jobs::pause_job,
+            commands::jobs::resume_job,
+            commands::jobs::cancel_job,
             notifications::watch_ops::watch_filesys,
+            notifications::follow_ops::follow_file,
+            notifications::follow_ops::unfollow_file,
             platforms::get_default_paths,
             platforms::mix::get_os_type,
         ])
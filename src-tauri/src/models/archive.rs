@@ -0,0 +1,75 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::models::transfer::ConflictPolicy;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    TarZst,
+}
+
+/// The payload of `archive-progress`
+#[derive(Debug, Serialize, PartialEq, Clone, Default)]
+pub struct ArchiveProgress {
+    pub job_id: String,
+    /// Bytes of the selected files read when creating, and of the archive read when
+    /// extracting
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub entries_done: u64,
+    pub current_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ExtractOptions {
+    /// What to do when a file already exists at the destination; folders are merged.
+    /// Defaults to `Fail`.
+    pub conflict: Option<ConflictPolicy>,
+    /// Stop if the contents add up to more than this many bytes. Defaults to 100
+    /// times the archive's size, and at least 1 GiB.
+    pub max_bytes: Option<u64>,
+}
+
+/// What a finished archive job did
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct ArchiveSummary {
+    pub job_id: String,
+    /// The archive created, or the folder extracted into
+    pub path: String,
+    pub format: ArchiveFormat,
+    pub entries: u64,
+    /// Uncompressed bytes of the files archived or extracted
+    pub bytes: u64,
+    /// Entries left out: links and special files that can't be stored or safely
+    /// recreated, paths leading outside the destination, and existing files kept
+    /// by the conflict policy
+    pub skipped: Vec<String>,
+    pub processing_time_ms: u64,
+}
@@ -0,0 +1,104 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub enum CaseTransform {
+    Lower,
+    Upper,
+    /// The first letter of every word upper case, the rest lower case
+    Title,
+}
+
+/// How to build the new names in a bulk rename. Each rule is applied in order: find and
+/// replace, then the case transform, then the template.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct BulkRenameRules {
+    /// Text or regex to look for in the name; every match is replaced
+    pub find: Option<String>,
+    /// The replacement; with `is_regex`, `$1` or `${name}` insert capture groups
+    pub replace: String,
+    pub is_regex: bool,
+    pub is_case_sensitive: bool,
+    pub case: Option<CaseTransform>,
+    /// Let find/replace and the case transform change the extension too. `{name}` is
+    /// then the whole name and `{ext}` is empty.
+    pub include_extension: bool,
+    /// The new name, built from these tokens (`{{` and `}}` are literal braces):
+    /// - `{name}`: the name without its extension, after find/replace and case
+    /// - `{ext}`: the extension with its dot, or nothing
+    /// - `{parent}`: the name of the containing folder
+    /// - `{n}` or `{n:3}`: the counter, optionally zero-padded to a width
+    /// - `{mtime}`, `{exif}` and `{date}`, optionally with a strftime format such as
+    ///   `{mtime:%Y%m%d}` (default `%Y-%m-%d`): the modification time, the EXIF date a
+    ///   photo was taken (falling back to the modification time), and today
+    ///
+    /// Defaults to `{name}{ext}`.
+    pub template: Option<String>,
+    /// The counter's value for the first item (default 1)
+    pub counter_start: i64,
+    /// Added to the counter for each following item (default 1)
+    pub counter_step: i64,
+}
+
+impl Default for BulkRenameRules {
+    fn default() -> Self {
+        BulkRenameRules {
+            find: None,
+            replace: String::new(),
+            is_regex: false,
+            is_case_sensitive: true,
+            case: None,
+            include_extension: false,
+            template: None,
+            counter_start: 1,
+            counter_step: 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum RenameStatus {
+    Rename,
+    /// The new name is the same as the old one
+    Unchanged,
+    /// The new name is taken, by another item of the batch or by an existing entry
+    Collision,
+    /// The new name is empty, `.` or `..`, or contains a path separator
+    InvalidName,
+}
+
+/// One line of a bulk rename preview
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct RenamePreview {
+    pub source: String,
+    pub new_name: String,
+    pub destination: String,
+    pub status: RenameStatus,
+    /// With `Collision`, the path the new name collides with
+    pub conflict: Option<String>,
+}
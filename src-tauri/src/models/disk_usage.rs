@@ -0,0 +1,66 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
+pub struct DirectoryUsage {
+    pub path: String,
+    /// Sum of file lengths
+    pub apparent_size: u64,
+    /// Space allocated on disk, counting each hard-linked file once
+    pub disk_size: u64,
+    pub file_count: u64,
+    pub directory_count: u64,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct FileUsage {
+    pub path: String,
+    pub apparent_size: u64,
+    pub disk_size: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiskUsageReport {
+    pub job_id: String,
+    pub root: DirectoryUsage,
+    /// Largest files anywhere under the root, biggest first by disk size
+    pub largest_files: Vec<FileUsage>,
+    /// Largest folders under the root (not including it), biggest first by disk size
+    pub largest_directories: Vec<DirectoryUsage>,
+    pub unreadable_entries: usize,
+    pub cancelled: bool,
+    pub from_cache: bool,
+    pub processing_time_ms: u64,
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct DiskUsageProgress {
+    pub job_id: String,
+    pub files_scanned: u64,
+    pub directories_scanned: u64,
+    pub bytes_scanned: u64,
+    pub processing_time_ms: u64,
+}
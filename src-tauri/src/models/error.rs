@@ -0,0 +1,285 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// The error every command returns. It reaches the frontend as
+/// `{ kind, path, code, message }`, so the UI can branch on `kind` and still has
+/// a readable `message` to show.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    NotFound {
+        path: String,
+        code: Option<i32>,
+    },
+    /// Also returned for paths outside the folders the app may reach, set with
+    /// `CKS_ROOT_DIR`
+    PermissionDenied {
+        path: String,
+        code: Option<i32>,
+    },
+    AlreadyExists {
+        path: String,
+        code: Option<i32>,
+    },
+    NotADirectory {
+        path: String,
+        code: Option<i32>,
+    },
+    NotAFile {
+        path: String,
+        code: Option<i32>,
+    },
+    /// A search query or glob that doesn't compile
+    InvalidPattern {
+        pattern: String,
+        reason: String,
+    },
+    /// An argument the command can't act on, such as an unknown item type or a stale cursor
+    InvalidArgument {
+        reason: String,
+    },
+    /// The item is no longer as an earlier operation left it, so that operation can't
+    /// be undone or redone safely
+    Changed {
+        path: String,
+    },
+    /// The file was modified by something else after it was read, so writing would
+    /// overwrite those changes
+    Modified {
+        path: String,
+    },
+    /// The operation was stopped by a cancel request before it finished
+    Cancelled {
+        path: Option<String>,
+    },
+    /// Any other OS-level failure
+    Io {
+        path: String,
+        code: Option<i32>,
+        reason: String,
+    },
+    /// The operation isn't available on this platform
+    Unsupported {
+        reason: String,
+    },
+    /// A failure inside the app itself, e.g. a poisoned lock or a watcher backend error
+    Internal {
+        reason: String,
+    },
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    /// Maps an I/O error on `path` to the matching variant, keeping the OS error code
+    pub fn from_io(err: io::Error, path: impl AsRef<Path>) -> Self {
+        let path = crate::platforms::normalize_path(&path.as_ref().to_string_lossy());
+        let code = err.raw_os_error();
+        match err.kind() {
+            io::ErrorKind::NotFound => AppError::NotFound { path, code },
+            io::ErrorKind::PermissionDenied => AppError::PermissionDenied { path, code },
+            io::ErrorKind::AlreadyExists => AppError::AlreadyExists { path, code },
+            io::ErrorKind::NotADirectory => AppError::NotADirectory { path, code },
+            io::ErrorKind::IsADirectory => AppError::NotAFile { path, code },
+            _ => AppError::Io {
+                path,
+                code,
+                reason: err.to_string(),
+            },
+        }
+    }
+
+    pub fn not_found(path: impl AsRef<Path>) -> Self {
+        AppError::NotFound {
+            path: display_path(path),
+            code: None,
+        }
+    }
+
+    pub fn permission_denied(path: impl AsRef<Path>) -> Self {
+        AppError::PermissionDenied {
+            path: display_path(path),
+            code: None,
+        }
+    }
+
+    pub fn already_exists(path: impl AsRef<Path>) -> Self {
+        AppError::AlreadyExists {
+            path: display_path(path),
+            code: None,
+        }
+    }
+
+    pub fn not_a_directory(path: impl AsRef<Path>) -> Self {
+        AppError::NotADirectory {
+            path: display_path(path),
+            code: None,
+        }
+    }
+
+    pub fn not_a_file(path: impl AsRef<Path>) -> Self {
+        AppError::NotAFile {
+            path: display_path(path),
+            code: None,
+        }
+    }
+
+    pub fn changed(path: impl AsRef<Path>) -> Self {
+        AppError::Changed {
+            path: display_path(path),
+        }
+    }
+
+    pub fn modified(path: impl AsRef<Path>) -> Self {
+        AppError::Modified {
+            path: display_path(path),
+        }
+    }
+
+    pub fn invalid_argument(reason: impl Into<String>) -> Self {
+        AppError::InvalidArgument {
+            reason: reason.into(),
+        }
+    }
+
+    pub fn unsupported(reason: impl Into<String>) -> Self {
+        AppError::Unsupported {
+            reason: reason.into(),
+        }
+    }
+
+    pub fn internal(reason: impl Into<String>) -> Self {
+        AppError::Internal {
+            reason: reason.into(),
+        }
+    }
+
+    /// The failure of `Mutex::lock`, which only happens if another thread panicked
+    pub fn lock_poisoned<T>(_: T) -> Self {
+        AppError::internal("Failed to acquire lock")
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::NotFound { .. } => "NotFound",
+            AppError::PermissionDenied { .. } => "PermissionDenied",
+            AppError::AlreadyExists { .. } => "AlreadyExists",
+            AppError::NotADirectory { .. } => "NotADirectory",
+            AppError::NotAFile { .. } => "NotAFile",
+            AppError::InvalidPattern { .. } => "InvalidPattern",
+            AppError::InvalidArgument { .. } => "InvalidArgument",
+            AppError::Changed { .. } => "Changed",
+            AppError::Modified { .. } => "Modified",
+            AppError::Cancelled { .. } => "Cancelled",
+            AppError::Io { .. } => "Io",
+            AppError::Unsupported { .. } => "Unsupported",
+            AppError::Internal { .. } => "Internal",
+        }
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            AppError::NotFound { path, .. }
+            | AppError::PermissionDenied { path, .. }
+            | AppError::AlreadyExists { path, .. }
+            | AppError::NotADirectory { path, .. }
+            | AppError::NotAFile { path, .. }
+            | AppError::Changed { path }
+            | AppError::Modified { path }
+            | AppError::Io { path, .. } => Some(path),
+            AppError::Cancelled { path } => path.as_deref(),
+            AppError::InvalidPattern { .. }
+            | AppError::InvalidArgument { .. }
+            | AppError::Unsupported { .. }
+            | AppError::Internal { .. } => None,
+        }
+    }
+
+    /// The OS error code, when the error came from the OS
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            AppError::NotFound { code, .. }
+            | AppError::PermissionDenied { code, .. }
+            | AppError::AlreadyExists { code, .. }
+            | AppError::NotADirectory { code, .. }
+            | AppError::NotAFile { code, .. }
+            | AppError::Io { code, .. } => *code,
+            _ => None,
+        }
+    }
+}
+
+fn display_path(path: impl AsRef<Path>) -> String {
+    crate::platforms::normalize_path(&path.as_ref().to_string_lossy())
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NotFound { path, .. } => write!(f, "'{}' does not exist.", path),
+            AppError::PermissionDenied { path, .. } => write!(f, "Permission denied: '{}'.", path),
+            AppError::AlreadyExists { path, .. } => write!(f, "'{}' already exists.", path),
+            AppError::NotADirectory { path, .. } => write!(f, "'{}' is not a folder.", path),
+            AppError::NotAFile { path, .. } => write!(f, "'{}' is not a file.", path),
+            AppError::InvalidPattern { pattern, reason } => {
+                write!(f, "Invalid pattern '{}': {}", pattern, reason)
+            }
+            AppError::InvalidArgument { reason } => f.write_str(reason),
+            AppError::Changed { path } => write!(
+                f,
+                "'{}' has changed since the operation, so it can't be reversed.",
+                path
+            ),
+            AppError::Modified { path } => write!(
+                f,
+                "'{}' was modified since it was read; reload it before saving.",
+                path
+            ),
+            AppError::Cancelled { path: Some(path) } => {
+                write!(f, "The operation on '{}' was cancelled.", path)
+            }
+            AppError::Cancelled { path: None } => f.write_str("The operation was cancelled."),
+            AppError::Io { path, reason, .. } => write!(f, "'{}': {}", path, reason),
+            AppError::Unsupported { reason } | AppError::Internal { reason } => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 4)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("path", &self.path())?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
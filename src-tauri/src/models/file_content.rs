@@ -0,0 +1,78 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    /// Both `\n` and `\r\n` occur
+    Mixed,
+    /// The content has no line breaks
+    None,
+}
+
+/// A file as read for display or editing
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct FileContent {
+    /// The text decoded to UTF-8, or a hex/ASCII dump when `is_binary`
+    pub content: String,
+    /// Identifies the version of the file that was read; pass it back to
+    /// `write_file_content` to refuse the write if the file has changed since
+    pub etag: String,
+    /// The encoding the text was decoded from, e.g. `UTF-8`, `UTF-16LE` or
+    /// `windows-1252`; none for binary files
+    pub encoding: Option<String>,
+    /// Whether the file starts with a byte order mark, which isn't part of `content`
+    pub has_bom: bool,
+    pub line_ending: LineEnding,
+    /// Bytes on disk
+    pub size: u64,
+    /// Only the start of the file is in `content`
+    pub truncated: bool,
+    pub is_binary: bool,
+}
+
+/// How the text being saved was read: the matching fields of its `FileContent`
+#[derive(Debug, Deserialize, PartialEq, Clone, Default)]
+#[serde(default)]
+pub struct ReadFormat {
+    /// The encoding to write in; UTF-8 when none
+    pub encoding: Option<String>,
+    pub has_bom: bool,
+    pub truncated: bool,
+    pub is_binary: bool,
+}
+
+/// What `write_file_content` left on disk
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct WrittenFile {
+    /// The etag of the new content, for the next write
+    pub etag: String,
+    pub size: u64,
+    /// The copy of the previous content, when one was asked for
+    pub backup: Option<String>,
+}
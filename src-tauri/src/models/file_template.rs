@@ -0,0 +1,45 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum TemplateSource {
+    BuiltIn,
+    /// A file in the user's templates folder
+    User,
+}
+
+/// A template new files can be created from
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct FileTemplate {
+    /// What to pass to `create_filesystem_item` to use this template
+    pub name: String,
+    /// Extensions, lower case and without the dot, of the files the template is for
+    pub extensions: Vec<String>,
+    pub source: TemplateSource,
+    /// The template file, for user templates
+    pub path: Option<String>,
+}
@@ -0,0 +1,49 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum FollowChange {
+    /// Lines were added to the end of the file
+    Appended,
+    /// The file got shorter, so it's being read again from the start
+    Truncated,
+    /// A new file replaced the followed one, as when a log is rotated; `lines` ends
+    /// the old file and the new one is read from its start
+    Rotated,
+    /// The file is gone; following goes on when it reappears
+    Missing,
+}
+
+/// The payload of `file-follow`
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct FollowEvent {
+    pub follow_id: String,
+    pub path: String,
+    pub change: FollowChange,
+    /// Complete lines, without their line endings
+    pub lines: Vec<String>,
+}
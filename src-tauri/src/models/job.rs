@@ -0,0 +1,106 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::models::error::AppError;
+use crate::models::transfer::{ConflictPolicy, CopyOptions};
+use serde::{Deserialize, Serialize};
+
+/// A long-running operation to run in the background
+#[derive(Debug, Deserialize, Clone)]
+pub enum JobRequest {
+    Copy {
+        sources: Vec<String>,
+        destination: String,
+        #[serde(default)]
+        options: CopyOptions,
+    },
+    Move {
+        sources: Vec<String>,
+        destination: String,
+        /// Defaults to Fail
+        #[serde(default)]
+        conflict: Option<ConflictPolicy>,
+    },
+    Delete {
+        paths: Vec<String>,
+        /// Defaults to the trash where the platform has one
+        #[serde(default)]
+        permanent: Option<bool>,
+    },
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum JobKind {
+    Copy,
+    Move,
+    Delete,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum JobState {
+    /// Waiting for an earlier job on the same paths, or for a free slot on its device
+    Queued,
+    Running,
+    Paused,
+    /// Done, possibly with errors on some items
+    Completed,
+    /// Stopped by an error that ended the whole job
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            JobState::Completed | JobState::Failed | JobState::Cancelled
+        )
+    }
+}
+
+/// A job as reported by `list_jobs` and the `job-progress` event
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct JobInfo {
+    pub job_id: String,
+    pub kind: JobKind,
+    pub state: JobState,
+    pub sources: Vec<String>,
+    pub destination: Option<String>,
+    /// Files for copies, top-level items for moves and trash deletes, and every entry
+    /// for permanent deletes
+    pub items_done: u64,
+    pub items_total: u64,
+    /// Only counted by copies
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub current_path: Option<String>,
+    pub skipped: Vec<String>,
+    /// Items that failed; the job carried on without them unless it `Failed`
+    pub errors: Vec<AppError>,
+    /// Milliseconds since the Unix epoch
+    pub created_at: u128,
+    pub started_at: Option<u128>,
+    pub finished_at: Option<u128>,
+}
@@ -0,0 +1,55 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum OperationKind {
+    Create,
+    /// A delete that moved the items to the trash; permanent deletes can't be undone
+    Trash,
+    Rename,
+    Move,
+    Copy,
+}
+
+/// An operation in the undo journal
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct OperationSummary {
+    pub id: u64,
+    pub kind: OperationKind,
+    /// The items the operation produced: the created item, the trashed item's original
+    /// path, the moved item's new path or the copy
+    pub paths: Vec<String>,
+    /// Milliseconds since the Unix epoch
+    pub recorded_at: u128,
+}
+
+/// What undo and redo would act on next
+#[derive(Debug, Serialize, PartialEq, Clone, Default)]
+pub struct JournalState {
+    pub undo: Option<OperationSummary>,
+    pub redo: Option<OperationSummary>,
+}
@@ -0,0 +1,58 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use serde::Serialize;
+
+/// How far indexing the lines of a large file has got; the payload of
+/// `line-index-progress`
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct LineIndexStatus {
+    pub path: String,
+    /// The version of the file being indexed, as in `FileContent`
+    pub etag: String,
+    /// Bytes on disk when indexing started; lines added later aren't counted
+    pub size: u64,
+    /// Lines found so far
+    pub indexed_lines: u64,
+    /// Set once indexing has finished
+    pub total_lines: Option<u64>,
+    pub encoding: String,
+}
+
+/// A window of lines from a large file
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct LinePage {
+    pub path: String,
+    pub etag: String,
+    /// The number of the first line in `lines`, counting from 0
+    pub start_line: u64,
+    /// The lines without their line endings. Fewer than asked for at the end of the
+    /// file, or past what has been indexed so far.
+    pub lines: Vec<String>,
+    pub indexed_lines: u64,
+    pub total_lines: Option<u64>,
+    /// Some lines were too long and only their start is included
+    pub clipped: bool,
+}
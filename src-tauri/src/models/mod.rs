@@ -22,4 +22,17 @@
 //
 // The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
 // for providing inspiration for this project.
+pub mod archive;
+pub mod bulk_rename;
+pub mod disk_usage;
+pub mod error;
+pub mod file_content;
+pub mod file_template;
+pub mod follow;
+pub mod job;
+pub mod journal;
+pub mod line_index;
 pub mod pathinfo;
+pub mod transfer;
+pub mod trash;
+pub mod tree_options;
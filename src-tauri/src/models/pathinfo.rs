@@ -28,16 +28,130 @@ use serde::Serialize;
 pub enum PathinfoKind {
     Directory,
     File,
+    Symlink,
 }
 
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct SymlinkDetails {
+    /// The link's contents as stored, which may be relative to the link's folder
+    pub target: String,
+    /// The target doesn't exist (or can't be reached)
+    pub broken: bool,
+    pub points_to_directory: bool,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
 pub struct NodeDetails {
     pub name: String,
     pub path: String,
     pub kind: PathinfoKind,
     pub hidden: bool,
+    /// Set when a gitignore-aware listing matched the entry against the ignore rules
+    pub ignored: bool,
     pub size: u64,
     pub created: u128,
     pub modified: u128,
+    /// Present when `kind` is `Symlink`
+    pub symlink: Option<SymlinkDetails>,
+    /// Present when extended metadata was requested on a Unix system
+    pub unix: Option<UnixMetadata>,
+    /// Present when content types were requested, for regular files and links to them
+    pub content_type: Option<ContentType>,
+    /// Present when git status was requested and the entry lives inside a working tree
+    pub git_status: Option<GitStatus>,
+    /// `None` until a directory's contents have been read; `Some(vec![])` is an empty directory
     pub children: Option<Vec<NodeDetails>>,
 }
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum FileCategory {
+    SourceCode,
+    Image,
+    Archive,
+    Document,
+    Binary,
+    Text,
+}
+
+/// What a file holds, judged from its first bytes and its name
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct ContentType {
+    pub mime: String,
+    pub category: FileCategory,
+    /// Programming or markup language, when the file is source code
+    pub language: Option<String>,
+    /// The content isn't text, whatever the name says
+    pub binary: bool,
+}
+
+/// A folder reports the most pressing status among its contents, in the order
+/// Conflicted, Modified, Staged, Untracked, Clean
+#[derive(Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum GitStatus {
+    Clean,
+    Ignored,
+    Untracked,
+    Staged,
+    Modified,
+    Conflicted,
+}
+
+/// Unix-only details, filled in when a listing asks for extended metadata
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct UnixMetadata {
+    /// Permission bits rendered as `rwxr-xr-x`
+    pub permissions: String,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub inode: u64,
+    pub hard_links: u64,
+    pub device: u64,
+    pub accessed: u128,
+}
+
+impl NodeDetails {
+    /// A directory, or a symlink that resolves to one
+    pub fn is_directory_like(&self) -> bool {
+        self.kind == PathinfoKind::Directory
+            || self
+                .symlink
+                .as_ref()
+                .is_some_and(|link| link.points_to_directory)
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TreePage {
+    pub entries: Vec<NodeDetails>,
+    /// Pass back to fetch the following page; `None` on the last page
+    pub next_cursor: Option<String>,
+    pub total_estimate: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TreeStreamBatch {
+    pub job_id: String,
+    pub nodes: Vec<NodeDetails>,
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct TreeStreamProgress {
+    pub job_id: String,
+    pub entries_found: usize,
+    pub directories_read: usize,
+    pub unreadable_directories: usize,
+    pub processing_time_ms: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TreeStreamSummary {
+    pub job_id: String,
+    pub entries_found: usize,
+    pub directories_read: usize,
+    pub unreadable_directories: usize,
+    pub cancelled: bool,
+    pub processing_time_ms: u64,
+}
@@ -0,0 +1,125 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::models::error::AppError;
+use serde::{Deserialize, Serialize};
+
+/// What to do when the destination of a move or copy already exists
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub enum ConflictPolicy {
+    /// Stop with an AlreadyExists error
+    #[default]
+    Fail,
+    /// Replace the existing item
+    Overwrite,
+    /// Keep the existing item and give the new one a numeric suffix, e.g. `notes (1).txt`
+    KeepBoth,
+    /// Leave both untouched and move on
+    Skip,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum MoveOutcome {
+    Moved,
+    /// Moved over an existing item under `Overwrite`, which is gone for good
+    Replaced,
+    Skipped,
+    Failed,
+}
+
+/// The result of moving one item
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct MoveResult {
+    pub source: String,
+    /// Where the item ended up, or would have with `Skipped` and `Failed`
+    pub destination: String,
+    pub outcome: MoveOutcome,
+    /// Set when `outcome` is `Failed`
+    pub error: Option<AppError>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CopyOptions {
+    /// Recreate symlinks as links instead of copying what they point to
+    pub preserve_symlinks: bool,
+    /// Resolve every conflict this way instead of asking
+    pub conflict: Option<ConflictPolicy>,
+}
+
+/// Sent with `copy-conflict` when the destination of an item already exists
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct CopyConflict {
+    pub job_id: String,
+    pub source: String,
+    pub destination: String,
+    pub source_is_dir: bool,
+    pub destination_is_dir: bool,
+}
+
+/// The answer to a `copy-conflict`. `Overwrite` of a folder by a folder merges them,
+/// asking again for each file that exists on both sides; `Fail` stops the copy.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct ConflictResolution {
+    pub policy: ConflictPolicy,
+    /// Use the same answer for the rest of the job
+    #[serde(default)]
+    pub apply_to_all: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CopyProgress {
+    pub job_id: String,
+    pub bytes_copied: u64,
+    pub total_bytes: u64,
+    pub files_copied: u64,
+    pub total_files: u64,
+    /// The file being copied
+    pub current_path: Option<String>,
+    pub processing_time_ms: u64,
+}
+
+/// A top-level item a copy created
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct CopiedItem {
+    pub source: String,
+    pub destination: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CopySummary {
+    pub job_id: String,
+    pub bytes_copied: u64,
+    pub files_copied: u64,
+    /// Items left alone because of a conflict
+    pub skipped: Vec<String>,
+    /// New items at the destination. Items merged into or replacing existing ones
+    /// aren't listed.
+    pub copied: Vec<CopiedItem>,
+    /// Items that couldn't be copied; the rest of the job carried on
+    pub errors: Vec<AppError>,
+    pub cancelled: bool,
+    pub processing_time_ms: u64,
+}
@@ -0,0 +1,43 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use serde::Serialize;
+
+/// An item in a trash folder
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct TrashItem {
+    /// The item's `.trashinfo` file, which identifies it for restore and empty
+    pub id: String,
+    pub name: String,
+    /// Where the item was when it was trashed, and where a restore puts it back
+    pub original_path: String,
+    /// Local time as written in the `.trashinfo` file, e.g. `2025-03-01T14:05:09`
+    pub deletion_date: Option<String>,
+    /// The trash folder holding the item: the home trash or one on another volume
+    pub trash_dir: String,
+    pub is_dir: bool,
+    /// Bytes for files; folders aren't measured
+    pub size: Option<u64>,
+}
@@ -0,0 +1,95 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy)]
+pub enum SortKey {
+    Name,
+    Size,
+    Modified,
+    Created,
+    Extension,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy, Default)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// How entries ignored by git are treated in a listing
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy, Default)]
+pub enum GitignoreMode {
+    #[default]
+    Off,
+    /// Leave ignored entries out
+    Hide,
+    /// Keep ignored entries but mark them with `ignored`
+    Flag,
+}
+
+/// Sorting and filtering applied to listings before they are returned
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct TreeOptions {
+    /// `None` keeps the order the filesystem returns entries in
+    pub sort_by: Option<SortKey>,
+    pub sort_direction: SortDirection,
+    pub folders_first: bool,
+    pub show_hidden: bool,
+    /// Glob patterns matched against file names; folders are always kept so they can be browsed
+    pub include: Vec<String>,
+    /// Glob patterns matched against file and folder names; excluded folders are not descended into
+    pub exclude: Vec<String>,
+    /// Ignored folders are never descended into, whichever mode hides or flags them
+    pub gitignore: GitignoreMode,
+    /// Descend into symlinked folders when recursing; each folder is still read only once
+    pub follow_symlinks: bool,
+    /// Fill in `NodeDetails::unix` (mode, owner, inode, ...); costs an extra lookup per entry
+    pub extended_metadata: bool,
+    /// Fill in `NodeDetails::content_type` for regular files; reads the start of each one
+    pub content_types: bool,
+    /// Fill in `NodeDetails::git_status`; reads the status of each working tree listed
+    pub git_status: bool,
+}
+
+impl Default for TreeOptions {
+    fn default() -> Self {
+        TreeOptions {
+            sort_by: None,
+            sort_direction: SortDirection::Ascending,
+            folders_first: false,
+            show_hidden: true,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            gitignore: GitignoreMode::Off,
+            follow_symlinks: false,
+            extended_metadata: false,
+            content_types: false,
+            git_status: false,
+        }
+    }
+}
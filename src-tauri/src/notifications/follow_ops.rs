@@ -0,0 +1,453 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+use crate::commands::constants::{
+    ENCODING_SNIFF_LEN, FOLLOW_BACKFILL_CHUNK, FOLLOW_MAX_READ, FOLLOW_POLL_INTERVAL,
+    MAX_FOLLOW_BACKFILL, MAX_PAGE_LINE_LEN,
+};
+use crate::commands::file_read::{decode_with, sniff_encoding};
+use crate::commands::filesystem_ops::ensure_file;
+use crate::commands::sandbox;
+use crate::models::error::{AppError, AppResult};
+use crate::models::follow::{FollowChange, FollowEvent};
+use crate::platforms;
+use encoding_rs::Encoding;
+use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tauri::Window;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// A file being followed, and how to stop following it
+struct Following {
+    stop: Arc<AtomicBool>,
+    wake: mpsc::Sender<()>,
+    /// Kept alive for as long as the file is followed; none if the watch couldn't be
+    /// set up and the file is only polled
+    _watcher: Option<RecommendedWatcher>,
+}
+
+// Files being followed, keyed by follow id
+static FOLLOWERS: Lazy<Mutex<HashMap<String, Following>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Follows a growing file the way `tail -F` does
+///
+/// Lines added to the file are emitted as `file-follow` events tagged with
+/// `follow_id`. When the file is truncated it's read again from the start, and when
+/// it's replaced by a new file (log rotation) the rest of the old file is read before
+/// the new one. If the file disappears, following resumes when it comes back. A line
+/// is only sent once its line break has been written.
+///
+/// Parameters:
+/// - follow_id: Caller-chosen id used to tag events and to stop following
+/// - file_path: The file to follow
+/// - backfill_lines: How many of the file's last lines to return (default 0, at
+///   most 10,000)
+///
+/// Returns:
+/// - Ok(Vec<String>): The last `backfill_lines` complete lines
+/// - Err(AppError): NotFound or NotAFile if the path isn't an existing file,
+///   InvalidArgument if it isn't text or the follow id is in use, Unsupported for
+///   UTF-16 text
+#[tauri::command]
+pub async fn follow_file(
+    window: Window,
+    follow_id: String,
+    file_path: String,
+    backfill_lines: Option<usize>,
+) -> AppResult<Vec<String>> {
+    let normalized_path = platforms::normalize_path(&file_path);
+    let path = PathBuf::from(&normalized_path);
+    sandbox::ensure_allowed(&path)?;
+
+    // The id is taken before the file is opened, so a second follow with it fails
+    // right away and an unfollow in between stops this one
+    let (wake, woken) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let mut followers = FOLLOWERS.lock().map_err(AppError::lock_poisoned)?;
+        if followers.contains_key(&follow_id) {
+            return Err(AppError::invalid_argument(format!(
+                "A follow with id '{}' is already running.",
+                follow_id
+            )));
+        }
+        followers.insert(
+            follow_id.clone(),
+            Following {
+                stop: stop.clone(),
+                wake: wake.clone(),
+                _watcher: None,
+            },
+        );
+    }
+    let is_ours = |following: &Following| Arc::ptr_eq(&following.stop, &stop);
+
+    // Reading back through a big file for the last lines can take a while
+    let backfill = backfill_lines.unwrap_or(0).min(MAX_FOLLOW_BACKFILL);
+    let opened = tokio::task::spawn_blocking(move || {
+        let (follower, lines) = Follower::open(&path, backfill)?;
+        let watcher = watch_parent(&path, wake);
+        Ok((follower, lines, watcher))
+    })
+    .await
+    .unwrap_or_else(|e| Err(AppError::internal(format!("Following stopped: {}", e))));
+
+    let mut followers = FOLLOWERS.lock().map_err(AppError::lock_poisoned)?;
+    let (mut follower, lines) = match opened {
+        Ok((follower, lines, watcher)) => {
+            if let Some(following) = followers.get_mut(&follow_id).filter(|f| is_ours(f)) {
+                following._watcher = watcher;
+            }
+            (follower, lines)
+        }
+        Err(e) => {
+            if followers.get(&follow_id).is_some_and(is_ours) {
+                followers.remove(&follow_id);
+            }
+            return Err(e);
+        }
+    };
+    drop(followers);
+
+    thread::spawn(move || {
+        let mut more = false;
+        loop {
+            // Read on without waiting while a burst of output is being caught up on
+            if !more
+                && woken.recv_timeout(FOLLOW_POLL_INTERVAL) == Err(RecvTimeoutError::Disconnected)
+            {
+                break;
+            }
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let changes;
+            (changes, more) = follower.poll();
+            for (change, lines) in changes {
+                let event = FollowEvent {
+                    follow_id: follow_id.clone(),
+                    path: normalized_path.clone(),
+                    change,
+                    lines,
+                };
+                if window.emit("file-follow", event).is_err() {
+                    // The window is gone, so nobody is listening any more
+                    stop.store(true, Ordering::Relaxed);
+                }
+            }
+        }
+        if let Ok(mut followers) = FOLLOWERS.lock() {
+            if followers
+                .get(&follow_id)
+                .is_some_and(|following| Arc::ptr_eq(&following.stop, &stop))
+            {
+                followers.remove(&follow_id);
+            }
+        }
+    });
+
+    Ok(lines)
+}
+
+/// Stops following a file; unknown ids are ignored
+///
+/// Parameters:
+/// - follow_id: The id passed to `follow_file`
+#[tauri::command]
+pub async fn unfollow_file(follow_id: String) -> AppResult<()> {
+    let following = FOLLOWERS
+        .lock()
+        .map_err(AppError::lock_poisoned)?
+        .remove(&follow_id);
+    if let Some(following) = following {
+        following.stop.store(true, Ordering::Relaxed);
+        let _ = following.wake.send(());
+    }
+    Ok(())
+}
+
+/// Watches the folder holding `path`, which also sees the file being replaced, and
+/// wakes the follower on any change to an entry of that name
+fn watch_parent(path: &Path, wake: mpsc::Sender<()>) -> Option<RecommendedWatcher> {
+    let name = path.file_name()?.to_os_string();
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let watcher = RecommendedWatcher::new(
+        move |res: Result<notify::Event, notify::Error>| {
+            if let Ok(event) = res {
+                if event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name() == Some(name.as_os_str()))
+                {
+                    let _ = wake.send(());
+                }
+            }
+        },
+        Config::default(),
+    )
+    .and_then(|mut watcher| {
+        watcher.watch(parent, RecursiveMode::NonRecursive)?;
+        Ok(watcher)
+    });
+    match watcher {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            log::warn!("Can't watch {:?}, polling it instead: {}", parent, e);
+            None
+        }
+    }
+}
+
+/// Reads what's added to a file, noticing when it's truncated or replaced
+pub(crate) struct Follower {
+    path: PathBuf,
+    /// None while the file is missing
+    file: Option<File>,
+    identity: Option<(u64, u64)>,
+    /// How far `file` has been read
+    position: u64,
+    /// The start of a line whose line break hasn't been written yet
+    pending: Vec<u8>,
+    encoding: &'static Encoding,
+}
+
+impl Follower {
+    /// Starts following the file at its current end, returning its last `backfill`
+    /// complete lines
+    pub(crate) fn open(path: &Path, backfill: usize) -> AppResult<(Self, Vec<String>)> {
+        ensure_file(path)?;
+        let io_error = |e| AppError::from_io(e, path);
+        let mut file = File::open(path).map_err(io_error)?;
+        let metadata = file.metadata().map_err(io_error)?;
+        let size = metadata.len();
+
+        let mut sample = Vec::with_capacity(ENCODING_SNIFF_LEN);
+        (&mut file)
+            .take(ENCODING_SNIFF_LEN as u64)
+            .read_to_end(&mut sample)
+            .map_err(io_error)?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let (encoding, _) = sniff_encoding(&name, &sample, (sample.len() as u64) < size)
+            .ok_or_else(|| {
+                AppError::invalid_argument(format!(
+                    "'{}' isn't a text file.",
+                    platforms::normalize_path(&path.to_string_lossy())
+                ))
+            })?;
+        if !encoding.is_ascii_compatible() {
+            return Err(AppError::unsupported(format!(
+                "Following {} files isn't supported.",
+                encoding.name()
+            )));
+        }
+
+        let mut follower = Follower {
+            path: path.to_path_buf(),
+            file: None,
+            identity: identity(&metadata),
+            position: size,
+            pending: Vec::new(),
+            encoding,
+        };
+        let mut tail = last_bytes(&mut file, size, backfill).map_err(io_error)?;
+        if tail.len() as u64 == size && tail.starts_with(UTF8_BOM) {
+            tail.drain(..UTF8_BOM.len());
+        }
+        let skip_first = (tail.len() as u64) < size;
+        follower.pending = tail;
+        let mut lines = follower.complete_lines();
+        if skip_first && !lines.is_empty() {
+            // Only the end of that line was read
+            lines.remove(0);
+        }
+        let keep_from = lines.len().saturating_sub(backfill);
+        lines.drain(..keep_from);
+
+        follower.file = Some(file);
+        Ok((follower, lines))
+    }
+
+    /// Checks the file for changes, returning them, and whether there's more to read
+    /// already
+    pub(crate) fn poll(&mut self) -> (Vec<(FollowChange, Vec<String>)>, bool) {
+        let mut changes = Vec::new();
+        let current = fs::metadata(&self.path).ok();
+        let replaced = match (&current, &self.file) {
+            (None, None) => return (changes, false),
+            (Some(metadata), Some(_)) => identity(metadata) != self.identity,
+            _ => true,
+        };
+
+        if !replaced {
+            let size = current.map_or(0, |metadata| metadata.len());
+            if size < self.position {
+                self.position = 0;
+                self.pending.clear();
+                changes.push((FollowChange::Truncated, Vec::new()));
+            }
+            let (lines, more) = self.read_more();
+            if !lines.is_empty() {
+                changes.push((FollowChange::Appended, lines));
+            }
+            return (changes, more);
+        }
+
+        let mut last_lines = Vec::new();
+        if self.file.is_some() {
+            // The old file can still be read through the open handle, so finish it first
+            let (lines, more) = self.read_more();
+            if more {
+                return (vec![(FollowChange::Appended, lines)], true);
+            }
+            last_lines = lines;
+            if !self.pending.is_empty() {
+                let rest = std::mem::take(&mut self.pending);
+                last_lines.push(self.decode_line(&rest));
+            }
+            self.file = None;
+        }
+
+        match File::open(&self.path) {
+            Ok(file) => {
+                self.identity = file.metadata().ok().as_ref().and_then(identity);
+                self.file = Some(file);
+                self.position = 0;
+                changes.push((FollowChange::Rotated, last_lines));
+                let (lines, more) = self.read_more();
+                if !lines.is_empty() {
+                    changes.push((FollowChange::Appended, lines));
+                }
+                (changes, more)
+            }
+            Err(_) => {
+                changes.push((FollowChange::Missing, last_lines));
+                (changes, false)
+            }
+        }
+    }
+
+    /// Reads up to `FOLLOW_MAX_READ` bytes past `position`, returning the lines they
+    /// complete, and whether the file goes on
+    fn read_more(&mut self) -> (Vec<String>, bool) {
+        let Some(file) = self.file.as_mut() else {
+            return (Vec::new(), false);
+        };
+        let mut bytes = Vec::new();
+        let read = file
+            .seek(SeekFrom::Start(self.position))
+            .and_then(|_| file.take(FOLLOW_MAX_READ).read_to_end(&mut bytes));
+        if let Err(e) = read {
+            log::warn!("Reading followed file {:?} failed: {}", self.path, e);
+            return (Vec::new(), false);
+        }
+
+        if self.position == 0 && bytes.starts_with(UTF8_BOM) {
+            bytes.drain(..UTF8_BOM.len());
+            self.position += UTF8_BOM.len() as u64;
+        }
+        self.position += bytes.len() as u64;
+        let more = bytes.len() as u64 >= FOLLOW_MAX_READ;
+        self.pending.extend_from_slice(&bytes);
+
+        let mut lines = self.complete_lines();
+        // A line that never ends is sent in pieces rather than held forever
+        if self.pending.len() > MAX_PAGE_LINE_LEN {
+            let piece = std::mem::take(&mut self.pending);
+            lines.push(self.decode_line(&piece));
+        }
+        (lines, more)
+    }
+
+    /// Takes the lines that have their line break out of `pending`
+    fn complete_lines(&mut self) -> Vec<String> {
+        let Some(last_break) = memchr::memrchr(b'\n', &self.pending) else {
+            return Vec::new();
+        };
+        let complete: Vec<u8> = self.pending.drain(..=last_break).collect();
+        complete[..last_break]
+            .split(|byte| *byte == b'\n')
+            .map(|line| self.decode_line(line))
+            .collect()
+    }
+
+    fn decode_line(&self, line: &[u8]) -> String {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        decode_with(self.encoding, line, true)
+    }
+}
+
+/// The end of the file, reading back until it holds `lines` complete lines and the
+/// break before them, the whole file, or as much as that many lines may take
+fn last_bytes(file: &mut File, size: u64, lines: usize) -> std::io::Result<Vec<u8>> {
+    let limit = (lines as u64 + 1) * MAX_PAGE_LINE_LEN as u64;
+    let mut tail = Vec::new();
+    let mut start = size;
+    while start > 0 && (tail.len() as u64) < limit {
+        let chunk_start = start.saturating_sub(FOLLOW_BACKFILL_CHUNK);
+        let mut chunk = Vec::with_capacity((start - chunk_start) as usize);
+        file.seek(SeekFrom::Start(chunk_start))?;
+        (&mut *file)
+            .take(start - chunk_start)
+            .read_to_end(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        start = chunk_start;
+        if memchr::memchr_iter(b'\n', &tail).count() > lines {
+            break;
+        }
+    }
+    Ok(tail)
+}
+
+/// What tells a file from the one that replaces it at the same path
+fn identity(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some((metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
@@ -0,0 +1,92 @@
+use crate::models::follow::FollowChange;
+use crate::notifications::follow_ops::Follower;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use tempfile::TempDir;
+
+fn append(path: &std::path::Path, text: &str) {
+    let mut file = OpenOptions::new().append(true).open(path).unwrap();
+    file.write_all(text.as_bytes()).unwrap();
+}
+
+fn lines(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+#[test]
+fn follow_file_should_backfill_and_stream_appended_lines() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("build.log");
+    fs::write(&path, "one\ntwo\r\nthree\npart").unwrap();
+
+    let (mut follower, backfill) = Follower::open(&path, 2).unwrap();
+    assert_eq!(backfill, lines(&["two", "three"]));
+    assert_eq!(follower.poll(), (Vec::new(), false));
+
+    // The held back start of a line is sent once its line break arrives
+    append(&path, "ial\nfour\nfi");
+    let (changes, more) = follower.poll();
+    assert!(!more);
+    assert_eq!(
+        changes,
+        vec![(FollowChange::Appended, lines(&["partial", "four"]))]
+    );
+}
+
+#[test]
+fn follow_file_should_restart_after_truncation() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("service.log");
+    fs::write(&path, "old line one\nold line two\n").unwrap();
+
+    let (mut follower, backfill) = Follower::open(&path, 0).unwrap();
+    assert!(backfill.is_empty());
+
+    fs::write(&path, "fresh\n").unwrap();
+    let (changes, _) = follower.poll();
+    assert_eq!(
+        changes,
+        vec![
+            (FollowChange::Truncated, Vec::new()),
+            (FollowChange::Appended, lines(&["fresh"])),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn follow_file_should_handle_rotation_and_missing_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("app.log");
+    fs::write(&path, "start\n").unwrap();
+    let (mut follower, _) = Follower::open(&path, 0).unwrap();
+
+    // Rotate: the last lines go to the old file, which is renamed away
+    append(&path, "last old\nunfinished");
+    fs::rename(&path, temp_dir.path().join("app.log.1")).unwrap();
+    fs::write(&path, "first new\n").unwrap();
+
+    let (changes, _) = follower.poll();
+    assert_eq!(
+        changes,
+        vec![
+            (FollowChange::Rotated, lines(&["last old", "unfinished"])),
+            (FollowChange::Appended, lines(&["first new"])),
+        ]
+    );
+
+    fs::remove_file(&path).unwrap();
+    let (changes, _) = follower.poll();
+    assert_eq!(changes, vec![(FollowChange::Missing, Vec::new())]);
+    assert_eq!(follower.poll(), (Vec::new(), false));
+
+    fs::write(&path, "back\n").unwrap();
+    let (changes, _) = follower.poll();
+    assert_eq!(
+        changes,
+        vec![
+            (FollowChange::Rotated, Vec::new()),
+            (FollowChange::Appended, lines(&["back"])),
+        ]
+    );
+}
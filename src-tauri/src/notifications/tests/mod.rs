@@ -1,4 +1,5 @@
 pub mod common;
+pub mod follow_file;
 pub mod watch_all;
 pub mod watch_file_creation;
 pub mod watch_file_deletion;
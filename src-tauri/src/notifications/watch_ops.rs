@@ -29,6 +29,8 @@ use std::sync::Mutex;
 use tauri::Runtime;
 use tauri::Manager;
 use serde::{Deserialize, Serialize};
+use crate::commands::{disk_usage, git_status, sandbox};
+use crate::models::error::{AppError, AppResult};
 use crate::platforms;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -48,16 +50,26 @@ pub async fn watch_filesys<R: Runtime>(
     app_handle: tauri::AppHandle<R>,
     path: String,
     target: Option<WatchTarget>,  // Make target optional
-) -> Result<(), String> {
+) -> AppResult<()> {
     println!("Setting up watcher for path: {:?}", path);
     let app_handle_clone = app_handle.clone();
 
     // Normalize the path for the current platform
     let normalized_path = platforms::normalize_path(&path);
+    sandbox::ensure_allowed(Path::new(&normalized_path))?;
 
     let mut watcher: RecommendedWatcher = Watcher::new(
         move |res: Result<notify::Event, notify::Error>| {
             if let Ok(event) = res {
+                // Any change under a scanned folder makes its cached size stale
+                disk_usage::invalidate_cached_usage(&event.paths);
+                for workdir in git_status::invalidate_git_status(&event.paths) {
+                    let _ = app_handle_clone.emit_all(
+                        "git-status-changed",
+                        platforms::normalize_path(&workdir.to_string_lossy()),
+                    );
+                }
+
                 let emit_event = |event_type: &str, path: String| {
                     // Normalize the path for the current platform
                     let normalized_path = platforms::normalize_path(&path);
@@ -122,16 +134,25 @@ pub async fn watch_filesys<R: Runtime>(
         },
         Config::default(),
     )
-    .map_err(|e| e.to_string())?;
+    .map_err(|e| watch_error(e, &normalized_path))?;
 
     // Watch the path
     watcher
         .watch(Path::new(&normalized_path), RecursiveMode::Recursive)
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| watch_error(e, &normalized_path))?;
 
     // Store watcher in app state to keep it alive
     let watchers = app_handle.state::<Mutex<Vec<RecommendedWatcher>>>();
     watchers.lock().unwrap().push(watcher);
 
     Ok(())
-} 
\ No newline at end of file
+} 
+
+// Maps a notify failure to the app's error, keeping the OS error when there is one
+fn watch_error(err: notify::Error, path: &str) -> AppError {
+    match err.kind {
+        notify::ErrorKind::Io(io_err) => AppError::from_io(io_err, path),
+        notify::ErrorKind::PathNotFound => AppError::not_found(path),
+        kind => AppError::internal(format!("Failed to watch '{}': {:?}", path, kind)),
+    }
+}
@@ -34,6 +34,12 @@ pub mod windows;
 
 pub mod mix;
 
+#[cfg(all(unix, not(target_os = "macos")))]
+pub mod trash;
+
+#[cfg(unix)]
+pub mod unix;
+
 /// Platform-independent way to check if a path is hidden
 pub fn is_hidden(path: &Path) -> bool {
     path.file_name()
@@ -42,6 +48,106 @@ pub fn is_hidden(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Identifies a file or directory independently of the path used to reach it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FileIdentity {
+    Inode { device: u64, inode: u64 },
+    #[cfg(not(unix))]
+    Canonical(std::path::PathBuf),
+}
+
+/// Identity of the file at `path`, following symlinks
+pub fn file_identity(path: &Path) -> Option<FileIdentity> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(FileIdentity::Inode {
+            device: metadata.dev(),
+            inode: metadata.ino(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    {
+        // Without inode numbers, the resolved path is the best stable identity
+        std::fs::canonicalize(path)
+            .ok()
+            .map(FileIdentity::Canonical)
+    }
+}
+
+/// Bytes actually allocated on disk for a file, which differs from its length for
+/// sparse or compressed files
+pub fn allocated_size(metadata: &std::fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // st_blocks is always in 512-byte units
+        metadata.blocks() * 512
+    }
+
+    #[cfg(not(unix))]
+    {
+        metadata.len()
+    }
+}
+
+/// Identity of a file with more than one hard link, so it can be counted once
+pub fn hard_link_identity(metadata: &std::fs::Metadata) -> Option<FileIdentity> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        (metadata.nlink() > 1).then(|| FileIdentity::Inode {
+            device: metadata.dev(),
+            inode: metadata.ino(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// The device (filesystem) holding `path`, or its nearest existing ancestor when `path`
+/// doesn't exist yet. `None` where the platform doesn't expose device numbers.
+pub fn device_id(path: &Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        path.ancestors()
+            .find_map(|ancestor| std::fs::symlink_metadata(ancestor).ok())
+            .map(|metadata| metadata.dev())
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Creates a symlink at `link` pointing to `target`. Windows needs to know whether the
+/// target is a folder; elsewhere `target_is_dir` is ignored.
+pub fn create_symlink(target: &Path, link: &Path, target_is_dir: bool) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        let _ = target_is_dir;
+        std::os::unix::fs::symlink(target, link)
+    }
+
+    #[cfg(windows)]
+    {
+        if target_is_dir {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        }
+    }
+}
+
 /// Normalize a path to the current platform's format
 pub fn normalize_path(path: &str) -> String {
     #[cfg(target_os = "windows")]
@@ -0,0 +1,521 @@
+// MIT License
+//
+// Copyright (c) 2025 Basab Dattaray
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+// The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
+// for providing inspiration for this project.
+
+//! The freedesktop.org Trash specification: a home trash under `$XDG_DATA_HOME/Trash`
+//! and a `.Trash/$uid` or `.Trash-$uid` folder at the top of other volumes. Each
+//! trash holds the items in `files/` and a `.trashinfo` file per item in `info/`.
+
+use crate::commands::{move_ops, sandbox};
+use crate::models::error::{AppError, AppResult};
+use crate::models::transfer::ConflictPolicy;
+use crate::models::trash::TrashItem;
+use crate::platforms;
+use chrono::{DateTime, Local};
+use percent_encoding::{percent_decode, percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const INFO_EXTENSION: &str = "trashinfo";
+const INFO_HEADER: &str = "[Trash Info]";
+const DIRECTORY_SIZES: &str = "directorysizes";
+const STICKY_BIT: u32 = 0o1000;
+
+/// Everything but the unreserved URI characters and `/` is escaped in `Path=`
+const PATH_ESCAPES: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// One trash folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashDir {
+    pub root: PathBuf,
+    /// The top of the volume for a volume trash, which its `Path=` entries are
+    /// relative to. `None` for the home trash, whose entries are absolute.
+    pub topdir: Option<PathBuf>,
+}
+
+impl TrashDir {
+    fn files(&self) -> PathBuf {
+        self.root.join("files")
+    }
+
+    fn info(&self) -> PathBuf {
+        self.root.join("info")
+    }
+
+    fn info_path(&self, name: &OsStr) -> PathBuf {
+        let mut file_name = name.to_os_string();
+        file_name.push(".");
+        file_name.push(INFO_EXTENSION);
+        self.info().join(file_name)
+    }
+
+    fn create(&self) -> io::Result<()> {
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true).mode(0o700);
+        builder.create(self.files())?;
+        builder.create(self.info())
+    }
+}
+
+/// The trash of the current user
+pub struct Trash {
+    home: PathBuf,
+    /// Use the trash folders of other volumes for items on them
+    volumes: bool,
+}
+
+impl Trash {
+    /// The user's home trash plus the trash folders of mounted volumes
+    pub fn for_current_user() -> AppResult<Self> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| AppError::internal("Can't find the home trash folder."))?;
+        Ok(Trash {
+            home: data_dir.join("Trash"),
+            volumes: true,
+        })
+    }
+
+    /// A trash that only uses the home trash at `home`
+    #[cfg(test)]
+    pub fn new(home: impl Into<PathBuf>) -> Self {
+        Trash {
+            home: home.into(),
+            volumes: false,
+        }
+    }
+
+    fn home_dir(&self) -> TrashDir {
+        TrashDir {
+            root: self.home.clone(),
+            topdir: None,
+        }
+    }
+
+    /// Moves the item at `path` into the trash of its volume, falling back to the home
+    /// trash when the volume has none. A symlink is trashed, not what it points to.
+    pub fn trash(&self, path: &Path) -> AppResult<TrashItem> {
+        let metadata = fs::symlink_metadata(path).map_err(|e| AppError::from_io(e, path))?;
+        let original = absolute_path(path)?;
+        let dir = self.trash_dir_for(&original, metadata.dev())?;
+        if dir.root.starts_with(&original) || original.starts_with(&dir.root) {
+            return Err(AppError::invalid_argument(format!(
+                "'{}' can't be moved to the trash.",
+                display(&original)
+            )));
+        }
+
+        let date = deletion_date(SystemTime::now());
+        let (name, info_path) = reserve_name(&dir, &original, &date, metadata.is_dir())?;
+        let target = dir.files().join(&name);
+        if let Err(err) = move_ops::move_path(path, &target, ConflictPolicy::Fail) {
+            let _ = fs::remove_file(&info_path);
+            return Err(err);
+        }
+
+        Ok(trash_item(&dir, &info_path, &name, original, Some(date)))
+    }
+
+    /// Every item in the home trash and in the trash folders of mounted volumes
+    pub fn list(&self) -> Vec<TrashItem> {
+        self.trash_dirs().iter().flat_map(items_in).collect()
+    }
+
+    /// Moves the item with the `.trashinfo` file `id` back to where it came from,
+    /// recreating missing parent folders. Returns the item's place in the trash, its
+    /// original path, and where it ended up (`None` if `policy` skipped it).
+    pub fn restore(
+        &self,
+        id: &Path,
+        policy: ConflictPolicy,
+    ) -> AppResult<(PathBuf, PathBuf, Option<PathBuf>)> {
+        let (dir, name) = self.locate(id)?;
+        let contents = fs::read_to_string(id).map_err(|e| AppError::from_io(e, id))?;
+        let (original, _) = parse_info(&contents, &dir)
+            .ok_or_else(|| AppError::invalid_argument(format!("'{}' is damaged.", display(id))))?;
+
+        // The original path comes from the info file, so it is checked like any other
+        sandbox::ensure_entry_allowed(&original)?;
+        let source = dir.files().join(&name);
+        fs::symlink_metadata(&source).map_err(|e| AppError::from_io(e, &source))?;
+        if let Some(parent) = original.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::from_io(e, parent))?;
+        }
+
+        let restored = move_ops::move_path(&source, &original, policy)?;
+        if restored.is_some() {
+            fs::remove_file(id).map_err(|e| AppError::from_io(e, id))?;
+            forget_directory_size(&dir, &name);
+        }
+        Ok((source, original, restored))
+    }
+
+    /// Permanently deletes the item with the `.trashinfo` file `id`
+    pub fn erase(&self, id: &Path) -> AppResult<()> {
+        let (dir, name) = self.locate(id)?;
+        erase_item(&dir, &name)
+    }
+
+    /// Permanently deletes everything in every trash folder. Returns the number of
+    /// items deleted.
+    pub fn empty(&self) -> AppResult<usize> {
+        let mut erased = 0;
+        for dir in self.trash_dirs() {
+            for name in info_names(&dir) {
+                erase_item(&dir, &name)?;
+                erased += 1;
+            }
+            // Items without a .trashinfo file are invisible, but still take up space
+            if let Ok(entries) = fs::read_dir(dir.files()) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    move_ops::remove_path(&path).map_err(|e| AppError::from_io(e, &path))?;
+                }
+            }
+            let _ = fs::remove_file(dir.root.join(DIRECTORY_SIZES));
+        }
+        Ok(erased)
+    }
+
+    /// The trash folder for an item at `original` on `device`
+    fn trash_dir_for(&self, original: &Path, device: u64) -> AppResult<TrashDir> {
+        let home = self.home_dir();
+        home.create()
+            .map_err(|e| AppError::from_io(e, &home.root))?;
+
+        let home_device = fs::metadata(&home.root).map(|m| m.dev()).ok();
+        if !self.volumes || home_device == Some(device) {
+            return Ok(home);
+        }
+        Ok(volume_trash(&topdir_of(original, device)).unwrap_or(home))
+    }
+
+    /// The trash folders that exist: the home trash, then those of mounted volumes
+    fn trash_dirs(&self) -> Vec<TrashDir> {
+        let mut dirs = vec![self.home_dir()];
+        if self.volumes {
+            let uid = uzers::get_current_uid();
+            for topdir in mount_points() {
+                let candidates = [
+                    topdir.join(".Trash").join(uid.to_string()),
+                    topdir.join(format!(".Trash-{}", uid)),
+                ];
+                for root in candidates {
+                    let is_dir = fs::symlink_metadata(&root).is_ok_and(|m| m.is_dir());
+                    if is_dir && !dirs.iter().any(|dir| dir.root == root) {
+                        dirs.push(TrashDir {
+                            root,
+                            topdir: Some(topdir.clone()),
+                        });
+                    }
+                }
+            }
+        }
+        dirs
+    }
+
+    /// The trash folder and item name for the `.trashinfo` file `id`, which must be in
+    /// the `info/` folder of one of our trash folders
+    fn locate(&self, id: &Path) -> AppResult<(TrashDir, OsString)> {
+        let not_in_trash = || {
+            AppError::invalid_argument(format!("'{}' is not an item in the trash.", display(id)))
+        };
+        if id.extension() != Some(OsStr::new(INFO_EXTENSION)) {
+            return Err(not_in_trash());
+        }
+        let info = id.parent().ok_or_else(not_in_trash)?;
+        let root = info
+            .parent()
+            .filter(|_| info.file_name() == Some(OsStr::new("info")));
+        let dir = root
+            .and_then(|root| self.trash_dirs().into_iter().find(|dir| dir.root == root))
+            .ok_or_else(not_in_trash)?;
+
+        fs::symlink_metadata(id).map_err(|e| AppError::from_io(e, id))?;
+        let name = id.file_stem().ok_or_else(not_in_trash)?.to_os_string();
+        Ok((dir, name))
+    }
+}
+
+/// `path` made absolute without resolving its last component, which may be a symlink
+fn absolute_path(path: &Path) -> AppResult<PathBuf> {
+    let name = path.file_name().ok_or_else(|| {
+        AppError::invalid_argument(format!("'{}' can't be moved to the trash.", display(path)))
+    })?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let parent = fs::canonicalize(parent).map_err(|e| AppError::from_io(e, parent))?;
+    Ok(parent.join(name))
+}
+
+/// Claims a free name in `dir` by creating its `.trashinfo` file, which the spec
+/// requires to exist before the item is moved in
+fn reserve_name(
+    dir: &TrashDir,
+    original: &Path,
+    date: &str,
+    is_dir: bool,
+) -> AppResult<(OsString, PathBuf)> {
+    let file_name = original.file_name().unwrap_or_default();
+    let stored_path = match &dir.topdir {
+        Some(topdir) => original.strip_prefix(topdir).unwrap_or(original),
+        None => original,
+    };
+    let contents = format!(
+        "{}\nPath={}\nDeletionDate={}\n",
+        INFO_HEADER,
+        percent_encode(stored_path.as_os_str().as_bytes(), PATH_ESCAPES),
+        date
+    );
+
+    for n in 0.. {
+        let name = match n {
+            0 => file_name.to_os_string(),
+            n => move_ops::numbered_name(&file_name.to_string_lossy(), n, is_dir).into(),
+        };
+        if fs::symlink_metadata(dir.files().join(&name)).is_ok() {
+            continue;
+        }
+        let info_path = dir.info_path(&name);
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(mut file) => {
+                if let Err(err) = file.write_all(contents.as_bytes()) {
+                    let _ = fs::remove_file(&info_path);
+                    return Err(AppError::from_io(err, &info_path));
+                }
+                return Ok((name, info_path));
+            }
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(AppError::from_io(err, &info_path)),
+        }
+    }
+    unreachable!("the candidate names are unbounded")
+}
+
+/// Deletes an item's file or folder, then its `.trashinfo` file
+fn erase_item(dir: &TrashDir, name: &OsStr) -> AppResult<()> {
+    let entry = dir.files().join(name);
+    match move_ops::remove_path(&entry) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(AppError::from_io(err, &entry)),
+    }
+    let info_path = dir.info_path(name);
+    fs::remove_file(&info_path).map_err(|e| AppError::from_io(e, &info_path))?;
+    forget_directory_size(dir, name);
+    Ok(())
+}
+
+/// The names of the items that have a `.trashinfo` file in `dir`
+fn info_names(dir: &TrashDir) -> Vec<OsString> {
+    let Ok(entries) = fs::read_dir(dir.info()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| PathBuf::from(entry.file_name()))
+        .filter(|path| path.extension() == Some(OsStr::new(INFO_EXTENSION)))
+        .filter_map(|path| path.file_stem().map(OsStr::to_os_string))
+        .collect()
+}
+
+fn items_in(dir: &TrashDir) -> Vec<TrashItem> {
+    info_names(dir)
+        .into_iter()
+        .filter_map(|name| {
+            let info_path = dir.info_path(&name);
+            let contents = fs::read_to_string(&info_path).ok()?;
+            let (original, date) = parse_info(&contents, dir)?;
+            // A .trashinfo file without its item is left over from an interrupted trash
+            fs::symlink_metadata(dir.files().join(&name)).ok()?;
+            Some(trash_item(dir, &info_path, &name, original, date))
+        })
+        .collect()
+}
+
+fn trash_item(
+    dir: &TrashDir,
+    info_path: &Path,
+    name: &OsStr,
+    original: PathBuf,
+    deletion_date: Option<String>,
+) -> TrashItem {
+    let metadata = fs::symlink_metadata(dir.files().join(name)).ok();
+    let is_dir = metadata.as_ref().is_some_and(|m| m.is_dir());
+    TrashItem {
+        id: display(info_path),
+        name: original
+            .file_name()
+            .unwrap_or(name)
+            .to_string_lossy()
+            .into_owned(),
+        original_path: display(&original),
+        deletion_date,
+        trash_dir: display(&dir.root),
+        is_dir,
+        size: metadata.filter(|_| !is_dir).map(|m| m.len()),
+    }
+}
+
+/// The original path and deletion date from a `.trashinfo` file
+fn parse_info(contents: &str, dir: &TrashDir) -> Option<(PathBuf, Option<String>)> {
+    let mut lines = contents.lines().map(str::trim);
+    lines.by_ref().find(|line| *line == INFO_HEADER)?;
+
+    let mut path = None;
+    let mut date = None;
+    // Keys are only read up to the next group
+    for line in lines.take_while(|line| !line.starts_with('[')) {
+        if let Some(value) = line.strip_prefix("Path=") {
+            let bytes: Vec<u8> = percent_decode(value.as_bytes()).collect();
+            path = Some(PathBuf::from(OsString::from_vec(bytes)));
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            date = Some(value.to_string());
+        }
+    }
+
+    let path = path?;
+    let original = match &dir.topdir {
+        Some(topdir) if path.is_relative() => topdir.join(path),
+        _ => path,
+    };
+    Some((original, date))
+}
+
+/// Drops a folder's cached size from the trash's `directorysizes` file, if it has one
+fn forget_directory_size(dir: &TrashDir, name: &OsStr) {
+    let sizes_path = dir.root.join(DIRECTORY_SIZES);
+    let Ok(contents) = fs::read_to_string(&sizes_path) else {
+        return;
+    };
+    let encoded = percent_encode(name.as_bytes(), PATH_ESCAPES).to_string();
+    // Each line is `size mtime name`
+    let kept: String = contents
+        .lines()
+        .filter(|line| line.splitn(3, ' ').nth(2) != Some(encoded.as_str()))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    if kept.len() != contents.len() {
+        let temp_path = dir.root.join(format!(".{}.tmp", DIRECTORY_SIZES));
+        if fs::write(&temp_path, kept).is_ok() {
+            let _ = fs::rename(&temp_path, &sizes_path);
+        }
+    }
+}
+
+/// The highest ancestor of `path` that is still on `device`, i.e. its mount point
+fn topdir_of(path: &Path, device: u64) -> PathBuf {
+    let mut topdir = path.parent().unwrap_or(path).to_path_buf();
+    for ancestor in path.ancestors().skip(2) {
+        match fs::metadata(ancestor) {
+            Ok(metadata) if metadata.dev() == device => topdir = ancestor.to_path_buf(),
+            _ => break,
+        }
+    }
+    topdir
+}
+
+/// The trash folder of the volume mounted at `topdir`. The shared `.Trash` folder is
+/// only used if it is a real folder with the sticky bit set, as the spec requires.
+fn volume_trash(topdir: &Path) -> Option<TrashDir> {
+    let uid = uzers::get_current_uid();
+    let shared = topdir.join(".Trash");
+    let shared_is_usable =
+        fs::symlink_metadata(&shared).is_ok_and(|m| m.is_dir() && m.mode() & STICKY_BIT != 0);
+
+    let mut candidates = Vec::with_capacity(2);
+    if shared_is_usable {
+        candidates.push(shared.join(uid.to_string()));
+    }
+    candidates.push(topdir.join(format!(".Trash-{}", uid)));
+
+    candidates.into_iter().find_map(|root| {
+        let dir = TrashDir {
+            root,
+            topdir: Some(topdir.to_path_buf()),
+        };
+        let _ = fs::DirBuilder::new().mode(0o700).create(&dir.root);
+        // Someone else's folder, or a symlink planted in its place, is no good
+        let owned = fs::symlink_metadata(&dir.root).is_ok_and(|m| m.is_dir() && m.uid() == uid);
+        (owned && dir.create().is_ok()).then_some(dir)
+    })
+}
+
+/// Mount points from `/proc/self/mounts`, which escapes spaces and the like as `\040`
+fn mount_points() -> Vec<PathBuf> {
+    let Ok(mounts) = fs::read_to_string("/proc/self/mounts") else {
+        return Vec::new();
+    };
+    mounts
+        .lines()
+        .filter_map(|line| line.split(' ').nth(1))
+        .map(|field| {
+            let bytes = field.as_bytes();
+            let mut decoded = Vec::with_capacity(bytes.len());
+            let mut i = 0;
+            while i < bytes.len() {
+                let octal = bytes.get(i + 1..i + 4).and_then(|digits| {
+                    u8::from_str_radix(std::str::from_utf8(digits).ok()?, 8).ok()
+                });
+                match (bytes[i], octal) {
+                    (b'\\', Some(byte)) => {
+                        decoded.push(byte);
+                        i += 4;
+                    }
+                    (byte, _) => {
+                        decoded.push(byte);
+                        i += 1;
+                    }
+                }
+            }
+            PathBuf::from(OsString::from_vec(decoded))
+        })
+        .collect()
+}
+
+/// `YYYY-MM-DDThh:mm:ss` in local time, as the spec asks for
+fn deletion_date(time: SystemTime) -> String {
+    DateTime::<Local>::from(time)
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string()
+}
+
+fn display(path: &Path) -> String {
+    platforms::normalize_path(&path.to_string_lossy())
+}
@@ -23,14 +23,84 @@
 // The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
 // for providing inspiration for this project.
 
-use std::env;
+use crate::models::pathinfo::UnixMetadata;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
 
-pub fn is_hidden(path: &Path) -> bool {
-    path.file_name()
-        .and_then(|name| name.to_str())
-        .map(|name| name.starts_with('.'))
-        .unwrap_or(false)
+// uid/gid lookups go through NSS, which can be slow, so names are cached
+static USER_NAMES: Lazy<Mutex<HashMap<u32, Option<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static GROUP_NAMES: Lazy<Mutex<HashMap<u32, Option<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Reads the `ls -l` style details of the entry at `path` without following a final symlink
+pub fn unix_metadata(path: &Path) -> Option<UnixMetadata> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    let accessed = metadata
+        .accessed()
+        .unwrap_or(UNIX_EPOCH)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+
+    Some(UnixMetadata {
+        permissions: format_mode(metadata.mode()),
+        mode: metadata.mode() & 0o7777,
+        uid: metadata.uid(),
+        gid: metadata.gid(),
+        owner: user_name(metadata.uid()),
+        group: group_name(metadata.gid()),
+        inode: metadata.ino(),
+        hard_links: metadata.nlink(),
+        device: metadata.dev(),
+        accessed,
+    })
 }
 
+/// Renders permission bits as `rwxr-xr-x`, including setuid/setgid/sticky markers
+pub fn format_mode(mode: u32) -> String {
+    let triplet = |read: u32, write: u32, exec: u32, special: u32, special_char: char| {
+        let mut out = String::with_capacity(3);
+        out.push(if mode & read != 0 { 'r' } else { '-' });
+        out.push(if mode & write != 0 { 'w' } else { '-' });
+        out.push(match (mode & exec != 0, mode & special != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+        out
+    };
+
+    [
+        triplet(0o400, 0o200, 0o100, 0o4000, 's'),
+        triplet(0o040, 0o020, 0o010, 0o2000, 's'),
+        triplet(0o004, 0o002, 0o001, 0o1000, 't'),
+    ]
+    .concat()
+}
 
+fn user_name(uid: u32) -> Option<String> {
+    let mut names = USER_NAMES.lock().ok()?;
+    names
+        .entry(uid)
+        .or_insert_with(|| {
+            uzers::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().to_string())
+        })
+        .clone()
+}
+
+fn group_name(gid: u32) -> Option<String> {
+    let mut names = GROUP_NAMES.lock().ok()?;
+    names
+        .entry(gid)
+        .or_insert_with(|| {
+            uzers::get_group_by_gid(gid).map(|group| group.name().to_string_lossy().to_string())
+        })
+        .clone()
+}
@@ -23,8 +23,13 @@
 // The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
 // for providing inspiration for this project.
 
+use crate::commands::archive_fs;
+use crate::commands::archive_ops::format_from_name;
+use crate::commands::constants::CONTENT_SNIFF_LEN;
+use crate::commands::content_type::{self, content_type_of};
 use crate::platforms;
-use crate::search::types::{SearchFileResult, SearchMatch};
+use crate::search::constants::{MAX_FILES_PER_DIR, MAX_RESULTS_PER_DIR};
+use crate::search::types::{SearchFileResult, SearchMatch, SearchStats};
 use regex::Regex;
 use std::fs;
 use std::path::Path;
@@ -37,47 +42,81 @@ pub fn should_search_directory(_path: &Path) -> bool {
 
 pub fn search_file(path: &Path, regex: &Regex) -> Option<SearchFileResult> {
     if let Ok(content) = fs::read_to_string(path) {
-        let mut file_matches = Vec::new();
+        return search_text(&path.to_string_lossy(), &content, regex);
+    }
+    None
+}
 
-        for (line_num, line) in content.lines().enumerate() {
-            let mut line_matches = Vec::new();
+/// Finds the matches of `regex` in the content of the file at `path`
+pub fn search_text(path: &str, content: &str, regex: &Regex) -> Option<SearchFileResult> {
+    let mut file_matches = Vec::new();
 
-            // Use find_iter to get all matches
-            for capture in regex.find_iter(line) {
-                line_matches.push([capture.start(), capture.end()]);
-            }
+    for (line_num, line) in content.lines().enumerate() {
+        let mut line_matches = Vec::new();
 
-            if !line_matches.is_empty() {
-                file_matches.push(SearchMatch {
-                    line: line_num + 1,
-                    content: line.to_string(),
-                    match_ranges: line_matches,
-                });
-            }
+        // Use find_iter to get all matches
+        for capture in regex.find_iter(line) {
+            line_matches.push([capture.start(), capture.end()]);
         }
 
-        if !file_matches.is_empty() {
-            // Normalize the path for the current platform
-            let normalized_path = platforms::normalize_path(&path.to_string_lossy());
-            return Some(SearchFileResult {
-                path: normalized_path,
-                matches: file_matches,
+        if !line_matches.is_empty() {
+            file_matches.push(SearchMatch {
+                line: line_num + 1,
+                content: line.to_string(),
+                match_ranges: line_matches,
             });
         }
     }
+
+    if !file_matches.is_empty() {
+        // Normalize the path for the current platform
+        let normalized_path = platforms::normalize_path(path);
+        return Some(SearchFileResult {
+            path: normalized_path,
+            matches: file_matches,
+        });
+    }
     None
 }
 
+/// Searches the text files inside an archive, found by name like `.zip` or
+/// `.tar.gz`. Results carry `archive.zip!/inner/path` paths.
+pub fn search_archive(
+    path: &Path,
+    regex: &Regex,
+    stats: &mut SearchStats,
+) -> Vec<SearchFileResult> {
+    let mut results = Vec::new();
+    let searched =
+        archive_fs::visit_files(path, MAX_FILE_SIZE, MAX_FILES_PER_DIR, |member, content| {
+            let sample = &content[..content.len().min(CONTENT_SNIFF_LEN)];
+            let name = member.rsplit(['/', '\\']).next().unwrap_or_default();
+            if content_type_of(name, sample).binary {
+                return true;
+            }
+            if let Some(result) = std::str::from_utf8(&content)
+                .ok()
+                .and_then(|text| search_text(&member, text, regex))
+            {
+                stats.total_matches += result.matches.len();
+                results.push(result);
+            }
+            results.len() < MAX_RESULTS_PER_DIR
+        });
+    stats.files_searched += searched.unwrap_or(0);
+    results
+}
+
+pub fn is_archive(path: &Path) -> bool {
+    format_from_name(path).is_some()
+}
+
 pub fn should_skip_file(path: &Path) -> bool {
     is_binary_file(path) || is_too_large(path)
 }
 
 pub fn is_binary_file(path: &Path) -> bool {
-    if let Ok(content) = fs::read(path) {
-        content.iter().any(|&b| b == 0)
-    } else {
-        false
-    }
+    content_type::is_binary(path)
 }
 
 pub fn is_too_large(path: &Path) -> bool {
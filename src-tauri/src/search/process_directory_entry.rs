@@ -24,7 +24,9 @@
 // for providing inspiration for this project.
 
 use crate::search::constants::MAX_FILES_PER_DIR;
-use crate::search::file_ops::{search_file, should_prioritize_directory, should_skip_file};
+use crate::search::file_ops::{
+    is_archive, search_file, should_prioritize_directory, should_skip_file,
+};
 use crate::search::types::{SearchFileResult, SearchStats};
 use regex::Regex;
 use std::path::PathBuf;
@@ -36,6 +38,7 @@ pub fn process_directory_entry(
     dir_results: &mut Vec<SearchFileResult>,
     dir_files_searched: &mut usize,
     stats: &mut SearchStats,
+    search_archives: bool,
 ) {
     let path = entry.path();
 
@@ -46,6 +49,9 @@ pub fn process_directory_entry(
             } else {
                 dirs_to_search.push(path);
             }
+        } else if ft.is_file() && search_archives && is_archive(&path) {
+            // Searched like a folder, once this one is done
+            dirs_to_search.push(path);
         } else if ft.is_file() && !should_skip_file(&path) {
             *dir_files_searched += 1;
             if *dir_files_searched > MAX_FILES_PER_DIR {
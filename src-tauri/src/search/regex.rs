@@ -23,6 +23,7 @@
 // The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
 // for providing inspiration for this project.
 
+use crate::models::error::{AppError, AppResult};
 use regex::Regex;
 
 pub fn build_regex(
@@ -30,7 +31,7 @@ pub fn build_regex(
     is_regex: bool,
     is_case_sensitive: bool,
     is_whole_word: bool,
-) -> Result<Regex, String> {
+) -> AppResult<Regex> {
     let regex_pattern = if is_regex {
         query.to_string()
     } else {
@@ -49,7 +50,10 @@ pub fn build_regex(
         Regex::new(&format!("(?i){}", regex_pattern))
     };
 
-    regex.map_err(|e| format!("Invalid search pattern: {}", e))
+    regex.map_err(|e| AppError::InvalidPattern {
+        pattern: query.to_string(),
+        reason: e.to_string(),
+    })
 }
 
 #[cfg(test)]
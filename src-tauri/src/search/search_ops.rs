@@ -23,7 +23,7 @@
 // The author would also like to give special thanks to the contributors of https://github.com/Souvlaki42/file-manager.git
 // for providing inspiration for this project.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use std::collections::HashSet;
 use tokio::sync::mpsc;
@@ -32,9 +32,9 @@ use once_cell::sync::Lazy;
 use tauri::Window;
 use std::fs;
 
-use crate::search::types::{SearchResponse, SearchStats, SearchFileResult, SearchProgress};
+use crate::search::types::{SearchResponse, SearchStats, SearchFileResult, SearchProgress, SearchOptions};
 use crate::search::regex::build_regex;
-use crate::search::file_ops::should_search_directory;
+use crate::search::file_ops::{should_search_directory, search_archive};
 use crate::search::constants::{
     MAX_RESULTS_PER_DIR,
     MAX_TOTAL_RESULTS,
@@ -43,16 +43,18 @@ use crate::search::constants::{
     EARLY_RESULTS_INTERVAL,
 };
 use crate::search::process_directory_entry::process_directory_entry;
+use crate::models::error::{AppError, AppResult};
+use crate::commands::sandbox;
 use crate::platforms;
 
 // Global cancellation channel
 static CANCEL_TX: Lazy<Mutex<Option<mpsc::Sender<()>>>> = Lazy::new(|| Mutex::new(None));
 
 #[tauri::command]
-pub async fn cancel_search() -> Result<(), String> {
+pub async fn cancel_search() -> AppResult<()> {
     // Take ownership of the sender and drop the lock immediately
     let tx = CANCEL_TX.lock()
-        .map_err(|_| "Failed to acquire lock".to_string())?
+        .map_err(AppError::lock_poisoned)?
         .take();
     
     if let Some(tx) = tx {
@@ -89,17 +91,14 @@ pub async fn search_folder(
     window: Window,
     path: String,
     query: String,
-    is_regex: bool,
-    is_case_sensitive: bool,
-    is_whole_word: bool,
-    timeout_seconds: u64,
-) -> Result<SearchResponse, String> {
+    options: SearchOptions,
+) -> AppResult<SearchResponse> {
     let (tx, mut rx) = mpsc::channel(1);
     
     *CANCEL_TX.lock()
-        .map_err(|_| "Failed to acquire lock".to_string())? = Some(tx);
+        .map_err(AppError::lock_poisoned)? = Some(tx);
 
-    let timeout = Duration::from_secs(timeout_seconds);
+    let timeout = Duration::from_secs(options.timeout_seconds);
     let start_time = Instant::now();
     let mut stats = SearchStats::default();
     let mut results = Vec::new();
@@ -108,11 +107,13 @@ pub async fn search_folder(
     let mut last_early_results = Instant::now();
     let mut batch_results = Vec::new();
     let mut cumulative_time = 0;
+    let search_archives = options.search_archives;
 
-    let regex = build_regex(&query, is_regex, is_case_sensitive, is_whole_word)?;
+    let regex = build_regex(&query, options.is_regex, options.is_case_sensitive, options.is_whole_word)?;
     
     // Normalize the search path for the current platform
     let normalized_path = platforms::normalize_path(&path);
+    sandbox::ensure_allowed(Path::new(&normalized_path))?;
     let mut dirs_to_search = vec![PathBuf::from(&normalized_path)];
     let mut curtailed = false;
 
@@ -149,6 +150,13 @@ pub async fn search_folder(
             continue;
         }
 
+        if current_dir.is_file() {
+            // An archive found while searching with search_archives
+            let archive_results = search_archive(&current_dir, &regex, &mut stats);
+            batch_results.extend(archive_results);
+            continue;
+        }
+
         let dir_entries = match fs::read_dir(&current_dir) {
             Ok(entries) => entries,
             Err(_) => continue,
@@ -170,6 +178,7 @@ pub async fn search_folder(
                 &mut dir_results,
                 &mut dir_files_searched,
                 &mut stats,
+                search_archives,
             );
         }
 
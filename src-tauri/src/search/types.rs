@@ -45,6 +45,18 @@ pub struct SearchStats {
     pub directories_searched: usize,
 }
 
+/// How `search_folder` matches the query, and where it looks
+#[derive(Deserialize, Debug, Clone)]
+pub struct SearchOptions {
+    pub is_regex: bool,
+    pub is_case_sensitive: bool,
+    pub is_whole_word: bool,
+    pub timeout_seconds: u64,
+    /// Also search the text files inside .zip, .jar and .tar(.gz/.zst) archives
+    #[serde(default)]
+    pub search_archives: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SearchResponse {
     pub results: Vec<SearchFileResult>,
@@ -26,7 +26,12 @@
  */
 
 import { useState, useCallback } from "react";
-import { NodeDetails } from "@/components/common/types/types";
+import {
+  FileContent,
+  NodeDetails,
+  WrittenFile,
+} from "@/components/common/types/types";
+import { writeFileContent } from "@/components/FileOperations/fileOperations";
 import { invoke } from "@tauri-apps/api/tauri";
 
 export function useViewFile() {
@@ -35,6 +40,8 @@ export function useViewFile() {
   const [fileContent, setFileContent] = useState("");
   const [fileName, setFileName] = useState("");
   const [nodeDetails, setNodeDetails] = useState<NodeDetails | null>(null);
+  // How the file was last read, passed back when saving it
+  const [readResult, setReadResult] = useState<FileContent | null>(null);
 
   // Open preview for a file (double click)
   const openPreview = useCallback(async (file: NodeDetails) => {
@@ -70,12 +77,14 @@ export function useViewFile() {
             kind: file.kind,
             hidden: file.hidden,
           });
-          const content = await invoke<string>("read_file_content", {
+          const result = await invoke<FileContent>("read_file_content", {
             filePath: file.path,
           });
           console.log("Successfully read file content");
-          return content;
+          setReadResult(result);
+          return result.content;
         } catch (error) {
+          setReadResult(null);
           console.error("Error reading file:", error, {
             file: file.path,
             kind: file.kind,
@@ -89,6 +98,33 @@ export function useViewFile() {
     []
   );
 
+  // Save edited text back to the previewed file, in the encoding it was read in.
+  // Refused if the file changed since, or only part of it (or a hex dump) was read.
+  const saveFile = useCallback(
+    async (content: string): Promise<WrittenFile> => {
+      if (!nodeDetails || !readResult) {
+        throw new Error("No file has been read to save");
+      }
+      const { etag, encoding, has_bom, truncated, is_binary } = readResult;
+      const written = await writeFileContent(
+        nodeDetails.path,
+        content,
+        etag,
+        false,
+        { encoding, has_bom, truncated, is_binary }
+      );
+      setReadResult({
+        ...readResult,
+        content,
+        etag: written.etag,
+        size: written.size,
+      });
+      setFileContent(content);
+      return written;
+    },
+    [nodeDetails, readResult]
+  );
+
   return {
     isPreviewOpen,
     fileContent,
@@ -97,5 +133,6 @@ export function useViewFile() {
     openPreview,
     closePreview,
     readFileContent,
+    saveFile,
   };
 }
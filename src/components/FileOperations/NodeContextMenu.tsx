@@ -38,6 +38,7 @@ import {
 } from "@/components/common/types/constants";
 import { deleteFileSystemItem } from "@/components/FileOperations/fileOperations";
 import { toast } from "sonner";
+import { errorMessage } from "@/components/common/functions/appError";
 import { valtioTreeStates } from "@/components/common/globalStateMgt/valtioTreeStates";
 
 interface NodeDetailsContextMenuProps {
@@ -143,9 +144,7 @@ export const NodeContextMenu = ({
       setShowDeleteConfirmation(false);
     } catch (error) {
       console.error("Error deleting item:", error);
-      toast.error(
-        error instanceof Error ? error.message : "Failed to delete item"
-      );
+      toast.error(errorMessage(error, "Failed to delete item"));
     }
   };
 
@@ -26,25 +26,248 @@
  */
 import { invoke } from "@tauri-apps/api/tauri";
 import { toPlatformPath } from "@/components/common/functions/platform_frontend";
+import {
+  ArchiveFormat,
+  ArchiveSummary,
+  BulkRenameRules,
+  ConflictPolicy,
+  ConflictResolution,
+  CopyOptions,
+  CopySummary,
+  ExtractOptions,
+  FileContent,
+  FileTemplate,
+  JobInfo,
+  JobRequest,
+  JournalState,
+  LineIndexStatus,
+  LinePage,
+  MoveResult,
+  OperationSummary,
+  ReadFormat,
+  RenamePreview,
+  TrashItem,
+  WrittenFile,
+} from "@/components/common/types/types";
+
+/**
+ * Reads a file, decoding text from whatever encoding it uses; files that aren't
+ * text come back as a hex dump
+ *
+ * @param filePath - The file to read
+ * @returns The content with its encoding and line endings, and the etag to pass
+ *   to writeFileContent when saving
+ */
+export async function readFileContent(filePath: string): Promise<FileContent> {
+  return invoke<FileContent>("read_file_content", {
+    filePath: toPlatformPath(filePath),
+  });
+}
+
+/**
+ * Saves a text file atomically, keeping its permissions
+ *
+ * @param filePath - The file to write; it is created if missing
+ * @param content - The new content
+ * @param etag - The etag from readFileContent; the save is rejected with a
+ *   "Modified" error if the file changed since. Omit it to overwrite regardless.
+ * @param keepBackup - Keep the previous content as <name>.bak
+ * @param format - The encoding, has_bom, truncated and is_binary fields from
+ *   readFileContent, so the text is saved in the encoding it was read in. Content
+ *   that was truncated or is a hex dump is refused. Omit it to write UTF-8.
+ * @returns The new etag, the size, and the backup path
+ */
+export async function writeFileContent(
+  filePath: string,
+  content: string,
+  etag?: string,
+  keepBackup?: boolean,
+  format?: ReadFormat
+): Promise<WrittenFile> {
+  return invoke<WrittenFile>("write_file_content", {
+    filePath: toPlatformPath(filePath),
+    content,
+    etag,
+    keepBackup,
+    format,
+  });
+}
+
+/**
+ * Starts indexing the lines of a large file in the background. Progress arrives
+ * through "line-index-progress" events, the last one with total_lines set.
+ *
+ * @param filePath - The file to index
+ * @returns How far indexing has got
+ */
+export async function openLargeFile(filePath: string): Promise<LineIndexStatus> {
+  return invoke<LineIndexStatus>("open_large_file", {
+    filePath: toPlatformPath(filePath),
+  });
+}
+
+/**
+ * Reads a window of lines from a large file. Lines not indexed yet aren't
+ * returned; ask again after the next "line-index-progress" event.
+ *
+ * @param filePath - The file to read
+ * @param startLine - The first line, counting from 0
+ * @param count - How many lines, at most 10,000
+ * @returns The lines and the indexing progress
+ */
+export async function readFileLines(
+  filePath: string,
+  startLine: number,
+  count: number
+): Promise<LinePage> {
+  return invoke<LinePage>("read_file_lines", {
+    filePath: toPlatformPath(filePath),
+    startLine,
+    count,
+  });
+}
+
+/**
+ * Stops indexing a large file and frees its index
+ *
+ * @param filePath - The file passed to openLargeFile
+ */
+export async function closeLargeFile(filePath: string): Promise<void> {
+  return invoke("close_large_file", { filePath: toPlatformPath(filePath) });
+}
+
+/**
+ * Follows a growing file like `tail -F`. New lines arrive through "file-follow"
+ * events tagged with followId; truncation and log rotation are handled.
+ *
+ * @param followId - Caller-chosen id used to tag events and to stop following
+ * @param filePath - The file to follow
+ * @param backfillLines - How many of the file's last lines to return first
+ * @returns The last backfillLines complete lines
+ */
+export async function followFile(
+  followId: string,
+  filePath: string,
+  backfillLines?: number
+): Promise<string[]> {
+  return invoke<string[]>("follow_file", {
+    followId,
+    filePath: toPlatformPath(filePath),
+    backfillLines,
+  });
+}
+
+/**
+ * Stops following a file
+ *
+ * @param followId - The id passed to followFile
+ */
+export async function unfollowFile(followId: string): Promise<void> {
+  return invoke("unfollow_file", { followId });
+}
+
+/**
+ * Packs files and folders into a new archive. Progress arrives through
+ * "archive-progress" events tagged with jobId.
+ *
+ * @param jobId - Caller-chosen id used to tag events and to cancel the job
+ * @param sources - The files and folders to pack
+ * @param archivePath - The archive to create
+ * @param format - Defaults to what archivePath's extension says
+ * @returns What was archived
+ */
+export async function createArchive(
+  jobId: string,
+  sources: string[],
+  archivePath: string,
+  format?: ArchiveFormat
+): Promise<ArchiveSummary> {
+  return invoke<ArchiveSummary>("create_archive", {
+    jobId,
+    sources: sources.map(toPlatformPath),
+    archivePath: toPlatformPath(archivePath),
+    format,
+  });
+}
+
+/**
+ * Unpacks a zip or tar archive into a folder. Entries that would land outside it
+ * are skipped, and oversized contents stop the job.
+ *
+ * @param jobId - Caller-chosen id used to tag events and to cancel the job
+ * @param archivePath - The archive to unpack
+ * @param destination - The folder to unpack into
+ * @param options - Conflict policy and size limit
+ * @returns What was extracted and skipped
+ */
+export async function extractArchive(
+  jobId: string,
+  archivePath: string,
+  destination: string,
+  options?: ExtractOptions
+): Promise<ArchiveSummary> {
+  return invoke<ArchiveSummary>("extract_archive", {
+    jobId,
+    archivePath: toPlatformPath(archivePath),
+    destination: toPlatformPath(destination),
+    options,
+  });
+}
+
+/**
+ * Cancels an archive job; whatever it created is removed
+ *
+ * @param jobId - The id passed to createArchive or extractArchive
+ */
+export async function cancelArchive(jobId: string): Promise<void> {
+  return invoke("cancel_archive", { jobId });
+}
+
+/**
+ * Lists the templates new files can be created from. User templates live in the
+ * "templates" folder of the app's config directory; default.<ext> replaces the
+ * built-in template for that extension.
+ *
+ * @returns The built-in templates followed by the user's
+ */
+export async function listFileTemplates(): Promise<FileTemplate[]> {
+  return invoke<FileTemplate[]>("list_file_templates");
+}
 
 /**
  * Deletes a file at the specified path
  *
  * @param filePath - The path to the file to delete
+ * @param permanent - Skip the trash; the default moves the file to the trash where
+ *   the platform has one
  * @returns A promise that resolves when the file is deleted
  */
-export async function deleteFile(filePath: string): Promise<void> {
-  return invoke("delete_file", { filePath: toPlatformPath(filePath) });
+export async function deleteFile(
+  filePath: string,
+  permanent?: boolean
+): Promise<void> {
+  return invoke("delete_file", {
+    filePath: toPlatformPath(filePath),
+    permanent,
+  });
 }
 
 /**
  * Deletes a folder and all its contents at the specified path
  *
  * @param folderPath - The path to the folder to delete
+ * @param permanent - Skip the trash; the default moves the folder to the trash where
+ *   the platform has one
  * @returns A promise that resolves when the folder is deleted
  */
-export async function deleteFolder(folderPath: string): Promise<void> {
-  return invoke("delete_folder", { folderPath: toPlatformPath(folderPath) });
+export async function deleteFolder(
+  folderPath: string,
+  permanent?: boolean
+): Promise<void> {
+  return invoke("delete_folder", {
+    folderPath: toPlatformPath(folderPath),
+    permanent,
+  });
 }
 
 /**
@@ -52,11 +275,252 @@ export async function deleteFolder(folderPath: string): Promise<void> {
  *
  * @param path - The path to the file or folder to delete
  * @param isDirectory - Whether the path is a directory
+ * @param permanent - Skip the trash
  * @returns A promise that resolves when the item is deleted
  */
 export async function deleteFileSystemItem(
   path: string,
-  isDirectory: boolean
+  isDirectory: boolean,
+  permanent?: boolean
+): Promise<void> {
+  return isDirectory
+    ? deleteFolder(path, permanent)
+    : deleteFile(path, permanent);
+}
+
+/**
+ * Moves a file or folder to the trash
+ *
+ * @param path - The item to trash
+ * @returns The item as it now appears in the trash
+ */
+export async function moveToTrash(path: string): Promise<TrashItem> {
+  return invoke<TrashItem>("move_to_trash", { path: toPlatformPath(path) });
+}
+
+/**
+ * Lists the home trash and the trash folders of mounted volumes
+ *
+ * @returns The items, most recently trashed first
+ */
+export async function listTrash(): Promise<TrashItem[]> {
+  return invoke<TrashItem[]>("list_trash");
+}
+
+/**
+ * Puts a trashed item back where it was
+ *
+ * @param id - The item's id from listTrash
+ * @param conflict - What to do if something now exists at the original path
+ * @returns Where the item ended up; a Skipped item stays in the trash
+ */
+export async function restoreFromTrash(
+  id: string,
+  conflict?: ConflictPolicy
+): Promise<MoveResult> {
+  return invoke<MoveResult>("restore_from_trash", { id, conflict });
+}
+
+/**
+ * Permanently deletes items from the trash
+ *
+ * @param ids - The items to delete; everything in every trash when omitted
+ * @returns The number of items deleted
+ */
+export async function emptyTrash(ids?: string[]): Promise<number> {
+  return invoke<number>("empty_trash", { ids });
+}
+
+/**
+ * Renames a file or folder in place
+ *
+ * @param path - The item to rename
+ * @param newName - The new name, without any folder part
+ * @param conflict - What to do if an item with the new name exists
+ * @returns Where the item ended up, or a Skipped result
+ */
+export async function renameItem(
+  path: string,
+  newName: string,
+  conflict?: ConflictPolicy
+): Promise<MoveResult> {
+  return invoke<MoveResult>("rename_item", {
+    path: toPlatformPath(path),
+    newName,
+    conflict,
+  });
+}
+
+/**
+ * Shows what a bulk rename would do without renaming anything
+ *
+ * @param paths - The items to rename, in counter order
+ * @param rules - Find/replace, case, template and counter settings
+ * @returns The new name of every item, with collisions and invalid names flagged
+ */
+export async function previewBulkRename(
+  paths: string[],
+  rules: BulkRenameRules
+): Promise<RenamePreview[]> {
+  return invoke<RenamePreview[]>("preview_bulk_rename", {
+    paths: paths.map(toPlatformPath),
+    rules,
+  });
+}
+
+/**
+ * Renames many items at once as one undoable operation. Nothing is renamed if any
+ * new name collides or is invalid.
+ *
+ * @param paths - The items to rename, in counter order
+ * @param rules - Find/replace, case, template and counter settings
+ * @returns What was done, as previewBulkRename described it
+ */
+export async function bulkRename(
+  paths: string[],
+  rules: BulkRenameRules
+): Promise<RenamePreview[]> {
+  return invoke<RenamePreview[]>("bulk_rename", {
+    paths: paths.map(toPlatformPath),
+    rules,
+  });
+}
+
+/**
+ * Moves files and folders into another folder
+ *
+ * @param sources - The items to move
+ * @param destination - The folder to move them into
+ * @param conflict - What to do when the folder already holds an item of the same name
+ * @returns One result per source; a failed item doesn't stop the others
+ */
+export async function moveItems(
+  sources: string[],
+  destination: string,
+  conflict?: ConflictPolicy
+): Promise<MoveResult[]> {
+  return invoke<MoveResult[]>("move_items", {
+    sources: sources.map(toPlatformPath),
+    destination: toPlatformPath(destination),
+    conflict,
+  });
+}
+
+/**
+ * Copies files and folders into another folder, keeping permissions and timestamps.
+ * Progress arrives through "copy-progress" events and conflicts through
+ * "copy-conflict" events, both tagged with jobId.
+ *
+ * @param jobId - Caller-chosen id used to tag events, answer conflicts and cancel
+ * @param sources - The items to copy
+ * @param destination - The folder to copy them into
+ * @param options - Symlink handling, and a conflict policy to use instead of asking
+ * @returns Counts, skipped items and per-item errors
+ */
+export async function copyItems(
+  jobId: string,
+  sources: string[],
+  destination: string,
+  options?: CopyOptions
+): Promise<CopySummary> {
+  return invoke<CopySummary>("copy_items", {
+    jobId,
+    sources: sources.map(toPlatformPath),
+    destination: toPlatformPath(destination),
+    options,
+  });
+}
+
+/**
+ * Answers the "copy-conflict" event a copy is waiting on
+ *
+ * @param jobId - The copy that asked
+ * @param resolution - What to do with the item, optionally for the rest of the job
+ */
+export async function resolveCopyConflict(
+  jobId: string,
+  resolution: ConflictResolution
 ): Promise<void> {
-  return isDirectory ? deleteFolder(path) : deleteFile(path);
+  return invoke("resolve_copy_conflict", { jobId, resolution });
+}
+
+/**
+ * Stops a running copy; the file being copied is discarded
+ *
+ * @param jobId - The copy to stop
+ */
+export async function cancelCopy(jobId: string): Promise<void> {
+  return invoke("cancel_copy", { jobId });
+}
+
+/**
+ * Reverses the most recent file operation. Rejects with a Changed error, touching
+ * nothing, if an item was modified since the operation.
+ *
+ * @returns The operation that was undone
+ */
+export async function undoOperation(): Promise<OperationSummary> {
+  return invoke<OperationSummary>("undo_operation");
+}
+
+/**
+ * Repeats the most recently undone file operation
+ *
+ * @returns The operation that was redone
+ */
+export async function redoOperation(): Promise<OperationSummary> {
+  return invoke<OperationSummary>("redo_operation");
+}
+
+/**
+ * Reports what undo and redo would act on next
+ */
+export async function getJournalState(): Promise<JournalState> {
+  return invoke<JournalState>("get_journal_state");
+}
+
+/**
+ * Starts a copy, move or delete in the background. Progress arrives through
+ * "job-progress" events; copy conflicts through "copy-conflict" events, answered with
+ * resolveCopyConflict using the job id.
+ *
+ * @param request - What to do
+ * @returns The job's id
+ */
+export async function startJob(request: JobRequest): Promise<string> {
+  return invoke<string>("start_job", { request });
+}
+
+/**
+ * Lists the queued, running and recently finished jobs, oldest first
+ */
+export async function listJobs(): Promise<JobInfo[]> {
+  return invoke<JobInfo[]>("list_jobs");
+}
+
+/**
+ * Pauses a job; a queued job won't start until resumed
+ *
+ * @param jobId - The job to pause
+ */
+export async function pauseJob(jobId: string): Promise<void> {
+  return invoke("pause_job", { jobId });
+}
+
+/**
+ * Lets a paused job carry on
+ *
+ * @param jobId - The job to resume
+ */
+export async function resumeJob(jobId: string): Promise<void> {
+  return invoke("resume_job", { jobId });
+}
+
+/**
+ * Stops a job; items already done stay done
+ *
+ * @param jobId - The job to cancel
+ */
+export async function cancelJob(jobId: string): Promise<void> {
+  return invoke("cancel_job", { jobId });
 }
@@ -30,6 +30,7 @@ import { join } from "@tauri-apps/api/path";
 import { NodeDetails } from "@/components/common/types/types";
 import { invoke } from "@tauri-apps/api/tauri";
 import { NODE_KINDS } from "@/components/common/types/constants";
+import { errorMessage } from "@/components/common/functions/appError";
 
 type CreateMode = typeof NODE_KINDS.FILE | typeof NODE_KINDS.DIRECTORY;
 
@@ -116,7 +117,7 @@ export const useCreateItem = (
       await onRefresh();
       onClose();
     } catch (error) {
-      setErrorMessage(String(error));
+      setErrorMessage(errorMessage(error));
       inputRef.current?.select();
       console.error(`Error creating ${mode}:`, error);
     } finally {
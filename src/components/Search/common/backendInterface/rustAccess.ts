@@ -27,15 +27,20 @@ export const searchFiles = async (
   isRegex: boolean,
   isCaseSensitive: boolean,
   isWholeWord: boolean,
-  timeoutSeconds: number
+  timeoutSeconds: number,
+  // also search the text files inside .zip, .jar and .tar(.gz/.zst) archives
+  searchArchives?: boolean
 ): Promise<SearchResponse> => {
   return invoke("search_folder", {
     path,
     query,
-    isRegex,
-    isCaseSensitive,
-    isWholeWord,
-    timeoutSeconds,
+    options: {
+      is_regex: isRegex,
+      is_case_sensitive: isCaseSensitive,
+      is_whole_word: isWholeWord,
+      timeout_seconds: timeoutSeconds,
+      search_archives: searchArchives ?? false,
+    },
   });
 };
 
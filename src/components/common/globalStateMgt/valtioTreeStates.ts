@@ -28,6 +28,7 @@ import { proxy, subscribe } from "valtio";
 import { NodeDetails } from "@/components/common/types/types";
 import { getTreeData } from "@/components/common/types/getTreeData";
 import { sortItems } from "../functions/sortItems";
+import { errorMessage } from "../functions/appError";
 import { valtioToggleStates } from "./valtioToggleStates";
 import { FORWARD_SLASH } from "../constants/filesys";
 
@@ -210,7 +211,7 @@ export const treeActions = {
       valtioTreeStates.sortedLocalData = treeActions.sortNodes(contents);
     } catch (err) {
       console.error("Error loading root tree:", err);
-      valtioTreeStates.error = err instanceof Error ? err.message : String(err);
+      valtioTreeStates.error = errorMessage(err);
       valtioTreeStates.sortedLocalData = [];
     }
     valtioTreeStates.updateTrigger++;
@@ -28,6 +28,7 @@
 export const NODE_KINDS = {
   DIRECTORY: "Directory",
   FILE: "File",
+  SYMLINK: "Symlink",
 } as const;
 
 // Menu operation labels
@@ -25,15 +25,81 @@
  * for providing inspiration for this project.
  */
 import { invoke } from "@tauri-apps/api/tauri";
-import { NodeDetails } from "./types";
+import {
+  DiskUsageReport,
+  NodeDetails,
+  TreeOptions,
+  TreePage,
+  TreeStreamSummary,
+} from "./types";
+
+// Folders inside archives are listed as "archive.zip!/inner/path"
+export const archiveEntryPath = (archivePath: string, innerPath = ""): string =>
+  `${archivePath}!/${innerPath.replace(/^[/\\]+/, "")}`;
 
 export const getTreeData = async (
   directoryPath: string,
-  recursive: boolean = false
+  recursive: boolean = false,
+  nested: boolean = false,
+  maxDepth?: number,
+  options?: TreeOptions
 ): Promise<NodeDetails[]> => {
   const data = await invoke<NodeDetails[]>("get_tree_data", {
     directoryPath,
     recursive,
+    nested,
+    maxDepth,
+    options,
   });
   return data;
 };
+
+export const getTreePage = async (
+  directoryPath: string,
+  cursor?: string | null,
+  pageSize?: number,
+  options?: TreeOptions
+): Promise<TreePage> => {
+  return invoke<TreePage>("get_tree_page", {
+    directoryPath,
+    cursor,
+    pageSize,
+    options,
+  });
+};
+
+// Nodes arrive through "tree-stream-batch" and "tree-stream-progress" events tagged with jobId
+export const streamTreeData = async (
+  jobId: string,
+  directoryPath: string,
+  maxDepth?: number
+): Promise<TreeStreamSummary> => {
+  return invoke<TreeStreamSummary>("stream_tree_data", {
+    jobId,
+    directoryPath,
+    maxDepth,
+  });
+};
+
+export const cancelTreeStream = async (jobId: string): Promise<void> => {
+  return invoke("cancel_tree_stream", { jobId });
+};
+
+// Progress arrives through "disk-usage-progress" events tagged with jobId
+export const analyzeDiskUsage = async (
+  jobId: string,
+  path: string,
+  topN?: number,
+  refresh?: boolean
+): Promise<DiskUsageReport> => {
+  return invoke<DiskUsageReport>("analyze_disk_usage", {
+    jobId,
+    path,
+    topN,
+    refresh,
+  });
+};
+
+export const cancelDiskUsage = async (jobId: string): Promise<void> => {
+  return invoke("cancel_disk_usage", { jobId });
+};
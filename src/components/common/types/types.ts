@@ -32,12 +32,418 @@ export interface NodeDetails {
   name: string;
   path: string;
   parentPath?: string;
-  kind: "Directory" | "File";
-  children?: ReadonlyArray<NodeDetails> | NodeDetails[];
+  kind: "Directory" | "File" | "Symlink";
+  // null until the directory has been read; an empty array is an empty directory
+  children?: ReadonlyArray<NodeDetails> | NodeDetails[] | null;
   hidden: boolean;
+  // set when a gitignore-aware listing matched the entry
+  ignored: boolean;
   size: number;
   created: Date;
   modified: Date;
+  // present when kind is "Symlink"
+  symlink?: SymlinkDetails | null;
+  // present when extended metadata was requested on a Unix system
+  unix?: UnixMetadata | null;
+  // detected for files that could be read
+  content_type?: ContentType | null;
+  // present when the entry lives inside a git working tree; folders roll up their contents
+  git_status?: GitStatus | null;
+}
+
+export type GitStatus =
+  | "Clean"
+  | "Ignored"
+  | "Untracked"
+  | "Staged"
+  | "Modified"
+  | "Conflicted";
+
+export type FileCategory =
+  | "SourceCode"
+  | "Image"
+  | "Archive"
+  | "Document"
+  | "Binary"
+  | "Text";
+
+export interface ContentType {
+  mime: string;
+  category: FileCategory;
+  language: string | null;
+  binary: boolean;
+}
+
+export interface UnixMetadata {
+  permissions: string;
+  mode: number;
+  uid: number;
+  gid: number;
+  owner: string | null;
+  group: string | null;
+  inode: number;
+  hard_links: number;
+  device: number;
+  accessed: number;
+}
+
+export interface SymlinkDetails {
+  target: string;
+  broken: boolean;
+  points_to_directory: boolean;
+}
+
+export type SortKey = "Name" | "Size" | "Modified" | "Created" | "Extension";
+
+export interface TreeOptions {
+  sort_by?: SortKey | null;
+  sort_direction?: "Ascending" | "Descending";
+  folders_first?: boolean;
+  show_hidden?: boolean;
+  // glob patterns matched against file names; folders are always kept
+  include?: string[];
+  // glob patterns matched against file and folder names
+  exclude?: string[];
+  gitignore?: "Off" | "Hide" | "Flag";
+  follow_symlinks?: boolean;
+  extended_metadata?: boolean;
+  // sniff the content type of regular files
+  content_types?: boolean;
+  // decorate entries inside git working trees with their status
+  git_status?: boolean;
+}
+
+export interface TreePage {
+  entries: NodeDetails[];
+  // pass back to fetch the following page; null on the last page
+  next_cursor: string | null;
+  total_estimate: number;
+}
+
+export interface TreeStreamBatch {
+  job_id: string;
+  nodes: NodeDetails[];
+}
+
+export interface TreeStreamProgress {
+  job_id: string;
+  entries_found: number;
+  directories_read: number;
+  unreadable_directories: number;
+  processing_time_ms: number;
+}
+
+export interface TreeStreamSummary extends TreeStreamProgress {
+  cancelled: boolean;
+}
+
+export interface DirectoryUsage {
+  path: string;
+  apparent_size: number;
+  disk_size: number;
+  file_count: number;
+  directory_count: number;
+}
+
+export interface FileUsage {
+  path: string;
+  apparent_size: number;
+  disk_size: number;
+}
+
+export interface DiskUsageReport {
+  job_id: string;
+  root: DirectoryUsage;
+  largest_files: FileUsage[];
+  largest_directories: DirectoryUsage[];
+  unreadable_entries: number;
+  cancelled: boolean;
+  from_cache: boolean;
+  processing_time_ms: number;
+}
+
+export interface DiskUsageProgress {
+  job_id: string;
+  files_scanned: number;
+  directories_scanned: number;
+  bytes_scanned: number;
+  processing_time_ms: number;
+}
+
+export type AppErrorKind =
+  | "NotFound"
+  | "PermissionDenied"
+  | "AlreadyExists"
+  | "NotADirectory"
+  | "NotAFile"
+  | "InvalidPattern"
+  | "InvalidArgument"
+  | "Changed"
+  | "Modified"
+  | "Cancelled"
+  | "Io"
+  | "Unsupported"
+  | "Internal";
+
+// What every backend command rejects with
+export interface AppError {
+  kind: AppErrorKind;
+  path: string | null;
+  // OS error code, when the error came from the OS
+  code: number | null;
+  message: string;
+}
+
+export type ConflictPolicy = "Fail" | "Overwrite" | "KeepBoth" | "Skip";
+
+export interface MoveResult {
+  source: string;
+  // where the item ended up, or would have when skipped or failed
+  destination: string;
+  // Replaced means it went over an existing item, which undo can't bring back
+  outcome: "Moved" | "Replaced" | "Skipped" | "Failed";
+  error: AppError | null;
+}
+
+export interface CopyOptions {
+  // recreate symlinks as links instead of copying what they point to
+  preserve_symlinks?: boolean;
+  // resolve every conflict this way instead of asking
+  conflict?: ConflictPolicy | null;
+}
+
+// Payload of "copy-conflict"; answer it with resolveCopyConflict
+export interface CopyConflict {
+  job_id: string;
+  source: string;
+  destination: string;
+  source_is_dir: boolean;
+  destination_is_dir: boolean;
+}
+
+export interface ConflictResolution {
+  // Overwrite merges a folder into an existing folder; Fail stops the copy
+  policy: ConflictPolicy;
+  apply_to_all?: boolean;
+}
+
+export interface CopyProgress {
+  job_id: string;
+  bytes_copied: number;
+  total_bytes: number;
+  files_copied: number;
+  total_files: number;
+  current_path: string | null;
+  processing_time_ms: number;
+}
+
+export interface CopiedItem {
+  source: string;
+  destination: string;
+}
+
+export interface CopySummary {
+  job_id: string;
+  bytes_copied: number;
+  files_copied: number;
+  skipped: string[];
+  // new items at the destination; merges and replacements aren't listed
+  copied: CopiedItem[];
+  errors: AppError[];
+  cancelled: boolean;
+  processing_time_ms: number;
+}
+
+export interface TrashItem {
+  // the item's .trashinfo file; pass it to restoreFromTrash and emptyTrash
+  id: string;
+  name: string;
+  original_path: string;
+  // local time, e.g. "2025-03-01T14:05:09"
+  deletion_date: string | null;
+  trash_dir: string;
+  is_dir: boolean;
+  // files only
+  size: number | null;
+}
+
+// Trash is a delete that went to the trash; permanent deletes can't be undone
+export type OperationKind = "Create" | "Trash" | "Rename" | "Move" | "Copy";
+
+export interface OperationSummary {
+  id: number;
+  kind: OperationKind;
+  // the items as the operation left them
+  paths: string[];
+  recorded_at: number;
+}
+
+export interface JournalState {
+  undo: OperationSummary | null;
+  redo: OperationSummary | null;
+}
+
+// What start_job should do; exactly one key is set
+export type JobRequest =
+  | {
+      Copy: { sources: string[]; destination: string; options?: CopyOptions };
+    }
+  | {
+      Move: {
+        sources: string[];
+        destination: string;
+        conflict?: ConflictPolicy | null;
+      };
+    }
+  // permanent defaults to the trash where the platform has one
+  | { Delete: { paths: string[]; permanent?: boolean | null } };
+
+export type JobKind = "Copy" | "Move" | "Delete";
+
+export type JobState =
+  | "Queued"
+  | "Running"
+  | "Paused"
+  | "Completed"
+  | "Failed"
+  | "Cancelled";
+
+// Payload of "job-progress" and entry of listJobs
+export interface JobInfo {
+  job_id: string;
+  kind: JobKind;
+  state: JobState;
+  sources: string[];
+  destination: string | null;
+  // files for copies, top-level items for moves and trash deletes, entries for
+  // permanent deletes
+  items_done: number;
+  items_total: number;
+  // only counted by copies
+  bytes_done: number;
+  bytes_total: number;
+  current_path: string | null;
+  skipped: string[];
+  errors: AppError[];
+  created_at: number;
+  started_at: number | null;
+  finished_at: number | null;
+}
+
+export type LineEnding = "Lf" | "Crlf" | "Mixed" | "None";
+
+// how the text being saved was read; the matching fields of its FileContent
+export interface ReadFormat {
+  // written as UTF-8 when null
+  encoding: string | null;
+  has_bom: boolean;
+  truncated: boolean;
+  is_binary: boolean;
+}
+
+export interface FileContent {
+  // the text decoded to UTF-8, or a hex/ASCII dump when is_binary
+  content: string;
+  // pass back to writeFileContent so it refuses to overwrite outside changes
+  etag: string;
+  // e.g. "UTF-8", "UTF-16LE", "windows-1252"; null for binary files
+  encoding: string | null;
+  // a byte order mark precedes the text on disk
+  has_bom: boolean;
+  line_ending: LineEnding;
+  size: number;
+  // only the start of the file is in content
+  truncated: boolean;
+  is_binary: boolean;
+}
+
+// Payload of "line-index-progress"
+export interface LineIndexStatus {
+  path: string;
+  etag: string;
+  // bytes when indexing started; lines appended later aren't counted
+  size: number;
+  indexed_lines: number;
+  // set once indexing has finished
+  total_lines: number | null;
+  encoding: string;
+}
+
+export interface LinePage {
+  path: string;
+  etag: string;
+  // counting from 0
+  start_line: number;
+  // without line endings; short at the end of the file or of what's indexed
+  lines: string[];
+  indexed_lines: number;
+  total_lines: number | null;
+  // some lines were too long and only their start is included
+  clipped: boolean;
+}
+
+// Truncated: read again from the start; Rotated: lines end the old file and the
+// new one follows; Missing: the file is gone until it reappears
+export type FollowChange = "Appended" | "Truncated" | "Rotated" | "Missing";
+
+// Payload of "file-follow"
+export interface FollowEvent {
+  follow_id: string;
+  path: string;
+  change: FollowChange;
+  // complete lines, without line endings
+  lines: string[];
+}
+
+export interface WrittenFile {
+  // the etag of what was just written, for the next save
+  etag: string;
+  size: number;
+  backup: string | null;
+}
+
+export interface FileTemplate {
+  // pass as template to create_filesystem_item
+  name: string;
+  // lower case, without the dot
+  extensions: string[];
+  source: "BuiltIn" | "User";
+  // the template file, for user templates
+  path: string | null;
+}
+
+export type CaseTransform = "Lower" | "Upper" | "Title";
+
+// Applied in order: find/replace, case, then the template. Omitted fields take
+// their defaults.
+export interface BulkRenameRules {
+  find?: string | null;
+  // with is_regex, $1 or ${name} insert capture groups
+  replace?: string;
+  is_regex?: boolean;
+  // default true
+  is_case_sensitive?: boolean;
+  case?: CaseTransform | null;
+  // let find/replace and case change the extension too
+  include_extension?: boolean;
+  // tokens: {name} {ext} {parent} {n} {n:3} {mtime[:fmt]} {exif[:fmt]} {date[:fmt]};
+  // default "{name}{ext}"
+  template?: string | null;
+  // default 1
+  counter_start?: number;
+  // default 1
+  counter_step?: number;
+}
+
+export type RenameStatus = "Rename" | "Unchanged" | "Collision" | "InvalidName";
+
+export interface RenamePreview {
+  source: string;
+  new_name: string;
+  destination: string;
+  status: RenameStatus;
+  // with Collision, the path the new name collides with
+  conflict: string | null;
 }
 
 export type DirectoryPaths = {
@@ -59,3 +465,34 @@ export interface ContextMenuItem<T, P = any> {
   props?: P;
   data?: T;
 }
+
+export type ArchiveFormat = "Zip" | "Tar" | "TarGz" | "TarZst";
+
+// Payload of "archive-progress"
+export interface ArchiveProgress {
+  job_id: string;
+  // of the selected files when creating, of the archive when extracting
+  bytes_done: number;
+  bytes_total: number;
+  entries_done: number;
+  current_path: string | null;
+}
+
+export interface ExtractOptions {
+  // for existing files; folders are merged. Defaults to "Fail"
+  conflict?: ConflictPolicy | null;
+  // stop once the contents grow past this; defaults to 100x the archive, min 1 GiB
+  max_bytes?: number | null;
+}
+
+export interface ArchiveSummary {
+  job_id: string;
+  // the archive created, or the folder extracted into
+  path: string;
+  format: ArchiveFormat;
+  entries: number;
+  bytes: number;
+  // entries left out: unsafe paths and links, special files, kept existing files
+  skipped: string[];
+  processing_time_ms: number;
+}